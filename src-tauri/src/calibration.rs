@@ -0,0 +1,409 @@
+// src-tauri/calibration.rs
+
+use serde::{Deserialize, Serialize};
+
+use crate::serial::{joint, round_joint, set_joint, RobotState};
+
+// 관절 각도 보정 구조체 정의 (degrees = slope * raw + intercept)
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct JointCalibration {
+    pub slope: f64,
+    pub intercept: f64,
+}
+
+impl JointCalibration {
+    // 두 측정점으로 선형 보정값 계산 (같은 raw 값이면 오류)
+    pub fn fit(
+        point_a_deg: f64,
+        point_a_raw: u8,
+        point_b_deg: f64,
+        point_b_raw: u8,
+    ) -> Result<Self, String> {
+        if !point_a_deg.is_finite() || !point_b_deg.is_finite() {
+            return Err("보정 각도는 유한한 값이어야 합니다.".into());
+        }
+        if point_a_raw == point_b_raw {
+            return Err("두 보정점의 raw 값이 같아 보정할 수 없습니다.".into());
+        }
+        let slope = (point_b_deg - point_a_deg) / (point_b_raw as f64 - point_a_raw as f64);
+        if slope == 0.0 {
+            return Err("두 보정점의 각도가 같아 보정할 수 없습니다.".into());
+        }
+        Ok(Self {
+            slope,
+            intercept: point_a_deg - slope * point_a_raw as f64,
+        })
+    }
+
+    // raw 값을 각도로 변환
+    pub fn degrees(&self, raw: u8) -> f64 {
+        self.slope * raw as f64 + self.intercept
+    }
+
+    // 각도를 raw 값으로 변환 (반올림, 범위 밖은 u8 범위로 제한)
+    pub fn raw(&self, degrees: f64) -> u8 {
+        round_joint((degrees - self.intercept) / self.slope)
+    }
+
+    // 관절 제한 범위 안의 확인용 각도 목표 (범위의 1/4, 1/2, 3/4 지점을 각도로 변환)
+    pub fn verify_setpoints(&self, min: u8, max: u8) -> Vec<f64> {
+        let (low, high) = (self.degrees(min), self.degrees(max));
+        VERIFY_FRACTIONS
+            .iter()
+            .map(|fraction| low + (high - low) * fraction)
+            .collect()
+    }
+
+    // 확인 지점 결과 작성 (읽은 raw 값을 각도로 바꿔 목표와 비교)
+    pub fn verify_point(&self, expected_deg: f64, read_raw: Option<u8>) -> CalibrationPoint {
+        let read_deg = read_raw.map(|raw| self.degrees(raw));
+        CalibrationPoint {
+            expected_deg,
+            commanded_raw: self.raw(expected_deg),
+            read_raw,
+            read_deg,
+            error_deg: read_deg.map(|degrees| degrees - expected_deg),
+        }
+    }
+}
+
+// 보정 확인 시 관절 범위 안에서 사용할 지점 (범위 비율)
+const VERIFY_FRACTIONS: [f64; 3] = [0.25, 0.5, 0.75];
+
+// 보정 확인 한 지점 결과 (read_raw: 대기 중 마지막으로 읽은 값)
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct CalibrationPoint {
+    pub expected_deg: f64,
+    pub commanded_raw: u8,
+    pub read_raw: Option<u8>,
+    pub read_deg: Option<f64>,
+    pub error_deg: Option<f64>,
+}
+
+// 관절별 보정 확인 결과
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct CalibrationReport {
+    pub joint: u8,
+    pub points: Vec<CalibrationPoint>,
+    // 읽은 지점 중 가장 큰 오차 절댓값 (하나도 읽지 못하면 None)
+    pub max_error_deg: Option<f64>,
+}
+
+impl CalibrationReport {
+    pub fn new(joint: u8, points: Vec<CalibrationPoint>) -> Self {
+        let max_error_deg = points
+            .iter()
+            .filter_map(|point| point.error_deg)
+            .map(f64::abs)
+            .reduce(f64::max);
+        Self {
+            joint,
+            points,
+            max_error_deg,
+        }
+    }
+}
+
+// 슬라이더 정규화 값(0.0~1.0)을 관절 제한 범위로 옮기기 전 적용하는 응답 곡선
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum ResponseCurve {
+    Linear,
+    // value^gamma (1보다 크면 아래쪽, 작으면 위쪽 끝에서 세밀하게 조정)
+    Exponential { gamma: f32 },
+    // (입력, 출력) 점 사이 선형 보간 (입력 0.0과 1.0을 포함하고 입력 순으로 정렬)
+    Points(Vec<(f32, f32)>),
+}
+
+impl ResponseCurve {
+    // 곡선 검사 (증가하는 곡선만 허용)
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ResponseCurve::Linear => Ok(()),
+            ResponseCurve::Exponential { gamma } => {
+                if gamma.is_finite() && *gamma > 0.0 {
+                    Ok(())
+                } else {
+                    Err("감마 값은 0보다 큰 유한한 값이어야 합니다.".into())
+                }
+            }
+            ResponseCurve::Points(points) => {
+                if points.len() < 2 {
+                    return Err("응답 곡선에는 점이 2개 이상 필요합니다.".into());
+                }
+                if points
+                    .iter()
+                    .any(|&(x, y)| !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y))
+                {
+                    return Err("응답 곡선의 점은 0.0에서 1.0 사이여야 합니다.".into());
+                }
+                if points[0].0 != 0.0 || points[points.len() - 1].0 != 1.0 {
+                    return Err("응답 곡선은 입력 0.0과 1.0을 포함해야 합니다.".into());
+                }
+                if points
+                    .windows(2)
+                    .any(|pair| pair[1].0 <= pair[0].0 || pair[1].1 < pair[0].1)
+                {
+                    return Err("응답 곡선은 입력 순으로 정렬되고 증가해야 합니다.".into());
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // 정규화 값에 곡선 적용 (검사를 통과한 곡선 기준, 결과는 0.0~1.0)
+    pub fn apply(&self, value: f32) -> f32 {
+        let value = value.clamp(0.0, 1.0);
+        match self {
+            ResponseCurve::Linear => value,
+            ResponseCurve::Exponential { gamma } => value.powf(*gamma),
+            ResponseCurve::Points(points) => points
+                .windows(2)
+                .find(|pair| value <= pair[1].0)
+                .map(|pair| {
+                    let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                    y0 + (y1 - y0) * (value - x0) / (x1 - x0)
+                })
+                .unwrap_or(value),
+        }
+    }
+}
+
+// 관절 표시 단위 구조체 정의 (단위 값 = raw * scale)
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct JointUnit {
+    // 표시 단위 이름 ("deg", "mm", "%" 등)
+    pub label: String,
+    pub scale: f64,
+}
+
+impl Default for JointUnit {
+    fn default() -> Self {
+        Self {
+            label: "raw".into(),
+            scale: 1.0,
+        }
+    }
+}
+
+impl JointUnit {
+    // 단위 검사 (빈 이름이나 0, 무한대 배율은 변환할 수 없음)
+    pub fn validate(&self) -> Result<(), String> {
+        if self.label.trim().is_empty() {
+            return Err("단위 이름이 비어 있습니다.".into());
+        }
+        if !self.scale.is_finite() || self.scale == 0.0 {
+            return Err("단위 배율은 0이 아닌 유한한 값이어야 합니다.".into());
+        }
+        Ok(())
+    }
+
+    // 단위 값을 raw 값으로 변환 (반올림, u8 범위를 벗어나면 오류)
+    pub fn raw(&self, value: f64) -> Result<u8, String> {
+        let raw = (value / self.scale).round();
+        if !(u8::MIN as f64..=u8::MAX as f64).contains(&raw) {
+            return Err(format!(
+                "{} {}은(는) raw 범위(0~255)를 벗어납니다.",
+                value, self.label
+            ));
+        }
+        Ok(raw as u8)
+    }
+}
+
+// 관절 정보 구조체 정의 (표시 단위와 raw 제한 범위)
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct JointInfo {
+    pub joint: u8,
+    pub unit: JointUnit,
+    pub min: u8,
+    pub max: u8,
+}
+
+// 팔 한 관절의 raw-각도 대응과 허용 raw 범위 (min, max 포함)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct JointMapping {
+    pub calibration: JointCalibration,
+    pub min: u8,
+    pub max: u8,
+}
+
+// 대상 팔에 없는 관절 처리 방식
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum MissingJointPolicy {
+    // 관절 값을 0으로 보냄 (대상 팔 범위가 있으면 그 최솟값)
+    Drop,
+    // 변환 실패로 처리
+    Error,
+}
+
+// 한 팔의 녹화 프레임을 다른 팔의 raw 공간으로 변환하는 함수 (각도 경유, 대상 범위로 제한)
+pub fn adapt_frames(
+    frames: &[RobotState],
+    source: &[Option<JointMapping>; 6],
+    target: &[Option<JointMapping>; 6],
+    missing_joints: MissingJointPolicy,
+) -> Result<Vec<RobotState>, String> {
+    for mapping in source.iter().chain(target).flatten() {
+        let slope = mapping.calibration.slope;
+        if !slope.is_finite() || slope == 0.0 || !mapping.calibration.intercept.is_finite() {
+            return Err("관절 보정값이 올바르지 않습니다.".into());
+        }
+        if mapping.min > mapping.max {
+            return Err("관절 범위의 최솟값이 최댓값보다 큽니다.".into());
+        }
+    }
+
+    frames
+        .iter()
+        .map(|frame| {
+            let mut adapted = frame.clone();
+            for index in 1..=6 {
+                let slot = index as usize - 1;
+                let value = match (source[slot], target[slot]) {
+                    (Some(source), Some(target)) => {
+                        let degrees = source.calibration.degrees(joint(frame, index));
+                        target
+                            .calibration
+                            .raw(degrees)
+                            .clamp(target.min, target.max)
+                    }
+                    (_, target) if missing_joints == MissingJointPolicy::Drop => {
+                        target.map_or(0, |target| target.min)
+                    }
+                    _ => {
+                        return Err(format!(
+                            "관절 {}의 대응 정보가 원본 또는 대상 팔에 없습니다.",
+                            index
+                        ))
+                    }
+                };
+                set_joint(&mut adapted, index, value);
+            }
+            Ok(adapted)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::state;
+
+    #[test]
+    fn fitted_mapping_reproduces_calibration_points() {
+        let calibration = JointCalibration::fit(-90.0, 20, 90.0, 200).unwrap();
+        assert!((calibration.degrees(20) + 90.0).abs() < 1e-9);
+        assert!((calibration.degrees(200) - 90.0).abs() < 1e-9);
+        assert_eq!(calibration.raw(-90.0), 20);
+        assert_eq!(calibration.raw(90.0), 200);
+
+        assert!(JointCalibration::fit(0.0, 5, 10.0, 5).is_err());
+        assert!(JointCalibration::fit(10.0, 5, 10.0, 6).is_err());
+        assert!(JointCalibration::fit(f64::NAN, 5, 10.0, 6).is_err());
+    }
+
+    #[test]
+    fn adapt_frames_remaps_between_arm_mappings() {
+        // 원본: 각도 = raw - 100, 대상: 각도 = 2 * raw - 200 (raw 0~140)
+        let source = JointMapping {
+            calibration: JointCalibration {
+                slope: 1.0,
+                intercept: -100.0,
+            },
+            min: 0,
+            max: 255,
+        };
+        let target = JointMapping {
+            calibration: JointCalibration {
+                slope: 2.0,
+                intercept: -200.0,
+            },
+            min: 0,
+            max: 140,
+        };
+        let sources = [Some(source); 6];
+        let mut targets = [Some(target); 6];
+        let frame = RobotState {
+            joint_2: 200,
+            ..state(120)
+        };
+
+        let frames = [frame];
+        let adapted = adapt_frames(&frames, &sources, &targets, MissingJointPolicy::Error).unwrap();
+        // 20도 → raw 110, 100도 → raw 150은 대상 범위로 제한
+        assert_eq!(adapted[0].joint_1, 110);
+        assert_eq!(adapted[0].joint_2, 140);
+        assert_eq!(adapted[0].robot_speed, 50);
+
+        targets[5] = None;
+        assert!(adapt_frames(&frames, &sources, &targets, MissingJointPolicy::Error).is_err());
+        let dropped = adapt_frames(&frames, &sources, &targets, MissingJointPolicy::Drop).unwrap();
+        assert_eq!(dropped[0].joint_6, 0);
+    }
+
+    #[test]
+    fn exponential_curve_maps_midpoint() {
+        let curve = ResponseCurve::Exponential { gamma: 2.0 };
+        curve.validate().unwrap();
+        assert!((curve.apply(0.5) - 0.25).abs() < 1e-6);
+        assert_eq!(curve.apply(0.0), 0.0);
+        assert_eq!(curve.apply(1.0), 1.0);
+
+        let points = ResponseCurve::Points(vec![(0.0, 0.0), (0.5, 0.2), (1.0, 1.0)]);
+        points.validate().unwrap();
+        assert!((points.apply(0.75) - 0.6).abs() < 1e-6);
+        assert!(ResponseCurve::Points(vec![(0.0, 0.5), (1.0, 0.2)])
+            .validate()
+            .is_err());
+        assert!(ResponseCurve::Exponential { gamma: 0.0 }
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn linear_axis_millimetres_convert_to_raw() {
+        // raw 1당 0.5mm인 직선 축
+        let unit = JointUnit {
+            label: "mm".into(),
+            scale: 0.5,
+        };
+        unit.validate().unwrap();
+        assert_eq!(unit.raw(60.0), Ok(120));
+        assert_eq!(unit.raw(60.2), Ok(120));
+        assert!(unit.raw(200.0).is_err());
+        assert!(JointUnit {
+            label: "mm".into(),
+            scale: 0.0,
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn setpoints_stay_inside_limits() {
+        // raw 0~200이 -90~90도
+        let calibration = JointCalibration::fit(-90.0, 0, 90.0, 200).unwrap();
+        let setpoints = calibration.verify_setpoints(20, 180);
+        assert_eq!(setpoints.len(), 3);
+        for degrees in setpoints {
+            assert!((20..=180).contains(&calibration.raw(degrees)));
+        }
+    }
+
+    #[test]
+    fn report_tracks_largest_error() {
+        let calibration = JointCalibration::fit(0.0, 0, 180.0, 180).unwrap();
+        let report = CalibrationReport::new(
+            2,
+            vec![
+                calibration.verify_point(45.0, Some(45)),
+                calibration.verify_point(90.0, Some(93)),
+                calibration.verify_point(135.0, None),
+            ],
+        );
+        assert_eq!(report.points[0].error_deg, Some(0.0));
+        assert_eq!(report.points[1].commanded_raw, 90);
+        assert_eq!(report.points[2].error_deg, None);
+        assert_eq!(report.max_error_deg, Some(3.0));
+    }
+}
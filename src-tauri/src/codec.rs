@@ -0,0 +1,1055 @@
+// src-tauri/codec.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::serial::RobotState;
+
+// 기본 상태 패킷 길이 (헤드 + 13바이트 + 테일)
+const BASE_FRAME_LEN: usize = 15;
+// 디지털 입출력 압축 패킷 길이 (헤드 + 관절 6 + 입출력 1 + 속도 1 + 테일)
+const PACKED_FRAME_LEN: usize = 10;
+// 타임스탬프 길이 (리틀 엔디언 u32)
+const TIMESTAMP_LEN: usize = 4;
+// 헤드와 테일을 제외한 페이로드 길이 상한
+pub const MAX_PAYLOAD_LEN: usize = 64;
+// 타임스탬프 위치 상한 (패킷 길이가 페이로드 상한을 넘지 않도록)
+const MAX_TIMESTAMP_OFFSET: usize = MAX_PAYLOAD_LEN - TIMESTAMP_LEN;
+// 전원 전압 길이 (리틀 엔디언 u16)
+const VOLTAGE_LEN: usize = 2;
+
+// 디지털 입출력 배치 방식
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum IoLayout {
+    // 입출력 하나당 1바이트 (기본)
+    Expanded,
+    // 입출력 6개를 1바이트에 비트로 압축 (비트 0~2: 입력 1~3, 비트 3~5: 출력 1~3)
+    Packed,
+}
+
+impl IoLayout {
+    // 타임스탬프 없는 상태 패킷 길이
+    pub fn base_frame_len(self) -> usize {
+        match self {
+            IoLayout::Expanded => BASE_FRAME_LEN,
+            IoLayout::Packed => PACKED_FRAME_LEN,
+        }
+    }
+}
+
+// 패킷 해석 설정 기준 상태 패킷 길이 (가장 뒤 필드 다음이 테일)
+pub fn frame_len(config: &DecoderConfig) -> usize {
+    config
+        .fields()
+        .iter()
+        .filter_map(|&(_, offset, len, _)| offset.map(|offset| offset + len + 1))
+        .fold(config.io_layout.base_frame_len(), usize::max)
+}
+
+// 헤드와 테일 사이 바이트의 XOR 체크섬 계산 함수 (체크섬 바이트 자신은 제외)
+pub fn xor_checksum(frame: &[u8], checksum_offset: usize) -> u8 {
+    frame[1..frame.len() - 1]
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i + 1 != checksum_offset)
+        .fold(0, |checksum, (_, &byte)| checksum ^ byte)
+}
+
+// 디지털 입출력 6개를 1바이트로 압축하는 함수
+pub fn pack_io(robot_state: &RobotState) -> u8 {
+    [
+        robot_state.digital_input_1,
+        robot_state.digital_input_2,
+        robot_state.digital_input_3,
+        robot_state.digital_output_1,
+        robot_state.digital_output_2,
+        robot_state.digital_output_3,
+    ]
+    .iter()
+    .enumerate()
+    .fold(0, |byte, (bit, &on)| byte | ((on as u8) << bit))
+}
+
+// 압축된 입출력 바이트를 풀어 상태에 반영하는 함수
+pub fn unpack_io(byte: u8, robot_state: &mut RobotState) {
+    robot_state.digital_input_1 = byte & (1 << 0) != 0;
+    robot_state.digital_input_2 = byte & (1 << 1) != 0;
+    robot_state.digital_input_3 = byte & (1 << 2) != 0;
+    robot_state.digital_output_1 = byte & (1 << 3) != 0;
+    robot_state.digital_output_2 = byte & (1 << 4) != 0;
+    robot_state.digital_output_3 = byte & (1 << 5) != 0;
+}
+
+// 로봇 명령 패킷 생성 함수
+pub fn encode_command(robot_state: &RobotState, io_layout: IoLayout) -> Vec<u8> {
+    let mut data = vec![0u8; io_layout.base_frame_len()];
+    data[0] = 253;
+    data[1] = robot_state.joint_1;
+    data[2] = robot_state.joint_2;
+    data[3] = robot_state.joint_3;
+    data[4] = robot_state.joint_4;
+    data[5] = robot_state.joint_5;
+    data[6] = robot_state.joint_6;
+    match io_layout {
+        IoLayout::Expanded => {
+            data[7] = robot_state.digital_input_1 as u8;
+            data[8] = robot_state.digital_input_2 as u8;
+            data[9] = robot_state.digital_input_3 as u8;
+            data[10] = robot_state.digital_output_1 as u8;
+            data[11] = robot_state.digital_output_2 as u8;
+            data[12] = robot_state.digital_output_3 as u8;
+            data[13] = robot_state.robot_speed;
+            data[14] = 254;
+        }
+        IoLayout::Packed => {
+            data[7] = pack_io(robot_state);
+            data[8] = robot_state.robot_speed;
+            data[9] = 254;
+        }
+    }
+    data
+}
+
+// 변경 필드 명령 패킷에 담을 최대 필드 수 (넘으면 전체 패킷 전송)
+const MAX_DELTA_FIELDS: usize = 3;
+
+// 변경 필드 명령 패킷 생성 함수 (opcode, 필드 수, (위치, 값)..., 254)
+// 직전 전체 패킷과 길이가 다르거나 바뀐 필드가 많아 더 짧지 않으면 None
+pub fn encode_delta(previous: &[u8], frame: &[u8], opcode: u8) -> Option<Vec<u8>> {
+    if previous.len() != frame.len() {
+        return None;
+    }
+    let changed: Vec<(usize, u8)> = (1..frame.len() - 1)
+        .filter(|&index| previous[index] != frame[index])
+        .map(|index| (index, frame[index]))
+        .collect();
+    if changed.len() > MAX_DELTA_FIELDS || 3 + 2 * changed.len() >= frame.len() {
+        return None;
+    }
+    let mut delta = Vec::with_capacity(3 + 2 * changed.len());
+    delta.push(opcode);
+    delta.push(changed.len() as u8);
+    for (index, value) in changed {
+        delta.push(index as u8);
+        delta.push(value);
+    }
+    delta.push(254);
+    Some(delta)
+}
+
+// 사용자 정의 명령 페이로드 필드 형식 (여러 바이트 값은 리틀 엔디언)
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum PayloadFieldType {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    // 0 또는 1
+    Bool,
+}
+
+impl PayloadFieldType {
+    pub fn len(self) -> usize {
+        match self {
+            PayloadFieldType::U8 | PayloadFieldType::I8 | PayloadFieldType::Bool => 1,
+            PayloadFieldType::U16 | PayloadFieldType::I16 => 2,
+            PayloadFieldType::U32 | PayloadFieldType::I32 => 4,
+        }
+    }
+
+    fn range(self) -> (i64, i64) {
+        match self {
+            PayloadFieldType::U8 => (0, u8::MAX as i64),
+            PayloadFieldType::I8 => (i8::MIN as i64, i8::MAX as i64),
+            PayloadFieldType::U16 => (0, u16::MAX as i64),
+            PayloadFieldType::I16 => (i16::MIN as i64, i16::MAX as i64),
+            PayloadFieldType::U32 => (0, u32::MAX as i64),
+            PayloadFieldType::I32 => (i32::MIN as i64, i32::MAX as i64),
+            PayloadFieldType::Bool => (0, 1),
+        }
+    }
+}
+
+// 사용자 정의 명령 페이로드 필드
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PayloadField {
+    pub name: String,
+    pub kind: PayloadFieldType,
+}
+
+// 사용자 정의 명령 (opcode, 필드 순서대로 채운 페이로드, 254)
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CustomCommand {
+    pub opcode: u8,
+    pub fields: Vec<PayloadField>,
+}
+
+// 사용자 정의 명령 패킷 생성 함수 (모든 필드가 있고 형식 범위 안이어야 함)
+pub fn encode_custom(
+    command: &CustomCommand,
+    payload: &HashMap<String, i64>,
+) -> Result<Vec<u8>, String> {
+    if let Some(name) = payload
+        .keys()
+        .find(|name| !command.fields.iter().any(|field| &field.name == *name))
+    {
+        return Err(format!("정의되지 않은 필드입니다: {}", name));
+    }
+    let mut frame = vec![command.opcode];
+    for field in &command.fields {
+        let value = *payload
+            .get(&field.name)
+            .ok_or_else(|| format!("필드 값이 없습니다: {}", field.name))?;
+        let (min, max) = field.kind.range();
+        if !(min..=max).contains(&value) {
+            return Err(format!(
+                "필드 {} 값은 {}에서 {} 사이여야 합니다.",
+                field.name, min, max
+            ));
+        }
+        frame.extend_from_slice(&value.to_le_bytes()[..field.kind.len()]);
+    }
+    frame.push(254);
+    Ok(frame)
+}
+
+// 페이로드 이스케이프 바이트
+pub const ESCAPE_BYTE: u8 = 251;
+// 이스케이프된 바이트 변환 마스크
+const ESCAPE_MASK: u8 = 0x20;
+
+// 패킷 인코딩 방식
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum FrameEncoding {
+    // 페이로드를 그대로 전송
+    Plain,
+    // 페이로드의 253/254/251 바이트를 251 + (바이트 ^ 0x20)으로 이스케이프
+    Escaped,
+}
+
+// 헤드와 테일 사이 페이로드 이스케이프 함수
+pub fn escape_frame(frame: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(frame.len() * 2);
+    escaped.push(frame[0]);
+    for &byte in &frame[1..frame.len() - 1] {
+        if matches!(byte, 253 | 254 | ESCAPE_BYTE) {
+            escaped.push(ESCAPE_BYTE);
+            escaped.push(byte ^ ESCAPE_MASK);
+        } else {
+            escaped.push(byte);
+        }
+    }
+    escaped.push(frame[frame.len() - 1]);
+    escaped
+}
+
+// 이스케이프된 페이로드 복원 함수 (잘못된 이스케이프 시 None)
+fn unescape_frame(frame: &[u8]) -> Option<Vec<u8>> {
+    let mut unescaped = Vec::with_capacity(frame.len());
+    unescaped.push(frame[0]);
+    let mut payload = frame[1..frame.len() - 1].iter();
+    while let Some(&byte) = payload.next() {
+        if byte == ESCAPE_BYTE {
+            unescaped.push(payload.next()? ^ ESCAPE_MASK);
+        } else {
+            unescaped.push(byte);
+        }
+    }
+    unescaped.push(frame[frame.len() - 1]);
+    Some(unescaped)
+}
+
+// 패킷 필드 배치 검사 함수 (기본 필드 뒤, 위치 상한 이내, 필드끼리 겹치지 않음)
+pub fn check_frame_layout(config: &DecoderConfig) -> Result<(), String> {
+    let min_offset = config.io_layout.base_frame_len() - 1;
+    let fields = config.fields();
+    for (i, &(name, offset, len, max_offset)) in fields.iter().enumerate() {
+        let Some(offset) = offset else {
+            continue;
+        };
+        if !(min_offset..=max_offset).contains(&offset) {
+            return Err(format!(
+                "{} 위치는 {}에서 {} 사이여야 합니다.",
+                name, min_offset, max_offset
+            ));
+        }
+        for &(other_name, other_offset, other_len, _) in &fields[..i] {
+            if other_offset.is_some_and(|other| offset < other + other_len && other < offset + len)
+            {
+                return Err(format!("{} 위치가 {} 위치와 겹칩니다.", name, other_name));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 컨트롤러 오류 패킷 설정 구조체 정의
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct ErrorFrameConfig {
+    // 오류 패킷 헤드 바이트 (None이면 오류 패킷 미사용)
+    pub opcode: Option<u8>,
+    // 오류 코드별 설명
+    pub messages: HashMap<u16, String>,
+}
+
+// 오류 패킷 헤더 길이 (헤드 + 코드 2바이트 + 메시지 길이 1바이트)
+const ERROR_FRAME_HEADER_LEN: usize = 4;
+
+// 장치 식별 응답 패킷 길이 (헤드 + 프로토콜 버전 + 테일)
+const IDENTITY_FRAME_LEN: usize = 3;
+
+// 오류 패킷 해석 함수 (코드는 리틀 엔디언)
+fn decode_error_frame(frame: &[u8], config: &ErrorFrameConfig) -> String {
+    if frame.last() != Some(&254) {
+        return "유효하지 않은 오류 패킷: 잘못된 테일 바이트".into();
+    }
+
+    let code = u16::from_le_bytes([frame[1], frame[2]]);
+    let body = &frame[ERROR_FRAME_HEADER_LEN..frame.len() - 1];
+    let meaning = config
+        .messages
+        .get(&code)
+        .map(String::as_str)
+        .unwrap_or("알 수 없는 오류");
+    if body.is_empty() {
+        format!("컨트롤러 오류 0x{:04X} ({})", code, meaning)
+    } else {
+        format!(
+            "컨트롤러 오류 0x{:04X} ({}): {}",
+            code,
+            meaning,
+            String::from_utf8_lossy(body)
+        )
+    }
+}
+
+// 컨트롤러 밀리초 시계 기준 두 타임스탬프 사이 간격 (u32 시계가 한 바퀴 돌아도 유지)
+pub fn device_interval(previous: u32, current: u32) -> Duration {
+    Duration::from_millis(current.wrapping_sub(previous) as u64)
+}
+
+// 상태 패킷 해석 함수
+fn decode_state(frame: &[u8], config: &DecoderConfig) -> RobotState {
+    // 타임스탬프 파싱 (리틀 엔디언)
+    let device_timestamp = config.timestamp_offset.map(|offset| {
+        let mut bytes = [0u8; TIMESTAMP_LEN];
+        bytes.copy_from_slice(&frame[offset..offset + TIMESTAMP_LEN]);
+        u32::from_le_bytes(bytes)
+    });
+    // 전원 전압 파싱 (리틀 엔디언)
+    let supply_voltage = config
+        .voltage_offset
+        .map(|offset| u16::from_le_bytes([frame[offset], frame[offset + 1]]));
+    let fault_bits = config.fault_offset.map(|offset| frame[offset]);
+
+    match config.io_layout {
+        IoLayout::Expanded => RobotState {
+            joint_1: frame[1],
+            joint_2: frame[2],
+            joint_3: frame[3],
+            joint_4: frame[4],
+            joint_5: frame[5],
+            joint_6: frame[6],
+            digital_input_1: frame[7] != 0,
+            digital_input_2: frame[8] != 0,
+            digital_input_3: frame[9] != 0,
+            digital_output_1: frame[10] != 0,
+            digital_output_2: frame[11] != 0,
+            digital_output_3: frame[12] != 0,
+            robot_speed: frame[13],
+            device_timestamp,
+            supply_voltage,
+            fault_bits,
+        },
+        IoLayout::Packed => {
+            let mut robot_state = RobotState {
+                joint_1: frame[1],
+                joint_2: frame[2],
+                joint_3: frame[3],
+                joint_4: frame[4],
+                joint_5: frame[5],
+                joint_6: frame[6],
+                digital_input_1: false,
+                digital_input_2: false,
+                digital_input_3: false,
+                digital_output_1: false,
+                digital_output_2: false,
+                digital_output_3: false,
+                robot_speed: frame[8],
+                device_timestamp,
+                supply_voltage,
+                fault_bits,
+            };
+            unpack_io(frame[7], &mut robot_state);
+            robot_state
+        }
+    }
+}
+
+// 패킷 사이 여분 바이트 처리 방식
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum FrameTrailingMode {
+    // 여분 바이트를 오류로 보고
+    Strict,
+    // 다음 헤드 바이트까지 조용히 버림
+    ConsumeToNextHead,
+}
+
+// 수신 버퍼에서 꺼낸 패킷
+enum RawFrame {
+    State(Vec<u8>),
+    // 컨트롤러 오류 패킷 해석 결과
+    Error(String),
+    // 장치 식별 응답의 프로토콜 버전
+    Identity(u8),
+    // 테일 바이트가 잘못된 상태 패킷 후보
+    Invalid(Vec<u8>),
+    // 엄격 모드에서 버린 패킷 사이 여분 바이트 수
+    Stray(usize),
+}
+
+// 수신 버퍼에서 패킷 하나를 꺼내는 함수 (데이터가 부족하면 None)
+fn take_frame(
+    pending: &mut Vec<u8>,
+    frame_len: usize,
+    error_frames: &ErrorFrameConfig,
+    identity_opcode: Option<u8>,
+    trailing_mode: FrameTrailingMode,
+    encoding: FrameEncoding,
+) -> Option<RawFrame> {
+    // 헤드 바이트 이전의 여분 바이트 버리기
+    let start = pending
+        .iter()
+        .position(|&byte| {
+            byte == 253 || error_frames.opcode == Some(byte) || identity_opcode == Some(byte)
+        })
+        .unwrap_or(pending.len());
+    if start > 0 {
+        pending.drain(..start);
+        if trailing_mode == FrameTrailingMode::Strict {
+            return Some(RawFrame::Stray(start));
+        }
+    }
+    if pending.is_empty() {
+        return None;
+    }
+
+    if pending[0] == 253 && encoding == FrameEncoding::Escaped {
+        // 페이로드에는 253/254가 나타나지 않으므로 다음 253/254가 패킷 경계
+        let Some(end) = pending[1..]
+            .iter()
+            .position(|&byte| byte == 253 || byte == 254)
+        else {
+            // 모든 바이트가 이스케이프되어도 넘을 수 없는 길이면 버리기
+            if pending.len() > 2 * frame_len {
+                return Some(RawFrame::Invalid(std::mem::take(pending)));
+            }
+            return None;
+        };
+        let end = end + 1;
+        if pending[end] == 253 {
+            // 테일 없이 새 헤드가 시작되면 앞 패킷은 손상된 것
+            return Some(RawFrame::Invalid(pending.drain(..end).collect()));
+        }
+        let raw: Vec<u8> = pending.drain(..=end).collect();
+        return match unescape_frame(&raw) {
+            Some(frame) if frame.len() == frame_len => Some(RawFrame::State(frame)),
+            _ => Some(RawFrame::Invalid(raw)),
+        };
+    }
+
+    if pending[0] == 253 {
+        if pending.len() < frame_len {
+            return None;
+        }
+        if pending[frame_len - 1] != 254 {
+            // 헤드 바이트만 버리고 다음 헤드부터 다시 동기화
+            let candidate = pending[..frame_len].to_vec();
+            pending.remove(0);
+            return Some(RawFrame::Invalid(candidate));
+        }
+        return Some(RawFrame::State(pending.drain(..frame_len).collect()));
+    }
+
+    // 장치 식별 응답 패킷
+    if identity_opcode == Some(pending[0]) {
+        if pending.len() < IDENTITY_FRAME_LEN {
+            return None;
+        }
+        let frame: Vec<u8> = pending.drain(..IDENTITY_FRAME_LEN).collect();
+        return Some(if frame[2] == 254 {
+            RawFrame::Identity(frame[1])
+        } else {
+            RawFrame::Invalid(frame)
+        });
+    }
+
+    // 컨트롤러 오류 패킷
+    if pending.len() < ERROR_FRAME_HEADER_LEN {
+        return None;
+    }
+    let total_len = ERROR_FRAME_HEADER_LEN + pending[3] as usize + 1;
+    if pending.len() < total_len {
+        return None;
+    }
+    let frame: Vec<u8> = pending.drain(..total_len).collect();
+    Some(RawFrame::Error(decode_error_frame(&frame, error_frames)))
+}
+
+// 패킷 해석 설정 (관리자의 현재 설정은 decoder_config로 복사)
+#[derive(Clone)]
+pub struct DecoderConfig {
+    pub timestamp_offset: Option<usize>,
+    pub voltage_offset: Option<usize>,
+    pub fault_offset: Option<usize>,
+    pub checksum_offset: Option<usize>,
+    pub io_layout: IoLayout,
+    pub trailing_mode: FrameTrailingMode,
+    pub encoding: FrameEncoding,
+    pub error_frames: ErrorFrameConfig,
+    // 장치 식별 응답 패킷 헤드 바이트 (None이면 식별 응답 미사용)
+    pub identity_opcode: Option<u8>,
+}
+
+impl Default for DecoderConfig {
+    fn default() -> Self {
+        Self {
+            timestamp_offset: None,
+            voltage_offset: None,
+            fault_offset: None,
+            checksum_offset: None,
+            io_layout: IoLayout::Expanded,
+            trailing_mode: FrameTrailingMode::ConsumeToNextHead,
+            encoding: FrameEncoding::Plain,
+            error_frames: ErrorFrameConfig::default(),
+            identity_opcode: None,
+        }
+    }
+}
+
+impl DecoderConfig {
+    // 기본 필드 뒤에 붙는 선택 필드 목록 (이름, 위치, 길이, 위치 상한)
+    fn fields(&self) -> [(&'static str, Option<usize>, usize, usize); 4] {
+        [
+            (
+                "타임스탬프",
+                self.timestamp_offset,
+                TIMESTAMP_LEN,
+                MAX_TIMESTAMP_OFFSET,
+            ),
+            (
+                "전원 전압",
+                self.voltage_offset,
+                VOLTAGE_LEN,
+                MAX_PAYLOAD_LEN - VOLTAGE_LEN,
+            ),
+            ("고장 상태", self.fault_offset, 1, MAX_PAYLOAD_LEN),
+            ("체크섬", self.checksum_offset, 1, MAX_PAYLOAD_LEN),
+        ]
+    }
+}
+
+// 패킷 해석 오류
+#[derive(Clone, PartialEq, Debug)]
+pub enum DecodeError {
+    // 테일 바이트가 잘못되었거나 이스케이프 해제 후 길이가 맞지 않는 패킷
+    InvalidFrame(Vec<u8>),
+    ChecksumMismatch { expected: u8, actual: u8 },
+    // 엄격 모드에서 버린 패킷 사이 여분 바이트 수
+    StrayBytes(usize),
+    // 컨트롤러 오류 패킷 해석 결과
+    Controller(String),
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::InvalidFrame(_) => {
+                write!(f, "유효하지 않은 데이터 패킷: 잘못된 테일 바이트")
+            }
+            DecodeError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "유효하지 않은 데이터 패킷: 체크섬 불일치 (기대값 0x{:02X}, 수신값 0x{:02X})",
+                expected, actual
+            ),
+            DecodeError::StrayBytes(count) => write!(
+                f,
+                "유효하지 않은 데이터 패킷: 패킷 사이 여분 바이트 {}개",
+                count
+            ),
+            DecodeError::Controller(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// 바이트 스트림을 상태 패킷으로 해석하는 디코더 (입출력과 무관)
+pub struct FrameDecoder {
+    config: DecoderConfig,
+    frame_len: usize,
+    pending: Vec<u8>,
+    // 헤드를 찾으려고 바이트를 버린 횟수와 버린 바이트 수
+    resyncs: u64,
+    skipped_bytes: u64,
+    // 마지막으로 받은 장치 식별 응답의 프로토콜 버전
+    identity: Option<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new(config: DecoderConfig) -> Result<Self, String> {
+        check_frame_layout(&config)?;
+        if matches!(config.error_frames.opcode, Some(253) | Some(254)) {
+            return Err("오류 패킷 헤드는 상태 패킷의 헤드/테일(253, 254)과 달라야 합니다.".into());
+        }
+        if let Some(opcode) = config.identity_opcode {
+            if opcode == 253 || opcode == 254 || config.error_frames.opcode == Some(opcode) {
+                return Err(
+                    "식별 응답 헤드는 상태 패킷 헤드/테일 및 오류 패킷 헤드와 달라야 합니다."
+                        .into(),
+                );
+            }
+        }
+        Ok(Self::with_pending(config, Vec::new()))
+    }
+
+    // 검증된 설정과 이전에 남은 바이트로 디코더 생성
+    pub fn with_pending(config: DecoderConfig, pending: Vec<u8>) -> Self {
+        let frame_len = frame_len(&config);
+        Self {
+            config,
+            frame_len,
+            pending,
+            resyncs: 0,
+            skipped_bytes: 0,
+            identity: None,
+        }
+    }
+
+    // 바이트 하나를 넣고 완성된 결과가 있으면 반환
+    // (한 바이트로 결과가 여러 개 생길 수 있으므로 남은 결과는 next_frame으로 꺼냄)
+    pub fn push_byte(&mut self, byte: u8) -> Option<Result<RobotState, DecodeError>> {
+        self.pending.push(byte);
+        self.next_frame()
+    }
+
+    // 여러 바이트를 버퍼에 추가 (결과는 next_frame으로 꺼냄)
+    pub fn extend(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+    }
+
+    // 버퍼에 남은 바이트에서 다음 결과 꺼내기 (데이터가 부족하면 None)
+    pub fn next_frame(&mut self) -> Option<Result<RobotState, DecodeError>> {
+        self.next_raw_frame()
+            .map(|result| result.map(|(_, robot_state)| robot_state))
+    }
+
+    // 다음 결과를 원시 패킷과 함께 꺼내기
+    pub fn next_raw_frame(&mut self) -> Option<Result<(Vec<u8>, RobotState), DecodeError>> {
+        let config = &self.config;
+        // take_frame이 헤드 앞에서 버릴 바이트 수
+        let skipped = self
+            .pending
+            .iter()
+            .take_while(|&&byte| {
+                byte != 253
+                    && config.error_frames.opcode != Some(byte)
+                    && config.identity_opcode != Some(byte)
+            })
+            .count();
+        if skipped > 0 {
+            self.resyncs += 1;
+            self.skipped_bytes += skipped as u64;
+        }
+        let raw_frame = take_frame(
+            &mut self.pending,
+            self.frame_len,
+            &config.error_frames,
+            config.identity_opcode,
+            config.trailing_mode,
+            config.encoding,
+        )?;
+        Some(match raw_frame {
+            RawFrame::State(frame) => match config.checksum_offset {
+                Some(checksum_offset)
+                    if frame[checksum_offset] != xor_checksum(&frame, checksum_offset) =>
+                {
+                    Err(DecodeError::ChecksumMismatch {
+                        expected: xor_checksum(&frame, checksum_offset),
+                        actual: frame[checksum_offset],
+                    })
+                }
+                _ => {
+                    let robot_state = decode_state(&frame, config);
+                    Ok((frame, robot_state))
+                }
+            },
+            RawFrame::Error(message) => Err(DecodeError::Controller(message)),
+            // 식별 응답은 상태 결과 대신 따로 보관하고 다음 패킷으로 넘어감
+            RawFrame::Identity(version) => {
+                self.identity = Some(version);
+                return self.next_raw_frame();
+            }
+            RawFrame::Invalid(frame) => Err(DecodeError::InvalidFrame(frame)),
+            RawFrame::Stray(count) => Err(DecodeError::StrayBytes(count)),
+        })
+    }
+
+    // 받은 장치 식별 응답의 프로토콜 버전 꺼내기
+    pub fn take_identity(&mut self) -> Option<u8> {
+        self.identity.take()
+    }
+
+    // 아직 패킷이 되지 않은 바이트 수
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    // 디코더를 버리고 아직 패킷이 되지 않은 바이트 꺼내기 (다음 읽기에 이어 사용)
+    pub fn into_pending(self) -> Vec<u8> {
+        self.pending
+    }
+
+    // 지금까지 다시 동기화한 횟수와 버린 바이트 수
+    pub fn resync_stats(&self) -> (u64, u64) {
+        (self.resyncs, self.skipped_bytes)
+    }
+}
+
+// 캡처 파일 분석 결과 구조체 정의
+#[derive(Serialize, Debug)]
+pub struct CaptureAnalysis {
+    pub total_bytes: usize,
+    pub valid_frames: usize,
+    // 테일/길이 오류, 체크섬 불일치, 엄격 모드 여분 바이트
+    pub framing_errors: usize,
+    pub controller_errors: usize,
+    // 헤드를 찾으려고 바이트를 버린 횟수와 버린 바이트 수
+    pub resyncs: u64,
+    pub skipped_bytes: u64,
+    // 파일 끝에서 패킷이 되지 못하고 잘린 바이트 수
+    pub truncated_bytes: usize,
+    pub states: Vec<RobotState>,
+}
+
+// 캡처한 바이트열을 패킷 디코더로 분석하는 함수 (포트와 무관)
+pub fn analyze_frames(config: DecoderConfig, bytes: &[u8]) -> Result<CaptureAnalysis, String> {
+    let mut decoder = FrameDecoder::new(config)?;
+    decoder.extend(bytes);
+    let mut analysis = CaptureAnalysis {
+        total_bytes: bytes.len(),
+        valid_frames: 0,
+        framing_errors: 0,
+        controller_errors: 0,
+        resyncs: 0,
+        skipped_bytes: 0,
+        truncated_bytes: 0,
+        states: Vec::new(),
+    };
+    while let Some(result) = decoder.next_frame() {
+        match result {
+            Ok(robot_state) => {
+                analysis.valid_frames += 1;
+                analysis.states.push(robot_state);
+            }
+            Err(DecodeError::Controller(_)) => analysis.controller_errors += 1,
+            Err(_) => analysis.framing_errors += 1,
+        }
+    }
+    (analysis.resyncs, analysis.skipped_bytes) = decoder.resync_stats();
+    analysis.truncated_bytes = decoder.pending_len();
+    Ok(analysis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::state;
+
+    #[test]
+    fn decoder_maps_controller_error_code_to_message() {
+        let mut decoder = FrameDecoder::new(DecoderConfig {
+            error_frames: ErrorFrameConfig {
+                opcode: Some(252),
+                messages: [(0x0012, "과전류".to_string())].into(),
+            },
+            ..DecoderConfig::default()
+        })
+        .unwrap();
+        decoder.extend(&[252, 0x12, 0x00, 2, b'h', b'i', 254]);
+        decoder.extend(&[252, 0x34, 0x00, 0, 254]);
+        decoder.extend(&frame(9));
+
+        assert_eq!(
+            decoder.next_frame().unwrap().unwrap_err(),
+            DecodeError::Controller("컨트롤러 오류 0x0012 (과전류): hi".into())
+        );
+        assert_eq!(
+            decoder.next_frame().unwrap().unwrap_err(),
+            DecodeError::Controller("컨트롤러 오류 0x0034 (알 수 없는 오류)".into())
+        );
+        assert_eq!(decoder.next_frame().unwrap().unwrap().joint_1, 9);
+    }
+
+    #[test]
+    fn decoder_sets_aside_identity_reply() {
+        assert!(FrameDecoder::new(DecoderConfig {
+            identity_opcode: Some(253),
+            ..DecoderConfig::default()
+        })
+        .is_err());
+        let mut decoder = FrameDecoder::new(DecoderConfig {
+            identity_opcode: Some(0xA0),
+            ..DecoderConfig::default()
+        })
+        .unwrap();
+        // 식별 응답 사이에 낀 상태 패킷은 그대로 해석
+        decoder.extend(&[0xA0, 3, 254]);
+        decoder.extend(&frame(9));
+        assert_eq!(decoder.next_frame().unwrap().unwrap().joint_1, 9);
+        assert_eq!(decoder.take_identity(), Some(3));
+        assert_eq!(decoder.take_identity(), None);
+
+        decoder.extend(&[0xA0, 3, 0]);
+        assert!(matches!(
+            decoder.next_frame(),
+            Some(Err(DecodeError::InvalidFrame(_)))
+        ));
+        assert_eq!(decoder.take_identity(), None);
+    }
+
+    #[test]
+    fn escaped_payload_with_head_and_tail_bytes_round_trips() {
+        let robot_state = RobotState {
+            joint_1: 253,
+            joint_2: 254,
+            joint_3: ESCAPE_BYTE,
+            ..state(4)
+        };
+        let plain = encode_command(&robot_state, IoLayout::Expanded);
+        let escaped = escape_frame(&plain);
+        assert_eq!(escaped.len(), plain.len() + 3);
+        assert!(!escaped[1..escaped.len() - 1]
+            .iter()
+            .any(|&byte| byte == 253 || byte == 254));
+        assert_eq!(unescape_frame(&escaped), Some(plain));
+
+        let mut decoder = FrameDecoder::new(DecoderConfig {
+            encoding: FrameEncoding::Escaped,
+            ..DecoderConfig::default()
+        })
+        .unwrap();
+        decoder.extend(&escaped);
+        decoder.extend(&escape_frame(&frame(7)));
+        let decoded = decoder.next_frame().unwrap().unwrap();
+        assert_eq!(
+            (
+                decoded.joint_1,
+                decoded.joint_2,
+                decoded.joint_3,
+                decoded.joint_4
+            ),
+            (253, 254, ESCAPE_BYTE, 4)
+        );
+        assert_eq!(decoder.next_frame().unwrap().unwrap().joint_1, 7);
+        assert!(decoder.next_frame().is_none());
+    }
+
+    #[test]
+    fn oversized_frame_length_is_rejected() {
+        let oversized = DecoderConfig {
+            timestamp_offset: Some(10000),
+            ..DecoderConfig::default()
+        };
+        assert!(check_frame_layout(&oversized).is_err());
+        assert!(FrameDecoder::new(oversized).is_err());
+
+        let largest = DecoderConfig {
+            timestamp_offset: Some(MAX_TIMESTAMP_OFFSET),
+            ..DecoderConfig::default()
+        };
+        assert!(frame_len(&largest) <= MAX_PAYLOAD_LEN + 2);
+        assert!(FrameDecoder::new(largest).is_ok());
+    }
+
+    #[test]
+    fn packed_io_round_trips_all_combinations() {
+        for byte in 0u8..64 {
+            let mut robot_state = state(0);
+            unpack_io(byte, &mut robot_state);
+            assert_eq!(pack_io(&robot_state), byte);
+
+            let frame = encode_command(&robot_state, IoLayout::Packed);
+            assert_eq!(frame.len(), PACKED_FRAME_LEN);
+            let mut decoder = FrameDecoder::new(DecoderConfig {
+                io_layout: IoLayout::Packed,
+                ..DecoderConfig::default()
+            })
+            .unwrap();
+            decoder.extend(&frame);
+            let decoded = decoder.next_frame().unwrap().unwrap();
+            assert_eq!(pack_io(&decoded), byte);
+        }
+
+        let robot_state = RobotState {
+            digital_input_2: true,
+            digital_output_3: true,
+            ..state(0)
+        };
+        assert_eq!(pack_io(&robot_state), 0b100010);
+    }
+
+    // 위치 14에 XOR 체크섬을 붙인 상태 패킷 (`valid`가 거짓이면 체크섬을 틀리게)
+    fn checksummed_frame(joint: u8, valid: bool) -> Vec<u8> {
+        let mut frame = frame(joint);
+        frame.insert(14, 0);
+        let checksum = xor_checksum(&frame, 14);
+        frame[14] = if valid { checksum } else { checksum ^ 1 };
+        frame
+    }
+
+    #[test]
+    fn decoder_validates_xor_checksum() {
+        let mut decoder = FrameDecoder::new(DecoderConfig {
+            checksum_offset: Some(14),
+            ..DecoderConfig::default()
+        })
+        .unwrap();
+        decoder.extend(&checksummed_frame(10, true));
+        decoder.extend(&checksummed_frame(20, false));
+        decoder.extend(&checksummed_frame(30, true));
+
+        assert_eq!(decoder.next_frame().unwrap().unwrap().joint_1, 10);
+        let error = decoder.next_frame().unwrap().unwrap_err();
+        assert!(
+            matches!(error, DecodeError::ChecksumMismatch { expected, actual } if expected == actual ^ 1)
+        );
+        assert!(error.to_string().contains("체크섬"));
+        assert_eq!(decoder.next_frame().unwrap().unwrap().joint_1, 30);
+        assert_eq!(xor_checksum(&[253, 1, 2, 3, 254], 2), 1 ^ 3);
+    }
+
+    // 디코더에 바이트를 하나씩 넣고 나온 결과를 관절 1 값으로 모으기
+    fn feed(decoder: &mut FrameDecoder, bytes: &[u8]) -> Vec<Result<u8, DecodeError>> {
+        let mut results = Vec::new();
+        for &byte in bytes {
+            if let Some(result) = decoder.push_byte(byte) {
+                results.push(result.map(|robot_state| robot_state.joint_1));
+                while let Some(result) = decoder.next_frame() {
+                    results.push(result.map(|robot_state| robot_state.joint_1));
+                }
+            }
+        }
+        results
+    }
+
+    fn frame(joint: u8) -> Vec<u8> {
+        encode_command(&state(joint), IoLayout::Expanded)
+    }
+
+    #[test]
+    fn decoder_reads_clean_stream_and_keeps_partial_frame() {
+        let mut decoder = FrameDecoder::new(DecoderConfig::default()).unwrap();
+        let mut bytes = frame(1);
+        bytes.extend(frame(2));
+        assert_eq!(feed(&mut decoder, &bytes), vec![Ok(1), Ok(2)]);
+        assert_eq!(decoder.pending_len(), 0);
+
+        assert!(feed(&mut decoder, &frame(3)[..7]).is_empty());
+        assert_eq!(decoder.pending_len(), 7);
+        assert_eq!(decoder.resync_stats(), (0, 0));
+    }
+
+    #[test]
+    fn decoder_resyncs_on_misaligned_stream() {
+        let mut bytes = vec![1, 2, 3];
+        bytes.extend(frame(4));
+
+        let mut decoder = FrameDecoder::new(DecoderConfig::default()).unwrap();
+        assert_eq!(feed(&mut decoder, &bytes), vec![Ok(4)]);
+        assert_eq!(decoder.resync_stats(), (3, 3));
+
+        let mut decoder = FrameDecoder::new(DecoderConfig {
+            trailing_mode: FrameTrailingMode::Strict,
+            ..DecoderConfig::default()
+        })
+        .unwrap();
+        let stray = Err(DecodeError::StrayBytes(1));
+        assert_eq!(
+            feed(&mut decoder, &bytes),
+            vec![stray.clone(), stray.clone(), stray, Ok(4)]
+        );
+    }
+
+    #[test]
+    fn decoder_reports_corrupted_frame_and_recovers() {
+        let mut bytes = frame(5);
+        bytes[14] = 0;
+        bytes.extend(frame(6));
+
+        let mut decoder = FrameDecoder::new(DecoderConfig::default()).unwrap();
+        let results = feed(&mut decoder, &bytes);
+        assert!(matches!(results[0], Err(DecodeError::InvalidFrame(_))));
+        assert_eq!(results.last(), Some(&Ok(6)));
+        assert_eq!(decoder.pending_len(), 0);
+    }
+
+    // 변경 필드 명령을 직전 전체 패킷에 적용
+    fn apply_delta(previous: &[u8], delta: &[u8]) -> Vec<u8> {
+        let mut frame = previous.to_vec();
+        let count = delta[1] as usize;
+        for field in delta[2..2 + 2 * count].chunks(2) {
+            frame[field[0] as usize] = field[1];
+        }
+        frame
+    }
+
+    #[test]
+    fn delta_with_one_changed_joint_round_trips() {
+        let previous = encode_command(&state(90), IoLayout::Expanded);
+        let next = encode_command(
+            &RobotState {
+                joint_3: 120,
+                ..state(90)
+            },
+            IoLayout::Expanded,
+        );
+
+        let delta = encode_delta(&previous, &next, 0xF0).unwrap();
+        assert_eq!(delta, vec![0xF0, 1, 3, 120, 254]);
+        assert_eq!(apply_delta(&previous, &delta), next);
+    }
+
+    #[test]
+    fn delta_falls_back_when_too_many_fields_change() {
+        let previous = encode_command(&state(90), IoLayout::Expanded);
+        let next = encode_command(&state(100), IoLayout::Expanded);
+        assert!(encode_delta(&previous, &next, 0xF0).is_none());
+    }
+
+    #[test]
+    fn decodes_little_endian_device_timestamp() {
+        let mut decoder = FrameDecoder::new(DecoderConfig {
+            timestamp_offset: Some(14),
+            ..DecoderConfig::default()
+        })
+        .unwrap();
+        let mut frame = encode_command(&state(90), IoLayout::Expanded);
+        frame.pop();
+        frame.extend(0x1234_5678u32.to_le_bytes());
+        frame.push(254);
+
+        decoder.extend(&frame);
+        let robot_state = decoder.next_frame().unwrap().unwrap();
+        assert_eq!(robot_state.device_timestamp, Some(0x1234_5678));
+        assert_eq!(robot_state.joint_6, 90);
+        assert!(decoder.next_frame().is_none());
+    }
+
+    #[test]
+    fn device_interval_survives_clock_wraparound() {
+        assert_eq!(device_interval(1000, 1016), Duration::from_millis(16));
+        assert_eq!(device_interval(u32::MAX - 5, 10), Duration::from_millis(16));
+    }
+}
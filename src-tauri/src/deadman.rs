@@ -0,0 +1,117 @@
+// src-tauri/deadman.rs
+
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::serial::{lock, AppState, SerialPortManager};
+
+// 데드맨 스위치 신호 확인 간격
+const DEADMAN_CHECK_INTERVAL: Duration = Duration::from_millis(20);
+
+impl SerialPortManager {
+    // 데드맨 스위치 설정 함수 (켜면 첫 신호 전까지 동작 명령 거부, 감시 토큰 반환)
+    pub fn set_deadman(&self, enabled: bool, timeout: Duration) -> Result<u64, String> {
+        if enabled && timeout.is_zero() {
+            return Err("데드맨 스위치 유효 시간은 0보다 커야 합니다.".into());
+        }
+        *lock(&self.deadman_timeout) = enabled.then_some(timeout);
+        *lock(&self.deadman_heartbeat) = None;
+        self.deadman_tripped.store(false, Ordering::SeqCst);
+        Ok(self.deadman_generation.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    // 데드맨 스위치 신호 갱신 함수 (프론트엔드에서 주기적으로 호출)
+    pub fn deadman_heartbeat(&self) -> Result<(), String> {
+        if lock(&self.deadman_timeout).is_none() {
+            return Err("데드맨 스위치가 꺼져 있습니다.".into());
+        }
+        *lock(&self.deadman_heartbeat) = Some(Instant::now());
+        self.deadman_tripped.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    // 데드맨 스위치 신호가 끊겼는지 확인 (끊긴 순간 한 번 진행 중인 동작 취소, 그 순간에만 true)
+    fn trip_deadman_if_lapsed(&self) -> bool {
+        let Some(timeout) = *lock(&self.deadman_timeout) else {
+            return false;
+        };
+        let lapsed = !lock(&self.deadman_heartbeat).is_some_and(|at| at.elapsed() <= timeout);
+        if lapsed && !self.deadman_tripped.swap(true, Ordering::SeqCst) {
+            self.cancel_motion();
+            return true;
+        }
+        false
+    }
+
+    // 데드맨 스위치가 켜져 있고 신호가 끊긴 상태인지 확인
+    pub(crate) fn deadman_lapsed(&self) -> bool {
+        self.trip_deadman_if_lapsed();
+        self.deadman_tripped.load(Ordering::SeqCst)
+    }
+
+    // 데드맨 스위치 감시 루프 (신호가 끊길 때마다 on_lapse 호출, 설정을 바꾸면 종료)
+    pub fn watch_deadman<F: FnMut()>(&self, token: u64, mut on_lapse: F) {
+        while self.deadman_generation.load(Ordering::SeqCst) == token {
+            if self.trip_deadman_if_lapsed() {
+                on_lapse();
+            }
+            thread::sleep(DEADMAN_CHECK_INTERVAL);
+        }
+    }
+}
+
+// 데드맨 스위치 설정 커맨드 (켜면 timeout_ms 안에 deadman_heartbeat가 없을 때 동작 중지)
+#[tauri::command]
+pub fn set_deadman(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+    timeout_ms: u64,
+) -> Result<(), String> {
+    let manager = state.serial_manager.clone();
+    let token = manager.set_deadman(enabled, Duration::from_millis(timeout_ms))?;
+    if enabled {
+        thread::spawn(move || {
+            // 끊긴 순간 동작 토큰만 취소하고 상태 스트림 같은 수집 작업은 유지
+            manager.watch_deadman(token, || {
+                let _ = app.emit("deadman_lapsed", ());
+            });
+        });
+    }
+    Ok(())
+}
+
+// 데드맨 스위치 신호 갱신 커맨드
+#[tauri::command]
+pub fn deadman_heartbeat(state: State<'_, AppState>) -> Result<(), String> {
+    state.serial_manager.deadman_heartbeat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{connected_manager, state};
+
+    #[test]
+    fn send_command_is_rejected_after_deadman_lapses() {
+        let (manager, _rx) = connected_manager(Vec::new());
+        manager
+            .set_deadman(true, Duration::from_millis(50))
+            .unwrap();
+        assert!(manager.send_command(&state(90)).is_err());
+
+        manager.deadman_heartbeat().unwrap();
+        manager.send_command(&state(90)).unwrap();
+        let token = manager.motion_token();
+
+        thread::sleep(Duration::from_millis(80));
+        assert!(manager.send_command(&state(90)).is_err());
+        // 끊긴 순간 진행 중인 동작 토큰도 취소됨
+        assert!(manager.is_motion_cancelled(token));
+
+        manager.deadman_heartbeat().unwrap();
+        manager.send_command(&state(90)).unwrap();
+    }
+}
@@ -0,0 +1,1266 @@
+// src-tauri/diagnostics.rs
+
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use crate::calibration::CalibrationReport;
+use crate::codec::{device_interval, frame_len};
+use crate::motion::{nudge_target, MOTION_WAIT_SLICE};
+use crate::serial::{
+    joint, joint_change, lock, max_frame_rate, run_motion_task, set_joint, AppState, RobotState,
+    SerialPortManager,
+};
+
+// 자동 조정 타임아웃 범위와 관측 주기 대비 배율
+const MIN_TUNED_TIMEOUT: Duration = Duration::from_millis(20);
+const MAX_TUNED_TIMEOUT: Duration = Duration::from_secs(2);
+const TUNED_TIMEOUT_FACTOR: f64 = 3.0;
+// 자동 조정 시 측정 간격 수 상한과 전체 측정 시간 상한
+const MAX_TUNING_SAMPLES: usize = 1000;
+const MAX_TUNING_DURATION: Duration = Duration::from_secs(30);
+// 처리량 측정 구간 상한
+const MAX_THROUGHPUT_DURATION: Duration = Duration::from_secs(60);
+// 수신 지연 분포 측정 횟수 상한과 측정 시간 상한
+const MAX_LATENCY_SAMPLES: usize = 10_000;
+const MAX_LATENCY_DURATION: Duration = Duration::from_secs(30);
+// 스텝 응답 시험 기록 시간 상한
+const MAX_STEP_RESPONSE_DURATION: Duration = Duration::from_secs(10);
+// 스텝 전 시작 위치로 이동 후 대기 시간
+const STEP_RESPONSE_SETTLE: Duration = Duration::from_millis(500);
+// 보정 확인 시 목표 도달 대기 시간과 도달로 볼 raw 오차
+const CALIBRATION_SETTLE: Duration = Duration::from_secs(1);
+const CALIBRATION_TOLERANCE: u8 = 1;
+// 명령-동작 지연 자동 측정 시 기록 시간
+const LATENCY_MEASURE_DURATION: Duration = Duration::from_secs(1);
+// 전송 후 도달 확인 대기 시간 상한
+const MAX_VERIFY_TIMEOUT: Duration = Duration::from_secs(30);
+// 피드백 확인 시 관절별 이동량 (raw 단위)과 응답 대기 시간
+const FEEDBACK_NUDGE: u8 = 6;
+const FEEDBACK_TIMEOUT: Duration = Duration::from_secs(1);
+
+// 관절 피드백 확인 결과 (observed: 대기 중 마지막으로 읽은 값)
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct JointFeedback {
+    pub joint: u8,
+    pub baseline: u8,
+    pub commanded: u8,
+    pub observed: Option<u8>,
+    pub passed: bool,
+}
+
+// 전송 확인 결과
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub enum VerifyOutcome {
+    // 모든 관절이 허용 오차 안에 들어옴
+    Reached,
+    // 컨트롤러가 오류 패킷으로 명령을 거부
+    Rejected(String),
+    // 제한 시간 안에 도달하지 못함
+    TimedOut,
+    // 확인 중 동작이 취소됨
+    Cancelled,
+}
+
+// 관절별 전송 확인 결과 (observed: 마지막으로 읽은 값)
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct JointVerification {
+    pub joint: u8,
+    pub commanded: u8,
+    pub observed: Option<u8>,
+    pub reached: bool,
+}
+
+// 전송 확인 결과 구조체 정의
+#[derive(Serialize, Debug)]
+pub struct VerifyReport {
+    pub outcome: VerifyOutcome,
+    pub joints: Vec<JointVerification>,
+}
+
+// 처리량 측정 결과 구조체 정의
+#[derive(Serialize)]
+pub struct ThroughputReport {
+    pub duration_ms: u64,
+    pub bytes: u64,
+    pub frames: u64,
+    pub bytes_per_sec: f64,
+    pub frames_per_sec: f64,
+    // TCP 연결은 보드레이트가 없으므로 None
+    pub max_bytes_per_sec: Option<f64>,
+    pub max_frames_per_sec: Option<f64>,
+}
+
+// 관절별 raw 값 통계 구조체 정의 (std_dev: 모표준편차)
+#[derive(Serialize, Debug)]
+pub struct JointStats {
+    pub joint: u8,
+    pub mean: f64,
+    pub min: u8,
+    pub max: u8,
+    pub std_dev: f64,
+}
+
+// 상태 패킷 묶음 통계 구조체 정의
+#[derive(Serialize, Debug)]
+pub struct JointStatistics {
+    pub samples: usize,
+    pub joints: Vec<JointStats>,
+    // 디지털 입력 1~3의 값 변경 횟수
+    pub input_flips: [u32; 3],
+}
+
+// 수신 상태 묶음의 관절별 통계 계산 함수 (상태가 없으면 None)
+pub fn joint_statistics(states: &[RobotState]) -> Option<JointStatistics> {
+    if states.is_empty() {
+        return None;
+    }
+    let samples = states.len();
+    let joints = (1..=6)
+        .map(|index| {
+            let values: Vec<u8> = states.iter().map(|state| joint(state, index)).collect();
+            let mean = values.iter().map(|&value| value as f64).sum::<f64>() / samples as f64;
+            let variance = values
+                .iter()
+                .map(|&value| (value as f64 - mean).powi(2))
+                .sum::<f64>()
+                / samples as f64;
+            JointStats {
+                joint: index,
+                mean,
+                min: values.iter().copied().min().unwrap_or(0),
+                max: values.iter().copied().max().unwrap_or(0),
+                std_dev: variance.sqrt(),
+            }
+        })
+        .collect();
+    let inputs = |state: &RobotState| {
+        [
+            state.digital_input_1,
+            state.digital_input_2,
+            state.digital_input_3,
+        ]
+    };
+    let mut input_flips = [0; 3];
+    for pair in states.windows(2) {
+        let (before, after) = (inputs(&pair[0]), inputs(&pair[1]));
+        for i in 0..3 {
+            if before[i] != after[i] {
+                input_flips[i] += 1;
+            }
+        }
+    }
+    Some(JointStatistics {
+        samples,
+        joints,
+        input_flips,
+    })
+}
+
+// 스텝 응답 측정점 (t_ms: 스텝 명령 전송 후 경과 시간)
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct StepSample {
+    pub t_ms: f64,
+    pub value: u8,
+}
+
+// 스텝 응답 시험 결과 구조체 정의
+#[derive(Serialize, Debug)]
+pub struct StepResponse {
+    pub joint: u8,
+    pub from: u8,
+    pub to: u8,
+    pub samples: Vec<StepSample>,
+    // 타임아웃 등으로 읽지 못한 횟수
+    pub failures: u64,
+}
+
+// 수신 지연 분포 구조체 정의 (counts[i]: i*bucket_ms 이상 (i+1)*bucket_ms 미만)
+#[derive(Serialize, Debug)]
+pub struct LatencyHistogram {
+    pub bucket_ms: u64,
+    // true면 컨트롤러 타임스탬프 기준 패킷 간격, false면 호스트 기준 읽기 지연
+    pub device_clock: bool,
+    pub counts: Vec<u64>,
+    // 타임아웃 등으로 지연을 측정하지 못한 횟수
+    pub failures: u64,
+}
+
+// 지연 값을 bucket 단위 구간별로 세는 함수 (마지막 구간은 최댓값이 속한 구간)
+pub fn latency_buckets(latencies: &[Duration], bucket: Duration) -> Vec<u64> {
+    let index = |latency: &Duration| (latency.as_nanos() / bucket.as_nanos().max(1)) as usize;
+    let mut counts = vec![0; latencies.iter().map(index).max().map_or(0, |max| max + 1)];
+    for latency in latencies {
+        counts[index(latency)] += 1;
+    }
+    counts
+}
+
+// 타임아웃 자동 조정 결과 구조체 정의
+#[derive(Serialize, Debug)]
+pub struct TimeoutTuning {
+    pub samples: usize,
+    pub mean_interval_ms: f64,
+    pub max_interval_ms: f64,
+    pub timeout_ms: u64,
+}
+
+impl SerialPortManager {
+    // 명령-동작 지연 자동 측정 함수 (스텝 응답에서 이동량 절반에 도달한 시간, 측정 후 원래 명령 복귀)
+    // 기록은 LATENCY_MEASURE_DURATION 동안만 하고 토큰이 취소되면 바로 복귀
+    pub fn auto_measure_actuation_latency(
+        &self,
+        joint: u8,
+        token: u64,
+    ) -> Result<Duration, String> {
+        if !(1..=6).contains(&joint) {
+            return Err("관절 번호는 1에서 6 사이여야 합니다.".into());
+        }
+        let base = self
+            .last_command()
+            .ok_or("지연 측정 전에 로봇 명령을 먼저 전송해주세요.")?;
+        let (min, max) = lock(&self.joint_limits)[joint as usize - 1];
+        let from = self::joint(&base, joint).clamp(min, max);
+        let to = nudge_target(from, min, max, FEEDBACK_NUDGE);
+        if from == to {
+            return Err(format!("관절 {} 제한 범위가 너무 좁습니다.", joint));
+        }
+        let response = self.step_response(joint, from, to, LATENCY_MEASURE_DURATION, token);
+        self.send_command(&base)?;
+        let required = from.abs_diff(to).div_ceil(2);
+        let latency = response?
+            .samples
+            .iter()
+            .find(|sample| sample.value.abs_diff(from) >= required)
+            .map(|sample| Duration::from_secs_f64(sample.t_ms / 1000.0))
+            .ok_or_else(|| format!("관절 {}이(가) 스텝 명령에 응답하지 않았습니다.", joint))?;
+        self.set_actuation_latency(latency)?;
+        Ok(latency)
+    }
+
+    // 보정 확인 함수 (보정된 관절마다 제한 범위 안의 각도 목표로 천천히 이동해 읽은 값과 비교)
+    // 관절 하나씩 확인하고 끝나면 시작 자세로 돌아감 (토큰이 취소되면 중단)
+    pub fn verify_calibration(&self, token: u64) -> Result<Vec<CalibrationReport>, String> {
+        let base = self
+            .last_command()
+            .ok_or("보정 확인 전에 로봇 명령을 먼저 전송해주세요.")?;
+        let calibration = self.calibration();
+        if calibration.iter().all(Option::is_none) {
+            return Err("보정된 관절이 없습니다.".into());
+        }
+        let limits = *lock(&self.joint_limits);
+
+        let mut reports = Vec::new();
+        let mut current = base.clone();
+        for (i, joint_calibration) in calibration.iter().enumerate() {
+            let Some(joint_calibration) = joint_calibration else {
+                continue;
+            };
+            if self.is_motion_cancelled(token) {
+                return Err("보정 확인이 취소되었습니다.".into());
+            }
+            let index = i as u8 + 1;
+            let (min, max) = limits[i];
+            let mut points = Vec::new();
+            for expected_deg in joint_calibration.verify_setpoints(min, max) {
+                let mut target = current.clone();
+                set_joint(&mut target, index, joint_calibration.raw(expected_deg));
+                if !self.move_gently(&current, &target, token)? {
+                    return Err("보정 확인이 취소되었습니다.".into());
+                }
+                current = target;
+                let read_raw = self.settled_joint(index, self::joint(&current, index), token)?;
+                points.push(joint_calibration.verify_point(expected_deg, read_raw));
+            }
+            reports.push(CalibrationReport::new(index, points));
+        }
+        if !self.move_gently(&current, &base, token)? {
+            return Err("보정 확인이 취소되었습니다.".into());
+        }
+        Ok(reports)
+    }
+
+    // 관절이 목표에 도달할 때까지 상태를 읽어 마지막 값 반환 (대기 시간 안에 못 읽으면 None)
+    fn settled_joint(&self, index: u8, target: u8, token: u64) -> Result<Option<u8>, String> {
+        let started = Instant::now();
+        let mut observed = None;
+        while let Some(remaining) = CALIBRATION_SETTLE.checked_sub(started.elapsed()) {
+            if self.is_motion_cancelled(token) {
+                return Err("보정 확인이 취소되었습니다.".into());
+            }
+            if let Ok(robot_state) = self.read_data_timeout(Some(remaining)) {
+                let value = self::joint(&robot_state, index);
+                observed = Some(value);
+                if value.abs_diff(target) <= CALIBRATION_TOLERANCE {
+                    break;
+                }
+            }
+        }
+        Ok(observed)
+    }
+
+    // 처리량 측정 함수 (포트를 직접 읽지 않고 상태 스트림 등 평소 수신 경로의 집계를 구간 동안 비교)
+    pub fn measure_throughput(
+        &self,
+        duration: Duration,
+        token: u64,
+    ) -> Result<ThroughputReport, String> {
+        if duration.is_zero() || duration > MAX_THROUGHPUT_DURATION {
+            return Err(format!(
+                "측정 시간은 1ms에서 {}ms 사이여야 합니다.",
+                MAX_THROUGHPUT_DURATION.as_millis()
+            ));
+        }
+        if !self.is_connected() {
+            return Err("시리얼 포트가 초기화되지 않았습니다.".into());
+        }
+
+        let start_bytes = self.metrics.bytes_read.load(Ordering::Relaxed);
+        let start_frames = self.metrics.frames_received.load(Ordering::Relaxed);
+        let started = Instant::now();
+        if !self.sleep_unless_cancelled(duration, token) {
+            return Err("처리량 측정이 취소되었습니다.".into());
+        }
+        let elapsed = started.elapsed().as_secs_f64();
+        let bytes = self.metrics.bytes_read.load(Ordering::Relaxed) - start_bytes;
+        let frames = self.metrics.frames_received.load(Ordering::Relaxed) - start_frames;
+
+        // 현재 포트 설정 기준 이론 최대치
+        let frame_len = frame_len(&self.decoder_config());
+        let bits_per_byte = self.line_bits_per_byte();
+        let max_bytes_per_sec = self
+            .baud_rate()
+            .map(|baud_rate| baud_rate as f64 / bits_per_byte as f64);
+
+        Ok(ThroughputReport {
+            duration_ms: (elapsed * 1000.0) as u64,
+            bytes,
+            frames,
+            bytes_per_sec: bytes as f64 / elapsed,
+            frames_per_sec: frames as f64 / elapsed,
+            max_bytes_per_sec,
+            max_frames_per_sec: self
+                .baud_rate()
+                .map(|baud_rate| max_frame_rate(baud_rate, bits_per_byte, frame_len)),
+        })
+    }
+
+    // 패킷 수신 간격을 측정해 포트 타임아웃 자동 조정 함수 (가장 긴 간격의 배수, 범위 제한)
+    pub fn auto_tune_timeout(&self, samples: usize, token: u64) -> Result<TimeoutTuning, String> {
+        if !(1..=MAX_TUNING_SAMPLES).contains(&samples) {
+            return Err(format!(
+                "측정 간격 수는 1에서 {} 사이여야 합니다.",
+                MAX_TUNING_SAMPLES
+            ));
+        }
+        if !self.is_connected() {
+            return Err("시리얼 포트가 초기화되지 않았습니다.".into());
+        }
+
+        // 다음 패킷 수신 시각 (패킷 간격 상한, 전체 측정 시간 상한, 취소 중 먼저 오는 쪽까지 대기)
+        let run_deadline = Instant::now() + MAX_TUNING_DURATION;
+        let next_frame = || -> Result<Instant, String> {
+            let frame_deadline = (Instant::now() + MAX_TUNED_TIMEOUT).min(run_deadline);
+            loop {
+                if self.is_motion_cancelled(token) {
+                    return Err("타임아웃 자동 조정이 취소되었습니다.".into());
+                }
+                let Some(remaining) = frame_deadline.checked_duration_since(Instant::now()) else {
+                    return Err(if Instant::now() >= run_deadline {
+                        format!(
+                            "{}ms 안에 측정을 마치지 못했습니다.",
+                            MAX_TUNING_DURATION.as_millis()
+                        )
+                    } else {
+                        format!(
+                            "{}ms 안에 상태 패킷을 받지 못했습니다.",
+                            MAX_TUNED_TIMEOUT.as_millis()
+                        )
+                    });
+                };
+                if self
+                    .read_data_timeout(Some(remaining.min(MOTION_WAIT_SLICE)))
+                    .is_ok()
+                {
+                    return Ok(Instant::now());
+                }
+            }
+        };
+
+        // 첫 패킷은 수신 시점 동기화용
+        let mut previous = next_frame()?;
+        let mut intervals = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            let now = next_frame()?;
+            intervals.push(now - previous);
+            previous = now;
+        }
+
+        let max_interval = intervals.iter().max().copied().unwrap_or_default();
+        let mean_interval = intervals.iter().sum::<Duration>() / samples as u32;
+        let timeout = max_interval
+            .mul_f64(TUNED_TIMEOUT_FACTOR)
+            .clamp(MIN_TUNED_TIMEOUT, MAX_TUNED_TIMEOUT);
+
+        let mut port_lock = lock(&self.port);
+        let port = port_lock
+            .as_mut()
+            .ok_or("시리얼 포트가 초기화되지 않았습니다.")?;
+        port.set_timeout(timeout)
+            .map_err(|e| format!("타임아웃 설정 실패: {}", e))?;
+
+        Ok(TimeoutTuning {
+            samples,
+            mean_interval_ms: mean_interval.as_secs_f64() * 1000.0,
+            max_interval_ms: max_interval.as_secs_f64() * 1000.0,
+            timeout_ms: timeout.as_millis() as u64,
+        })
+    }
+
+    // 상태 패킷 수신 지연 분포 측정 함수 (samples번 읽어 지연을 bucket 단위로 집계)
+    // 타임스탬프가 설정되어 있으면 호스트 읽기 시간 대신 컨트롤러 시계 기준 패킷 간격 집계
+    // 측정 시간 상한을 넘으면 그때까지 읽은 값으로 집계
+    pub fn latency_histogram(
+        &self,
+        samples: usize,
+        bucket: Duration,
+        token: u64,
+    ) -> Result<LatencyHistogram, String> {
+        if !(1..=MAX_LATENCY_SAMPLES).contains(&samples) {
+            return Err(format!(
+                "측정 횟수는 1에서 {} 사이여야 합니다.",
+                MAX_LATENCY_SAMPLES
+            ));
+        }
+        if bucket.is_zero() {
+            return Err("구간 크기는 0보다 커야 합니다.".into());
+        }
+        if !self.is_connected() {
+            return Err("시리얼 포트가 초기화되지 않았습니다.".into());
+        }
+
+        let device_clock = lock(&self.timestamp_offset).is_some();
+        let mut latencies = Vec::with_capacity(samples);
+        let mut failures = 0;
+        let mut previous_timestamp = None;
+        let deadline = Instant::now() + MAX_LATENCY_DURATION;
+        for _ in 0..samples {
+            if self.is_motion_cancelled(token) {
+                return Err("수신 지연 분포 측정이 취소되었습니다.".into());
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+            let started = Instant::now();
+            match self.read_data() {
+                Ok(robot_state) if device_clock => {
+                    let current = robot_state.device_timestamp;
+                    if let Some((previous, current)) = previous_timestamp.zip(current) {
+                        latencies.push(device_interval(previous, current));
+                    }
+                    previous_timestamp = current;
+                }
+                Ok(_) => latencies.push(started.elapsed()),
+                Err(_) => {
+                    failures += 1;
+                    // 놓친 패킷이 간격에 섞이지 않도록 기준 타임스탬프 초기화
+                    previous_timestamp = None;
+                }
+            }
+        }
+
+        Ok(LatencyHistogram {
+            bucket_ms: bucket.as_millis() as u64,
+            device_clock,
+            counts: latency_buckets(&latencies, bucket),
+            failures,
+        })
+    }
+
+    // 스텝 응답 시험 함수 (한 관절만 from에서 to로 바꾸고 duration 동안 읽은 값 기록)
+    pub fn step_response(
+        &self,
+        joint: u8,
+        from: u8,
+        to: u8,
+        duration: Duration,
+        token: u64,
+    ) -> Result<StepResponse, String> {
+        if !(1..=6).contains(&joint) {
+            return Err("관절 번호는 1에서 6 사이여야 합니다.".into());
+        }
+        if duration.is_zero() || duration > MAX_STEP_RESPONSE_DURATION {
+            return Err(format!(
+                "기록 시간은 1ms에서 {}ms 사이여야 합니다.",
+                MAX_STEP_RESPONSE_DURATION.as_millis()
+            ));
+        }
+        // 제한에 걸려 잘린 스텝은 응답 분석에 쓸 수 없으므로 거부
+        let (min, max) = lock(&self.joint_limits)[joint as usize - 1];
+        if [from, to].iter().any(|value| !(min..=max).contains(value)) {
+            return Err(format!(
+                "스텝 값이 관절 {} 제한({}~{})을 벗어납니다.",
+                joint, min, max
+            ));
+        }
+        let mut command = self
+            .last_command()
+            .ok_or("스텝 응답 시험 전에 로봇 명령을 먼저 전송해주세요.")?;
+
+        // 이미 시작 위치로 명령된 경우 대기 생략
+        if self::joint(&command, joint) != from {
+            set_joint(&mut command, joint, from);
+            self.send_command(&command)?;
+            if !self.sleep_unless_cancelled(STEP_RESPONSE_SETTLE, token) {
+                return Err("스텝 응답 시험이 취소되었습니다.".into());
+            }
+        }
+        set_joint(&mut command, joint, to);
+        self.send_command(&command)?;
+
+        let started = Instant::now();
+        let mut samples = Vec::new();
+        let mut failures = 0;
+        while started.elapsed() < duration {
+            if self.is_motion_cancelled(token) {
+                return Err("스텝 응답 시험이 취소되었습니다.".into());
+            }
+            match self.read_data() {
+                Ok(robot_state) => samples.push(StepSample {
+                    t_ms: started.elapsed().as_secs_f64() * 1000.0,
+                    value: self::joint(&robot_state, joint),
+                }),
+                Err(_) => failures += 1,
+            }
+        }
+
+        Ok(StepResponse {
+            joint,
+            from,
+            to,
+            samples,
+            failures,
+        })
+    }
+
+    // 명령 전송 후 읽은 값이 허용 오차 안에 들어올 때까지 확인하는 함수
+    pub fn send_and_verify(
+        &self,
+        robot_state: &RobotState,
+        tolerance: u8,
+        timeout: Duration,
+        token: u64,
+    ) -> Result<VerifyReport, String> {
+        if timeout.is_zero() || timeout > MAX_VERIFY_TIMEOUT {
+            return Err(format!(
+                "확인 시간은 1ms에서 {}ms 사이여야 합니다.",
+                MAX_VERIFY_TIMEOUT.as_millis()
+            ));
+        }
+        // 관절 제한으로 조정된 값이 실제 전송되는 목표
+        let target = self.clamp_to_limits(robot_state).0;
+        self.send_command(robot_state)?;
+
+        let started = Instant::now();
+        let mut observed: Option<RobotState> = None;
+        let outcome = loop {
+            if let Some(read_back) = &observed {
+                if (1..=6).all(|index| {
+                    joint(read_back, index).abs_diff(joint(&target, index)) <= tolerance
+                }) {
+                    break VerifyOutcome::Reached;
+                }
+            }
+            if self.is_motion_cancelled(token) {
+                break VerifyOutcome::Cancelled;
+            }
+            let Some(remaining) = timeout.checked_sub(started.elapsed()) else {
+                break VerifyOutcome::TimedOut;
+            };
+            let controller_errors = self.metrics.controller_errors.load(Ordering::Relaxed);
+            match self.read_data_timeout(Some(remaining.min(MOTION_WAIT_SLICE))) {
+                Ok(read_back) => observed = Some(read_back),
+                // 오류 패킷은 컨트롤러가 명령을 받아들이지 않은 것으로 판단
+                Err(e)
+                    if self.metrics.controller_errors.load(Ordering::Relaxed)
+                        != controller_errors =>
+                {
+                    break VerifyOutcome::Rejected(e);
+                }
+                Err(_) => {}
+            }
+        };
+
+        let joints = (1..=6)
+            .map(|index| {
+                let commanded = joint(&target, index);
+                let value = observed.as_ref().map(|read_back| joint(read_back, index));
+                JointVerification {
+                    joint: index,
+                    commanded,
+                    observed: value,
+                    reached: value.is_some_and(|value| value.abs_diff(commanded) <= tolerance),
+                }
+            })
+            .collect();
+        Ok(VerifyReport { outcome, joints })
+    }
+
+    // 관절 피드백 확인 함수 (관절마다 제한 안에서 조금 움직여 읽은 값이 따라오는지 확인 후 복귀)
+    // 읽기마다 남은 대기 시간을 넘기지 않으므로 관절당 FEEDBACK_TIMEOUT 안에 끝남
+    pub fn verify_feedback(&self, token: u64) -> Result<Vec<JointFeedback>, String> {
+        let base = self
+            .last_command()
+            .ok_or("피드백 확인 전에 로봇 명령을 먼저 전송해주세요.")?;
+        let baseline = self.read_data_timeout(Some(FEEDBACK_TIMEOUT))?;
+        // 이동량의 절반 이상 따라오면 응답한 것으로 판단
+        let required = FEEDBACK_NUDGE.div_ceil(2);
+
+        let mut results = Vec::with_capacity(6);
+        for index in 1..=6 {
+            let (min, max) = lock(&self.joint_limits)[index as usize - 1];
+            let before = self::joint(&baseline, index);
+            // 현재 읽은 값에서 제한 안쪽으로 여유가 있는 방향으로 이동
+            let target = nudge_target(before, min, max, FEEDBACK_NUDGE);
+            let rising = target >= before;
+
+            let mut command = base.clone();
+            set_joint(&mut command, index, target);
+            self.send_command(&command)?;
+            let started = Instant::now();
+            let mut observed = None;
+            let mut passed = false;
+            while let Some(remaining) = FEEDBACK_TIMEOUT.checked_sub(started.elapsed()) {
+                if self.is_motion_cancelled(token) {
+                    let _ = self.send_command(&base);
+                    return Err("피드백 확인이 취소되었습니다.".into());
+                }
+                let Ok(robot_state) =
+                    self.read_data_timeout(Some(remaining.min(MOTION_WAIT_SLICE)))
+                else {
+                    continue;
+                };
+                let value = self::joint(&robot_state, index);
+                observed = Some(value);
+                let moved = if rising {
+                    joint_change(before, value)
+                } else {
+                    joint_change(value, before)
+                };
+                if moved >= required as i32 {
+                    passed = true;
+                    break;
+                }
+            }
+            // 다음 관절 확인 전에 원래 자세로 복귀
+            self.send_command(&base)?;
+            results.push(JointFeedback {
+                joint: index,
+                baseline: before,
+                commanded: target,
+                observed,
+                passed,
+            });
+        }
+        Ok(results)
+    }
+}
+
+// 명령 전송 후 도달 확인 커맨드 (스크립트용 동기 이동, 확인은 메인 스레드 밖에서 대기)
+#[tauri::command]
+pub async fn send_and_verify(
+    state: State<'_, AppState>,
+    robot_state: RobotState,
+    tolerance: u8,
+    timeout_ms: u64,
+) -> Result<VerifyReport, String> {
+    run_motion_task(&state, "전송 후 도달 확인", move |manager, token| {
+        manager.send_and_verify(
+            &robot_state,
+            tolerance,
+            Duration::from_millis(timeout_ms),
+            token,
+        )
+    })
+    .await
+}
+
+// 정지 상태 관절 값 흔들림 측정 커맨드 (시간 안에 받은 패킷만으로 계산, 백그라운드 작업으로 실행)
+#[tauri::command]
+pub async fn sample_joint_statistics(
+    state: State<'_, AppState>,
+    n: usize,
+    timeout_ms: u64,
+) -> Result<JointStatistics, String> {
+    let states = run_motion_task(&state, "관절 통계 측정", move |manager, token| {
+        manager.read_batch(n, Duration::from_millis(timeout_ms), token)
+    })
+    .await
+    .map_err(|e| format!("관절 통계 측정 실패: {}", e))?;
+    joint_statistics(&states)
+        .ok_or_else(|| "관절 통계 측정 실패: 수신한 상태 패킷이 없습니다.".into())
+}
+
+// 처리량 측정 커맨드 (상태 스트림이 수신하는 동안 백그라운드 작업으로 측정)
+#[tauri::command]
+pub async fn measure_throughput(
+    state: State<'_, AppState>,
+    duration_ms: u64,
+) -> Result<ThroughputReport, String> {
+    run_motion_task(&state, "처리량 측정", move |manager, token| {
+        manager.measure_throughput(Duration::from_millis(duration_ms), token)
+    })
+    .await
+    .map_err(|e| format!("처리량 측정 실패: {}", e))
+}
+
+// 상태 패킷 수신 지연 분포 측정 커맨드 (백그라운드 작업으로 실행, cancel_motion으로 중지)
+#[tauri::command]
+pub async fn latency_histogram(
+    state: State<'_, AppState>,
+    samples: usize,
+    bucket_ms: u64,
+) -> Result<LatencyHistogram, String> {
+    run_motion_task(
+        &state,
+        "수신 지연 분포 측정",
+        move |manager, token| {
+            manager.latency_histogram(samples, Duration::from_millis(bucket_ms), token)
+        },
+    )
+    .await
+    .map_err(|e| format!("수신 지연 분포 측정 실패: {}", e))
+}
+
+// 한 관절 스텝 응답 시험 커맨드 (백그라운드 작업으로 실행, cancel_task나 cancel_motion으로 중지)
+#[tauri::command]
+pub async fn step_response_test(
+    state: State<'_, AppState>,
+    joint: u8,
+    from: u8,
+    to: u8,
+    duration_ms: u64,
+) -> Result<StepResponse, String> {
+    let description = format!("관절 {} 스텝 응답 시험", joint);
+    run_motion_task(&state, &description, move |manager, token| {
+        manager.step_response(joint, from, to, Duration::from_millis(duration_ms), token)
+    })
+    .await
+    .map_err(|e| format!("스텝 응답 시험 실패: {}", e))
+}
+
+// 보정 확인 커맨드 (보정된 관절별 목표 각도와 읽은 각도의 오차, cancel_motion으로 중지)
+#[tauri::command]
+pub async fn verify_calibration(
+    state: State<'_, AppState>,
+) -> Result<Vec<CalibrationReport>, String> {
+    run_motion_task(&state, "보정 확인", |manager, token| {
+        manager.verify_calibration(token)
+    })
+    .await
+    .map_err(|e| format!("보정 확인 실패: {}", e))
+}
+
+// 관절 피드백 확인 커맨드 (관절별 통과 여부, 응답 없는 관절은 엔코더 연결 확인 필요)
+// 백그라운드 작업으로 실행, cancel_task나 cancel_motion으로 중지
+#[tauri::command]
+pub async fn verify_feedback(state: State<'_, AppState>) -> Result<Vec<JointFeedback>, String> {
+    run_motion_task(&state, "관절 피드백 확인", |manager, token| {
+        manager.verify_feedback(token)
+    })
+    .await
+    .map_err(|e| format!("피드백 확인 실패: {}", e))
+}
+
+// 패킷 수신 간격 기반 타임아웃 자동 조정 커맨드 (백그라운드 작업으로 실행, cancel_motion으로 중지)
+#[tauri::command]
+pub async fn auto_tune_timeout(
+    state: State<'_, AppState>,
+    samples: usize,
+) -> Result<TimeoutTuning, String> {
+    run_motion_task(
+        &state,
+        "타임아웃 자동 조정",
+        move |manager, token| manager.auto_tune_timeout(samples, token),
+    )
+    .await
+    .map_err(|e| format!("타임아웃 자동 조정 실패: {}", e))
+}
+
+// 명령-동작 지연 자동 측정 커맨드 (측정한 지연을 ms로 반환하고 바로 적용, cancel_motion으로 중지)
+#[tauri::command]
+pub async fn auto_measure_actuation_latency(
+    state: State<'_, AppState>,
+    joint: u8,
+) -> Result<u64, String> {
+    let description = format!("관절 {} 명령-동작 지연 측정", joint);
+    run_motion_task(&state, &description, move |manager, token| {
+        manager.auto_measure_actuation_latency(joint, token)
+    })
+    .await
+    .map(|latency| latency.as_millis() as u64)
+    .map_err(|e| format!("명령-동작 지연 측정 실패: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration::JointCalibration;
+    use crate::codec::{encode_command, IoLayout};
+    use crate::test_support::{
+        connected_manager, echo_responder, joint_1_frame, mock_responder, paced_controller,
+        received, received_before, state, wait_until,
+    };
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn throughput_counts_frames_at_controlled_rate() {
+        // 20ms 간격 40개 패킷 → 약 50fps
+        let frames = vec![encode_command(&state(30), IoLayout::Expanded); 40];
+        let addr = paced_controller(frames, Duration::from_millis(20));
+        let manager = Arc::new(SerialPortManager::new());
+        manager.initialize_tcp(&addr).unwrap();
+        assert!(manager
+            .measure_throughput(MAX_THROUGHPUT_DURATION + Duration::from_millis(1), 0)
+            .is_err());
+
+        // 평소 수신 경로 역할을 하는 읽기 스레드
+        let done = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let (manager, done) = (manager.clone(), done.clone());
+            thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    let _ = manager.read_data();
+                }
+            })
+        };
+        let report = manager
+            .measure_throughput(Duration::from_millis(500), manager.motion_token())
+            .unwrap();
+        done.store(true, Ordering::Relaxed);
+        reader.join().unwrap();
+        assert_eq!(report.bytes, report.frames * 15);
+        assert!((30.0..=70.0).contains(&report.frames_per_sec));
+        // TCP 연결은 이론 최대치 없음
+        assert!(report.max_frames_per_sec.is_none());
+        assert!(SerialPortManager::new()
+            .measure_throughput(Duration::from_millis(10), 0)
+            .is_err());
+    }
+
+    #[test]
+    fn throughput_measurement_stops_on_cancel() {
+        let (manager, _rx) = connected_manager(Vec::new());
+        let manager = Arc::new(manager);
+        let token = manager.motion_token();
+        let measurer = {
+            let manager = manager.clone();
+            thread::spawn(move || manager.measure_throughput(MAX_THROUGHPUT_DURATION, token))
+        };
+        let started = Instant::now();
+        manager.cancel_motion();
+        assert!(measurer.join().unwrap().err().unwrap().contains("취소"));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn auto_tuned_timeout_follows_frame_interval() {
+        let manager = SerialPortManager::new();
+        assert!(manager.auto_tune_timeout(3, 0).is_err());
+        let frames = vec![encode_command(&state(5), IoLayout::Expanded); 8];
+        manager
+            .initialize_tcp(&paced_controller(frames, Duration::from_millis(50)))
+            .unwrap();
+        assert!(manager.auto_tune_timeout(0, 0).is_err());
+
+        let tuning = manager
+            .auto_tune_timeout(5, manager.motion_token())
+            .unwrap();
+        assert!(
+            (40.0..70.0).contains(&tuning.mean_interval_ms),
+            "{:?}",
+            tuning
+        );
+        assert!((120..=250).contains(&tuning.timeout_ms), "{:?}", tuning);
+        let applied = manager.port_config().unwrap().timeout_ms;
+        assert!(applied.abs_diff(tuning.timeout_ms) <= 5);
+    }
+
+    #[test]
+    fn auto_tune_timeout_stops_on_cancel() {
+        let (manager, _rx) = connected_manager(Vec::new());
+        let manager = Arc::new(manager);
+        let token = manager.motion_token();
+        let tuner = {
+            let manager = manager.clone();
+            thread::spawn(move || manager.auto_tune_timeout(MAX_TUNING_SAMPLES, token))
+        };
+        let started = Instant::now();
+        manager.cancel_motion();
+        assert!(tuner.join().unwrap().unwrap_err().contains("취소"));
+        assert!(started.elapsed() < MAX_TUNED_TIMEOUT);
+    }
+
+    #[test]
+    fn joint_statistics_cover_partial_batch() {
+        let samples = [
+            RobotState {
+                digital_input_1: true,
+                ..state(10)
+            },
+            state(20),
+            RobotState {
+                digital_input_1: true,
+                digital_input_3: true,
+                ..state(30)
+            },
+        ];
+        let stream: Vec<u8> = samples
+            .iter()
+            .flat_map(|sample| encode_command(sample, IoLayout::Expanded))
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+
+        // 5개를 요청해도 타임아웃까지 받은 3개로 계산
+        let states = manager
+            .read_batch(5, Duration::from_millis(300), manager.motion_token())
+            .unwrap();
+        let statistics = joint_statistics(&states).unwrap();
+        assert_eq!(statistics.samples, 3);
+        let joint_1 = &statistics.joints[0];
+        assert_eq!((joint_1.min, joint_1.max), (10, 30));
+        assert!((joint_1.mean - 20.0).abs() < 1e-9);
+        assert!((joint_1.std_dev - (200.0f64 / 3.0).sqrt()).abs() < 1e-9);
+        assert_eq!(statistics.input_flips, [2, 0, 1]);
+        assert!(joint_statistics(&[]).is_none());
+    }
+
+    #[test]
+    fn latency_buckets_count_injected_latencies() {
+        let latencies = [500, 1500, 1999, 4200, 0].map(Duration::from_micros);
+        assert_eq!(
+            latency_buckets(&latencies, Duration::from_millis(1)),
+            vec![2, 2, 0, 0, 1]
+        );
+        assert!(latency_buckets(&[], Duration::from_millis(1)).is_empty());
+
+        let (manager, _rx) = connected_manager(Vec::new());
+        assert!(manager
+            .latency_histogram(0, Duration::from_millis(1), 0)
+            .is_err());
+        assert!(manager
+            .latency_histogram(MAX_LATENCY_SAMPLES + 1, Duration::from_millis(1), 0)
+            .is_err());
+        assert!(manager.latency_histogram(4, Duration::ZERO, 0).is_err());
+
+        // 취소되면 남은 측정 횟수와 상관없이 바로 중단
+        let token = manager.motion_token();
+        manager.cancel_motion();
+        assert!(manager
+            .latency_histogram(MAX_LATENCY_SAMPLES, Duration::from_millis(1), token)
+            .unwrap_err()
+            .contains("취소"));
+    }
+
+    #[test]
+    fn step_response_records_returned_series() {
+        let stream: Vec<u8> = [40, 55, 70, 82, 78, 80]
+            .into_iter()
+            .flat_map(|value| {
+                let reply = RobotState {
+                    joint_2: value,
+                    ..state(100)
+                };
+                encode_command(&reply, IoLayout::Expanded)
+            })
+            .collect();
+        let (manager, rx) = connected_manager(stream);
+        manager.set_joint_limit(2, 0, 200).unwrap();
+        let duration = Duration::from_millis(300);
+        // 기준 명령 전에는 시험할 수 없음
+        assert!(manager.step_response(2, 40, 80, duration, 0).is_err());
+        manager.send_command(&state(100)).unwrap();
+        assert!(manager.step_response(2, 40, 201, duration, 0).is_err());
+        assert!(manager.step_response(7, 40, 80, duration, 0).is_err());
+
+        assert!(manager
+            .step_response(2, 40, 80, MAX_STEP_RESPONSE_DURATION * 2, 0)
+            .is_err());
+
+        let response = manager
+            .step_response(2, 40, 80, duration, manager.motion_token())
+            .unwrap();
+        let values: Vec<u8> = response.samples.iter().map(|sample| sample.value).collect();
+        assert_eq!(values, vec![40, 55, 70, 82, 78, 80]);
+        assert!(response
+            .samples
+            .windows(2)
+            .all(|pair| pair[0].t_ms <= pair[1].t_ms));
+        assert!(response.failures > 0);
+
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        let sent = received_before(&rx, &marker);
+        let frames: Vec<&[u8]> = sent.chunks(15).collect();
+        assert_eq!(frames.len(), 3);
+        assert_eq!((frames[1][2], frames[2][2]), (40, 80));
+        assert!(frames[1..]
+            .iter()
+            .all(|frame| frame[1] == 100 && frame[3..7].iter().all(|&byte| byte == 100)));
+    }
+
+    #[test]
+    fn step_response_cancel_skips_the_step() {
+        let (manager, rx) = connected_manager(Vec::new());
+        manager.send_command(&state(100)).unwrap();
+        let manager = Arc::new(manager);
+        let token = manager.motion_token();
+        let tester = {
+            let manager = manager.clone();
+            thread::spawn(move || {
+                manager.step_response(2, 40, 80, MAX_STEP_RESPONSE_DURATION, token)
+            })
+        };
+        // 시작 위치 명령을 받은 뒤 대기 중 취소
+        received(&rx, 15 * 2);
+        manager.cancel_motion();
+        assert!(tester.join().unwrap().unwrap_err().contains("취소"));
+
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        assert!(received_before(&rx, &marker).is_empty());
+    }
+
+    #[test]
+    fn actuation_latency_is_measured_and_restores_command() {
+        let stream: Vec<u8> = [100, 100, 103, 106]
+            .into_iter()
+            .flat_map(joint_1_frame)
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        assert!(manager.auto_measure_actuation_latency(1, 0).is_err());
+        manager.send_command(&state(100)).unwrap();
+
+        // 취소된 토큰이면 측정하지 않고 원래 명령으로 복귀
+        let token = manager.motion_token();
+        manager.cancel_motion();
+        assert!(manager
+            .auto_measure_actuation_latency(1, token)
+            .unwrap_err()
+            .contains("취소"));
+        assert_eq!(manager.last_command().unwrap().joint_1, 100);
+
+        let started = Instant::now();
+        let latency = manager
+            .auto_measure_actuation_latency(1, manager.motion_token())
+            .unwrap();
+        assert!(started.elapsed() < LATENCY_MEASURE_DURATION * 2);
+        assert!(latency < LATENCY_MEASURE_DURATION);
+        assert_eq!(*lock(&manager.actuation_latency), latency);
+        assert_eq!(manager.last_command().unwrap().joint_1, 100);
+    }
+
+    #[test]
+    fn silent_joint_is_flagged_by_feedback_check() {
+        // 관절 1~5는 시험 이동을 따라오고 관절 6은 끝까지 움직이지 않음
+        let home = encode_command(&state(100), IoLayout::Expanded);
+        let mut stream = home.clone();
+        for joint in 1..=5 {
+            let mut frame = home.clone();
+            frame[joint] = if joint == 2 { 96 } else { 104 };
+            stream.extend(frame);
+        }
+        let (manager, rx) = connected_manager(stream);
+        assert!(manager.verify_feedback(0).is_err());
+        manager.send_command(&state(100)).unwrap();
+        manager.set_joint_limit(2, 0, 102).unwrap();
+
+        let started = Instant::now();
+        let results = manager.verify_feedback(manager.motion_token()).unwrap();
+        // 응답 없는 관절 6만 대기 시간을 모두 씀
+        assert!(started.elapsed() < FEEDBACK_TIMEOUT * 2);
+        assert_eq!(results.len(), 6);
+        assert!(results[..5].iter().all(|result| result.passed));
+        // 관절 2는 위쪽 여유가 없어 아래로 시험
+        assert_eq!((results[0].commanded, results[1].commanded), (106, 94));
+        assert_eq!(
+            (results[0].observed, results[1].observed),
+            (Some(104), Some(96))
+        );
+        assert!(!results[5].passed);
+        assert!(results[5].observed.is_none());
+
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        let sent = received_before(&rx, &marker);
+        let last = sent.chunks(15).last().unwrap();
+        assert!(last[1..7].iter().all(|&byte| byte == 100));
+    }
+
+    #[test]
+    fn verify_feedback_stops_on_cancel() {
+        let (manager, rx) = connected_manager(encode_command(&state(100), IoLayout::Expanded));
+        manager.send_command(&state(100)).unwrap();
+        let manager = Arc::new(manager);
+        let token = manager.motion_token();
+        let verifier = {
+            let manager = manager.clone();
+            thread::spawn(move || manager.verify_feedback(token))
+        };
+        // 관절 1 시험 이동을 받은 뒤 응답을 기다리는 중 취소
+        received(&rx, 15 * 2);
+        let started = Instant::now();
+        manager.cancel_motion();
+        assert!(verifier.join().unwrap().unwrap_err().contains("취소"));
+        assert!(started.elapsed() < FEEDBACK_TIMEOUT);
+        assert_eq!(manager.last_command().unwrap().joint_1, 100);
+    }
+
+    #[test]
+    fn send_and_verify_reports_convergence() {
+        let stream: Vec<u8> = [50, 60, 69, 70]
+            .into_iter()
+            .flat_map(joint_1_frame)
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        let target = RobotState {
+            joint_1: 70,
+            ..state(100)
+        };
+
+        let report = manager
+            .send_and_verify(&target, 2, Duration::from_millis(800), 0)
+            .unwrap();
+        assert_eq!(report.outcome, VerifyOutcome::Reached);
+        assert_eq!(report.joints[0].observed, Some(69));
+        assert!(report.joints.iter().all(|joint| joint.reached));
+    }
+
+    #[test]
+    fn send_and_verify_times_out_when_joint_stalls() {
+        let (manager, _rx) = connected_manager(joint_1_frame(50));
+        let target = RobotState {
+            joint_1: 70,
+            ..state(100)
+        };
+
+        assert!(manager
+            .send_and_verify(&target, 2, MAX_VERIFY_TIMEOUT * 2, 0)
+            .is_err());
+
+        let started = Instant::now();
+        let report = manager
+            .send_and_verify(&target, 2, Duration::from_millis(300), 0)
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_millis(600));
+        assert_eq!(report.outcome, VerifyOutcome::TimedOut);
+        assert_eq!(report.joints[0].observed, Some(50));
+        assert!(!report.joints[0].reached);
+        assert!(report.joints[1..].iter().all(|joint| joint.reached));
+    }
+
+    #[test]
+    fn send_and_verify_stops_on_cancel() {
+        let (manager, _rx) = connected_manager(joint_1_frame(50));
+        let manager = Arc::new(manager);
+        let token = manager.motion_token();
+        let verifier = {
+            let manager = manager.clone();
+            thread::spawn(move || {
+                let target = RobotState {
+                    joint_1: 70,
+                    ..state(100)
+                };
+                manager.send_and_verify(&target, 2, MAX_VERIFY_TIMEOUT, token)
+            })
+        };
+        wait_until(|| manager.last_command().is_some());
+        let started = Instant::now();
+        manager.cancel_motion();
+        let report = verifier.join().unwrap().unwrap();
+        assert_eq!(report.outcome, VerifyOutcome::Cancelled);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    // 타임스탬프 위치 14에 timestamp를 담은 상태 패킷
+    fn timestamped_frame(timestamp: u32) -> Vec<u8> {
+        let mut frame = encode_command(&state(90), IoLayout::Expanded);
+        frame.pop();
+        frame.extend(timestamp.to_le_bytes());
+        frame.push(254);
+        frame
+    }
+
+    #[test]
+    fn latency_histogram_uses_wrapping_device_clock() {
+        let stream: Vec<u8> = [u32::MAX - 5, 10, 26, 42]
+            .into_iter()
+            .flat_map(timestamped_frame)
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        manager.set_timestamp_offset(Some(14)).unwrap();
+
+        let histogram = manager
+            .latency_histogram(4, Duration::from_millis(10), manager.motion_token())
+            .unwrap();
+        assert!(histogram.device_clock);
+        assert_eq!(histogram.counts, vec![0, 3]);
+        assert_eq!(histogram.failures, 0);
+
+        let last = manager.last_raw_frame().unwrap();
+        assert_eq!(last.state.device_timestamp, Some(42));
+        assert_eq!(last.device_interval_ms, Some(16));
+    }
+
+    #[test]
+    fn verify_calibration_reports_near_zero_error_when_read_back_matches() {
+        let (addr, _rx) = mock_responder(Vec::new(), echo_responder);
+        let manager = SerialPortManager::new();
+        manager.initialize_tcp(&addr).unwrap();
+        assert!(manager.verify_calibration(0).is_err());
+        manager.send_command(&state(100)).unwrap();
+        assert!(manager.verify_calibration(0).is_err());
+
+        manager.set_joint_limit(2, 40, 160).unwrap();
+        manager
+            .set_calibration(2, JointCalibration::fit(-90.0, 0, 90.0, 200).unwrap())
+            .unwrap();
+        let reports = manager.verify_calibration(manager.motion_token()).unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].joint, 2);
+        assert_eq!(reports[0].points.len(), 3);
+        // raw 1단위가 0.9도이므로 반올림 오차 이내
+        assert!(reports[0].max_error_deg.unwrap() <= 0.45);
+        for point in &reports[0].points {
+            assert_eq!(point.read_raw, Some(point.commanded_raw));
+            assert!((40..=160).contains(&point.commanded_raw));
+        }
+        // 확인이 끝나면 시작 자세로 복귀
+        assert_eq!(manager.last_command().unwrap().joint_2, 100);
+    }
+
+    #[test]
+    fn verify_calibration_stops_on_cancel() {
+        let (addr, rx) = mock_responder(Vec::new(), echo_responder);
+        let manager = Arc::new(SerialPortManager::new());
+        manager.initialize_tcp(&addr).unwrap();
+        manager.send_command(&state(100)).unwrap();
+        manager
+            .set_calibration(2, JointCalibration::fit(-90.0, 0, 90.0, 200).unwrap())
+            .unwrap();
+
+        let token = manager.motion_token();
+        let verifier = {
+            let manager = manager.clone();
+            thread::spawn(move || manager.verify_calibration(token))
+        };
+        // 첫 목표로 이동하는 도중 취소
+        received(&rx, 15 * 4);
+        manager.cancel_motion();
+        assert!(verifier.join().unwrap().unwrap_err().contains("취소"));
+    }
+}
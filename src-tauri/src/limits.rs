@@ -0,0 +1,256 @@
+// src-tauri/limits.rs
+
+use serde::{Deserialize, Serialize};
+
+use crate::serial::{joint, set_joint, RobotState};
+
+// 관절 값 범위 구조체 정의 (min, max 포함)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JointRange {
+    pub joint: u8,
+    pub min: u8,
+    pub max: u8,
+}
+
+// 관절별 제한 확인 결과 구조체 정의 (margin: 가까운 한계까지 거리, 범위 밖이면 음수)
+#[derive(Serialize)]
+pub struct JointLimitStatus {
+    pub joint: u8,
+    pub value: u8,
+    pub min: u8,
+    pub max: u8,
+    pub within: bool,
+    pub margin: i16,
+}
+
+// 자세 제한 확인 결과 구조체 정의
+#[derive(Serialize)]
+pub struct LimitCheck {
+    pub ok: bool,
+    pub per_joint_violations: Vec<u8>,
+    pub joints: Vec<JointLimitStatus>,
+}
+
+// 관절 공간 금지 영역 구조체 정의 (모든 범위를 동시에 만족하면 영역 안)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KeepoutRegion {
+    pub name: String,
+    pub constraints: Vec<JointRange>,
+}
+
+impl KeepoutRegion {
+    // 영역 검사 (관절 범위가 하나 이상이고 각 범위가 올바른지)
+    pub fn validate(&self) -> Result<(), String> {
+        if self.constraints.is_empty() {
+            return Err("금지 영역에는 관절 범위가 하나 이상 필요합니다.".into());
+        }
+        for range in &self.constraints {
+            if !(1..=6).contains(&range.joint) {
+                return Err("관절 번호는 1에서 6 사이여야 합니다.".into());
+            }
+            if range.min > range.max {
+                return Err(format!(
+                    "관절 {} 범위의 최솟값이 최댓값보다 큽니다.",
+                    range.joint
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // 자세가 영역 안에 있는지 확인
+    pub fn contains(&self, robot_state: &RobotState) -> bool {
+        self.constraints
+            .iter()
+            .all(|range| (range.min..=range.max).contains(&joint(robot_state, range.joint)))
+    }
+}
+
+// 관절 제한 일괄 검사 함수 (하나라도 최솟값이 최댓값보다 크면 오류)
+pub fn validate_limits(limits: &[(u8, u8); 6]) -> Result<(), String> {
+    for (i, &(min, max)) in limits.iter().enumerate() {
+        if min > max {
+            return Err(format!("관절 {} 제한의 최솟값이 최댓값보다 큽니다.", i + 1));
+        }
+    }
+    Ok(())
+}
+
+// 관절별 (최솟값, 최댓값)을 관절 번호가 붙은 범위 목록으로 변환
+pub fn limit_ranges(limits: &[(u8, u8); 6]) -> Vec<JointRange> {
+    limits
+        .iter()
+        .enumerate()
+        .map(|(i, &(min, max))| JointRange {
+            joint: i as u8 + 1,
+            min,
+            max,
+        })
+        .collect()
+}
+
+// 자세의 관절 제한 확인 함수
+pub fn check_pose(robot_state: &RobotState, limits: &[(u8, u8); 6]) -> LimitCheck {
+    let joints: Vec<JointLimitStatus> = limit_ranges(limits)
+        .into_iter()
+        .map(|range| {
+            let value = joint(robot_state, range.joint);
+            JointLimitStatus {
+                joint: range.joint,
+                value,
+                min: range.min,
+                max: range.max,
+                within: (range.min..=range.max).contains(&value),
+                margin: (value as i16 - range.min as i16).min(range.max as i16 - value as i16),
+            }
+        })
+        .collect();
+    let per_joint_violations: Vec<u8> = joints
+        .iter()
+        .filter(|status| !status.within)
+        .map(|status| status.joint)
+        .collect();
+    LimitCheck {
+        ok: per_joint_violations.is_empty(),
+        per_joint_violations,
+        joints,
+    }
+}
+
+// 한계 접근 감속 설정 (margin: 감속을 시작할 한계까지 raw 거리, 0이면 끔)
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub struct ApproachSlowdown {
+    pub margin: u8,
+    // 한계 바로 앞에서 쓸 최저 속도
+    pub min_speed: u8,
+}
+
+impl ApproachSlowdown {
+    // 가장 가까운 한계까지 거리에 비례해 속도 감소 (margin 이상 떨어져 있으면 그대로)
+    pub fn speed(&self, robot_state: &RobotState, limits: &[(u8, u8); 6]) -> u8 {
+        let speed = robot_state.robot_speed;
+        if self.margin == 0 || speed <= self.min_speed {
+            return speed;
+        }
+        let closest = limit_ranges(limits)
+            .iter()
+            .map(|range| {
+                let value = joint(robot_state, range.joint);
+                value
+                    .saturating_sub(range.min)
+                    .min(range.max.saturating_sub(value))
+            })
+            .min()
+            .unwrap_or(u8::MAX);
+        if closest >= self.margin {
+            return speed;
+        }
+        let span = (speed - self.min_speed) as u32;
+        self.min_speed + (span * closest as u32 / self.margin as u32) as u8
+    }
+}
+
+// 관절 값을 제한 범위로 조정하는 함수 (조정된 관절 번호 함께 반환)
+pub fn clamp_pose(robot_state: &RobotState, limits: &[(u8, u8); 6]) -> (RobotState, Vec<u8>) {
+    let mut clamped = robot_state.clone();
+    let mut clamped_joints = Vec::new();
+    for range in limit_ranges(limits) {
+        let value = joint(robot_state, range.joint);
+        let limited = value.clamp(range.min, range.max);
+        if limited != value {
+            set_joint(&mut clamped, range.joint, limited);
+            clamped_joints.push(range.joint);
+        }
+    }
+    (clamped, clamped_joints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::state;
+
+    fn clash_region() -> KeepoutRegion {
+        KeepoutRegion {
+            name: "clash".into(),
+            constraints: vec![
+                JointRange {
+                    joint: 2,
+                    min: 150,
+                    max: 255,
+                },
+                JointRange {
+                    joint: 3,
+                    min: 150,
+                    max: 255,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn keepout_region_contains_pose_only_inside_every_range() {
+        let region = clash_region();
+        region.validate().unwrap();
+        assert!(region.contains(&state(160)));
+        let nearby = RobotState {
+            joint_3: 149,
+            ..state(160)
+        };
+        assert!(!region.contains(&nearby));
+
+        let empty = KeepoutRegion {
+            name: "empty".into(),
+            constraints: Vec::new(),
+        };
+        assert!(empty.validate().is_err());
+        let bad_joint = KeepoutRegion {
+            name: "bad".into(),
+            constraints: vec![JointRange {
+                joint: 7,
+                min: 0,
+                max: 1,
+            }],
+        };
+        assert!(bad_joint.validate().is_err());
+    }
+
+    #[test]
+    fn check_pose_flags_only_the_violating_joint() {
+        let mut limits = [(0, 255); 6];
+        limits[0] = (0, 60);
+        limits[1] = (10, 100);
+        let pose = RobotState {
+            joint_2: 120,
+            ..state(50)
+        };
+
+        let check = check_pose(&pose, &limits);
+        assert!(!check.ok);
+        assert_eq!(check.per_joint_violations, vec![2]);
+        assert_eq!(check.joints[0].margin, 10);
+        assert_eq!(check.joints[1].margin, -20);
+        assert!(check.joints[2].within);
+
+        let (clamped, clamped_joints) = clamp_pose(&pose, &limits);
+        assert_eq!(clamped_joints, vec![2]);
+        assert_eq!(clamped.joint_2, 100);
+        assert_eq!(clamped.joint_1, 50);
+    }
+
+    #[test]
+    fn slowdown_scales_speed_inside_margin() {
+        let limits = [(10, 200); 6];
+        let slowdown = ApproachSlowdown {
+            margin: 20,
+            min_speed: 10,
+        };
+        // 여유가 충분하면 속도 유지
+        assert_eq!(slowdown.speed(&state(100), &limits), 50);
+        // 최소 한계까지 10 남음: 10 + 40 * 10 / 20
+        assert_eq!(slowdown.speed(&state(20), &limits), 30);
+        // 최대 한계에 닿으면 최저 속도
+        assert_eq!(slowdown.speed(&state(200), &limits), 10);
+        assert_eq!(ApproachSlowdown::default().speed(&state(200), &limits), 50);
+    }
+}
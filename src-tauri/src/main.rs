@@ -1,4 +1,3 @@
-
 // main.rs 수정
 // src-tauri/src/main.rs
 
@@ -7,21 +6,216 @@
     windows_subsystem = "windows"
 )]
 
+mod calibration;
+mod codec;
+mod deadman;
+mod diagnostics;
+mod limits;
+#[cfg(feature = "metrics-server")]
+mod metrics_server;
+mod motion;
+mod pipe;
+mod poses;
 mod serial;
+mod sessions;
+mod streaming;
+mod tasks;
+#[cfg(test)]
+mod test_support;
+mod transport;
 
-use serial::{initialize_serial, list_serial_ports, read_robot_state, send_robot_commands, AppState, SerialPortManager};
+use deadman::{deadman_heartbeat, set_deadman};
+use diagnostics::{
+    auto_measure_actuation_latency, auto_tune_timeout, latency_histogram, measure_throughput,
+    sample_joint_statistics, send_and_verify, step_response_test, verify_calibration,
+    verify_feedback,
+};
+use motion::{
+    benchmark_planner, burn_in, cancel_motion, goto_pose, guarded_move, jog_start, jog_stop,
+    reverse_motion, run_oscillation, set_actuation_latency, set_interp_sync, set_joint_phase,
+    set_pinned_joints, walk_outputs,
+};
+use serial::{
+    adapt_recording, add_keepout_region, analyze_capture, auto_connect, calibrate_joint,
+    cancel_all_tasks, cancel_task, clear_controller_fault, decode_frames, delete_pose,
+    disconnect_port, export_pose_preset, get_buffer_overrun_count, get_calibration,
+    get_capabilities, get_health, get_joint_info, get_joint_limits, get_last_raw_frame,
+    get_serial_config, get_session_history, handle_frontend_loss, identify_device,
+    import_pose_preset, initialize_serial, initialize_tcp, is_pose_within_limits,
+    list_active_tasks, list_custom_commands, list_keepout_regions, list_poses, list_serial_ports,
+    max_command_rate, read_robot_state, read_robot_state_full, read_robot_states,
+    read_signal_lines, read_until, register_command, remove_keepout_region, restore_state,
+    save_pose, send_custom, send_in_units, send_normalized, send_robot_commands,
+    set_all_joint_limits, set_checksum_offset, set_delta_commands, set_device_timestamp,
+    set_error_frames, set_fault_offset, set_frame_encoding, set_frame_trailing_mode,
+    set_frontend_loss_behavior, set_global_speed_scale, set_home_on_connect, set_init_pose,
+    set_input_binding, set_io_layout, set_io_mirror, set_joint_limits, set_joint_response_curve,
+    set_joint_unit, set_limit_approach_margin, set_output_dwell, set_read_buffer_size,
+    set_require_handshake, set_stuck_detection, set_voltage_offset, set_voltage_threshold,
+    snapshot_state, spawn_input_worker, start_metrics_server, stop_metrics_server, AppState,
+    SerialPortManager,
+};
 use std::sync::Arc;
+#[cfg(feature = "metrics-server")]
+use std::sync::Mutex;
+use streaming::{
+    get_metrics_history, set_metrics_sampling, set_read_error_policy, set_telemetry_decimation,
+    start_ndjson_log, start_pipe_stream, start_state_stream, stop_ndjson_log, stop_pipe_stream,
+    stop_state_stream,
+};
+use tasks::TaskRegistry;
+use tauri::Manager;
 
 fn main() {
+    let app_state = AppState {
+        serial_manager: Arc::new(SerialPortManager::new()),
+        #[cfg(feature = "metrics-server")]
+        metrics_server: Arc::new(Mutex::new(None)),
+        tasks: Arc::new(TaskRegistry::new()),
+    };
+    // 입력 바인딩 동작은 수신 경로 밖의 처리 스레드에서 실행
+    spawn_input_worker(&app_state);
+    let window_state = app_state.clone();
+    let setup_state = app_state.clone();
+
     tauri::Builder::default()
-        .manage(AppState {
-            serial_manager: Arc::new(SerialPortManager::new()),
+        .manage(app_state)
+        // 연결 기록과 저장 자세는 앱 데이터 폴더에 저장해 재시작 후에도 유지
+        .setup(move |app| {
+            match app.path().app_data_dir() {
+                Ok(dir) => {
+                    if let Err(e) = setup_state
+                        .serial_manager
+                        .load_session_history(dir.join("sessions.json"))
+                    {
+                        println!("Loading session history failed: {}", e);
+                    }
+                    if let Err(e) = setup_state
+                        .serial_manager
+                        .load_poses(dir.join("poses.json"))
+                    {
+                        println!("Loading poses failed: {}", e);
+                    }
+                }
+                Err(e) => println!("App data directory unavailable: {}", e),
+            }
+            Ok(())
+        })
+        // 창이 닫히면 진행 중인 동작을 계속하지 않도록 처리
+        .on_window_event(move |_window, event| {
+            if let tauri::WindowEvent::Destroyed = event {
+                handle_frontend_loss(&window_state);
+            }
         })
         .invoke_handler(tauri::generate_handler![
             list_serial_ports,
             initialize_serial,
+            initialize_tcp,
             send_robot_commands,
-            read_robot_state
+            read_robot_state,
+            read_robot_states,
+            set_device_timestamp,
+            set_init_pose,
+            walk_outputs,
+            cancel_motion,
+            get_health,
+            measure_throughput,
+            set_error_frames,
+            set_read_buffer_size,
+            read_signal_lines,
+            set_global_speed_scale,
+            max_command_rate,
+            set_frame_trailing_mode,
+            set_frame_encoding,
+            auto_connect,
+            save_pose,
+            goto_pose,
+            list_poses,
+            delete_pose,
+            get_serial_config,
+            run_oscillation,
+            set_metrics_sampling,
+            get_metrics_history,
+            set_io_mirror,
+            get_last_raw_frame,
+            set_stuck_detection,
+            start_pipe_stream,
+            stop_pipe_stream,
+            set_io_layout,
+            set_input_binding,
+            start_metrics_server,
+            stop_metrics_server,
+            add_keepout_region,
+            remove_keepout_region,
+            list_keepout_regions,
+            set_joint_limits,
+            get_joint_limits,
+            is_pose_within_limits,
+            start_state_stream,
+            stop_state_stream,
+            set_read_error_policy,
+            calibrate_joint,
+            get_calibration,
+            snapshot_state,
+            restore_state,
+            set_output_dwell,
+            set_checksum_offset,
+            guarded_move,
+            list_active_tasks,
+            cancel_task,
+            cancel_all_tasks,
+            auto_tune_timeout,
+            set_frontend_loss_behavior,
+            adapt_recording,
+            decode_frames,
+            get_capabilities,
+            reverse_motion,
+            set_delta_commands,
+            sample_joint_statistics,
+            latency_histogram,
+            jog_start,
+            jog_stop,
+            set_voltage_offset,
+            set_voltage_threshold,
+            set_all_joint_limits,
+            start_ndjson_log,
+            stop_ndjson_log,
+            step_response_test,
+            verify_feedback,
+            set_deadman,
+            deadman_heartbeat,
+            send_and_verify,
+            register_command,
+            list_custom_commands,
+            send_custom,
+            set_telemetry_decimation,
+            read_until,
+            export_pose_preset,
+            import_pose_preset,
+            set_actuation_latency,
+            set_pinned_joints,
+            set_joint_phase,
+            set_interp_sync,
+            benchmark_planner,
+            auto_measure_actuation_latency,
+            disconnect_port,
+            get_session_history,
+            set_joint_response_curve,
+            send_normalized,
+            burn_in,
+            get_buffer_overrun_count,
+            set_joint_unit,
+            get_joint_info,
+            send_in_units,
+            analyze_capture,
+            set_home_on_connect,
+            verify_calibration,
+            set_limit_approach_margin,
+            read_robot_state_full,
+            identify_device,
+            set_require_handshake,
+            set_fault_offset,
+            clear_controller_fault
         ])
         .run(tauri::generate_context!())
         .expect("Tauri 애플리케이션 실행 중 오류 발생");
@@ -0,0 +1,142 @@
+// src-tauri/metrics_server.rs
+
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// 연결 대기 확인 간격
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+// 요청 읽기 타임아웃
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Prometheus 텍스트 형식 지표를 제공하는 HTTP 서버
+pub struct MetricsServer {
+    port: u16,
+    running: Arc<AtomicBool>,
+}
+
+impl MetricsServer {
+    // 로컬 주소에서 /metrics 제공 시작 (port 0이면 임의 포트)
+    pub fn start<F>(port: u16, render: F) -> Result<Self, String>
+    where
+        F: Fn() -> String + Send + 'static,
+    {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .map_err(|e| format!("지표 서버를 시작할 수 없습니다 (포트 {}): {}", port, e))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("지표 서버 주소 확인 실패: {}", e))?
+            .port();
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("지표 서버 설정 실패: {}", e))?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        {
+            let running = running.clone();
+            thread::spawn(move || {
+                while running.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            if let Err(e) = respond(stream, &render) {
+                                println!("Metrics request failed: {}", e);
+                            }
+                        }
+                        Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                            thread::sleep(ACCEPT_INTERVAL)
+                        }
+                        Err(e) => {
+                            println!("Metrics server accept failed: {}", e);
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+
+        Ok(Self { port, running })
+    }
+
+    // 실제 대기 중인 포트
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+// 요청 한 건 처리 함수 (GET /metrics 외에는 404)
+fn respond<F: Fn() -> String>(stream: TcpStream, render: &F) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // 요청 헤더는 빈 줄까지 읽고 버림
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            render(),
+        ),
+        _ => (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "not found\n".into(),
+        ),
+    };
+
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(port: u16, path: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_rendered_metrics_only_on_metrics_path() {
+        let server = MetricsServer::start(0, || "robot_arm_frames_sent_total 3\n".into()).unwrap();
+
+        let response = get(server.port(), "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        assert!(response.contains("text/plain; version=0.0.4"));
+        assert!(response.ends_with("\r\n\r\nrobot_arm_frames_sent_total 3\n"));
+        assert!(get(server.port(), "/").starts_with("HTTP/1.1 404"));
+
+        let port = server.port();
+        drop(server);
+        thread::sleep(ACCEPT_INTERVAL * 3);
+        assert!(TcpStream::connect(("127.0.0.1", port)).is_err());
+    }
+}
@@ -0,0 +1,1647 @@
+// src-tauri/motion.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::serial::{
+    joint, joint_change, lock, offset_joint, register_motion_task, round_joint, set_digital_output,
+    set_joint, AppState, RobotState, SerialPortManager,
+};
+
+// 왕복 동작 명령 전송 간격
+const OSCILLATION_STEP: Duration = Duration::from_millis(20);
+// 출력 점검 시 출력당 켜짐 시간 상한
+const MAX_OUTPUT_WALK_ON: Duration = Duration::from_secs(10);
+// 동작 중 대기할 때 취소 요청 확인 간격
+pub(crate) const MOTION_WAIT_SLICE: Duration = Duration::from_millis(20);
+// 저장 자세 이동, 보정 확인 등 천천히 이동할 때 단계 간격 및 단계당 최대 관절 변화량
+const POSE_MOVE_STEP: Duration = Duration::from_millis(20);
+const POSE_MOVE_MAX_DELTA: u8 = 2;
+// 길들이기 동작 명령 전송 간격
+const BURN_IN_STEP: Duration = Duration::from_millis(20);
+// 감시 이동 단계 간격 및 단계당 최대 관절 변화량
+const GUARDED_MOVE_STEP: Duration = Duration::from_millis(20);
+const GUARDED_MOVE_MAX_DELTA: u8 = 2;
+// 명령-동작 지연 설정 상한
+const MAX_ACTUATION_LATENCY: Duration = Duration::from_secs(2);
+// 감시 이동 되돌리기 시 감속/가속 단계 수
+const REVERSE_RAMP_STEPS: usize = 3;
+// 연속 조그 명령 전송 간격, 목표 속도까지 가속 시간, 최대 속도 (raw 단위/초)
+const JOG_STEP: Duration = Duration::from_millis(20);
+const JOG_RAMP: Duration = Duration::from_millis(200);
+const MAX_JOG_SPEED: f64 = 500.0;
+
+// 사인파 왕복 궤적 생성 함수 (중앙에서 시작해 cycles 주기 후 중앙에서 끝남)
+pub fn oscillation_profile(min: u8, max: u8, freq_hz: f64, cycles: u32, step: Duration) -> Vec<u8> {
+    let center = (min as f64 + max as f64) / 2.0;
+    let amplitude = (max as f64 - min as f64) / 2.0;
+    let samples = (cycles as f64 / freq_hz / step.as_secs_f64()).round() as usize;
+    (0..=samples)
+        .map(|i| {
+            let t = i as f64 * step.as_secs_f64();
+            let value = center + amplitude * (2.0 * std::f64::consts::PI * freq_hz * t).sin();
+            round_joint(value).clamp(min, max)
+        })
+        .collect()
+}
+
+// 한 관절 직선 이동 값 생성 함수 (from 다음 값부터 to까지, 단계당 최대 per_step만큼 변화)
+fn sweep_values(from: u8, to: u8, per_step: f64) -> Vec<u8> {
+    let steps = (from.abs_diff(to) as f64 / per_step).ceil() as usize;
+    (1..=steps)
+        .map(|step| {
+            let value = from as f64 + joint_change(from, to) as f64 * step as f64 / steps as f64;
+            round_joint(value)
+        })
+        .collect()
+}
+
+// 길들이기 진행 상태 (cycle: 1부터, 0이면 시작 위치로 이동/복귀 중)
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct BurnInProgress {
+    pub joint: u8,
+    pub cycle: u32,
+}
+
+// 길들이기 동작 순서 생성 함수
+// 관절 1부터 차례로 최솟값으로 이동해 최솟값→최댓값→최솟값을 cycles번 반복한 뒤 시작 값으로 복귀
+pub fn burn_in_sequence(
+    start: &RobotState,
+    limits: &[(u8, u8); 6],
+    cycles: u32,
+    per_step: f64,
+) -> Vec<(BurnInProgress, RobotState)> {
+    let mut pose = start.clone();
+    let mut sequence = Vec::new();
+    for index in 1..=6u8 {
+        let (min, max) = limits[index as usize - 1];
+        let origin = joint(start, index).clamp(min, max);
+        // 시작 위치가 한계를 벗어나 있으면 한계 안으로 옮긴 뒤 시작
+        set_joint(&mut pose, index, origin);
+        let mut legs = vec![(0, origin, min)];
+        for cycle in 1..=cycles {
+            legs.push((cycle, min, max));
+            legs.push((cycle, max, min));
+        }
+        legs.push((0, min, origin));
+        for (cycle, from, to) in legs {
+            for value in sweep_values(from, to, per_step) {
+                set_joint(&mut pose, index, value);
+                sequence.push((
+                    BurnInProgress {
+                        joint: index,
+                        cycle,
+                    },
+                    pose.clone(),
+                ));
+            }
+        }
+    }
+    sequence
+}
+
+// 관절별 이동 구간 (전체 이동 시간 대비 시작/끝 위치, 0.0~1.0)
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct JointPhase {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl Default for JointPhase {
+    fn default() -> Self {
+        Self {
+            start: 0.0,
+            end: 1.0,
+        }
+    }
+}
+
+impl JointPhase {
+    // 구간 안에서의 진행률 (시작 전 0.0, 끝난 뒤 1.0)
+    fn progress(&self, t: f64) -> f64 {
+        ((t - self.start) / (self.end - self.start)).clamp(0.0, 1.0)
+    }
+}
+
+// 보간 이동 관절 동기화 방식
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+pub enum InterpSync {
+    // 모든 관절이 가장 느린 관절에 맞춰 함께 도착
+    #[default]
+    Synchronized,
+    // 관절마다 최대 속도로 움직여 먼저 도착한 관절은 기다림
+    Independent,
+}
+
+// 보간 경로 설정
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct PathPlan {
+    pub phases: [JointPhase; 6],
+    pub sync: InterpSync,
+}
+
+impl PathPlan {
+    // 관절이 움직일 수 있는 구간 길이 (독립 이동은 시작 위치부터 끝까지)
+    fn span(&self, index: u8) -> f64 {
+        let phase = self.phases[index as usize - 1];
+        match self.sync {
+            InterpSync::Synchronized => phase.end - phase.start,
+            InterpSync::Independent => 1.0 - phase.start,
+        }
+    }
+
+    // step번째 단계에서 관절 진행률 (distance: 관절 이동 거리)
+    fn progress(&self, index: u8, step: usize, steps: usize, distance: f64, max_delta: f64) -> f64 {
+        let phase = self.phases[index as usize - 1];
+        match self.sync {
+            InterpSync::Synchronized => phase.progress(step as f64 / steps as f64),
+            InterpSync::Independent if distance == 0.0 => 1.0,
+            InterpSync::Independent => {
+                ((step as f64 - phase.start * steps as f64) * max_delta / distance).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+// 감시 이동 경로 생성 함수 (관절별 선형 보간, 첫 단계는 from 다음 위치이고 마지막 단계는 to)
+// 관절마다 자기 구간 안에서만 움직이며, 구간이 짧아도 단계당 max_delta를 넘지 않도록 단계 수를 늘림
+// 관절별 소요 단계는 이동 거리 / max_delta이고, 전체 단계 수는 가장 오래 걸리는 관절 기준
+pub fn guarded_path(
+    from: &RobotState,
+    to: &RobotState,
+    max_delta: u8,
+    plan: &PathPlan,
+) -> Vec<RobotState> {
+    let max_delta = max_delta.max(1) as f64;
+    let distance = |index| joint(from, index).abs_diff(joint(to, index)) as f64;
+    let steps = (1..=6)
+        .map(|index| (distance(index) / (max_delta * plan.span(index))).ceil() as usize)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    (1..=steps)
+        .map(|step| {
+            let mut state = to.clone();
+            for index in 1..=6 {
+                let start = joint(from, index);
+                let change = joint_change(start, joint(to, index)) as f64;
+                let progress = plan.progress(index, step, steps, distance(index), max_delta);
+                set_joint(
+                    &mut state,
+                    index,
+                    round_joint(start as f64 + change * progress),
+                );
+            }
+            state
+        })
+        .collect()
+}
+
+// 보간 경로 생성 벤치마크 결과
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct PlannerBenchmark {
+    // 생성한 경로 단계 수
+    pub frames: usize,
+    // 전체 경로 생성에 걸린 시간
+    pub generation_us: u64,
+    // 생성 속도로 낼 수 있는 최대 프레임 속도
+    pub max_rate_hz: f64,
+    // 요청한 프레임 속도를 따라갈 수 있는지 여부
+    pub sustainable: bool,
+}
+
+// 고정 관절을 반영한 이동 목표 (mask 비트에 해당하는 관절은 from 값 유지)
+pub fn pin_joints(from: &RobotState, to: &RobotState, mask: u8) -> RobotState {
+    let mut target = to.clone();
+    for index in 1..=6 {
+        if mask & (1 << (index - 1)) != 0 {
+            set_joint(&mut target, index, joint(from, index));
+        }
+    }
+    target
+}
+
+// 명령-동작 지연을 고려한 기대 위치 (commanded: 시작 자세부터 지금까지 전송한 단계)
+// 지연만큼 앞서 전송한 단계를 기대 위치로 사용하고, 기록보다 지연이 길면 시작 자세
+pub fn lagged_expectation(
+    commanded: &[RobotState],
+    latency: Duration,
+    step: Duration,
+) -> &RobotState {
+    let lag = (latency.as_secs_f64() / step.as_secs_f64()).round() as usize;
+    &commanded[commanded.len().saturating_sub(lag + 1)]
+}
+
+// 관절 확인용 이동 목표 (제한 안쪽으로 여유가 있는 방향으로 nudge만큼, 양쪽 다 좁으면 가능한 만큼만)
+pub(crate) fn nudge_target(value: u8, min: u8, max: u8, nudge: u8) -> u8 {
+    let start = value.clamp(min, max);
+    let room_up = joint_change(start, max);
+    let room_down = joint_change(min, start);
+    if room_up >= nudge as i32 || room_up >= room_down {
+        offset_joint(start, nudge as i32).min(max)
+    } else {
+        offset_joint(start, -(nudge as i32)).max(min)
+    }
+}
+
+// 감시 이동 되돌리기 일정 생성 함수
+// sent: 시작 자세부터 지금까지 전송한 단계, remaining: 아직 전송하지 않은 단계
+// 남은 경로를 따라 간격을 늘려 감속한 뒤, 지나온 경로를 간격을 줄여 가며 시작 자세까지 되돌아감
+pub fn reversal_schedule(
+    sent: &[RobotState],
+    remaining: &[RobotState],
+    step: Duration,
+) -> Vec<(RobotState, Duration)> {
+    let ramp = remaining.len().min(REVERSE_RAMP_STEPS);
+    let decelerate = remaining[..ramp]
+        .iter()
+        .enumerate()
+        .map(|(i, state)| (state.clone(), step * (i as u32 + 2)));
+    let back = remaining[..ramp.saturating_sub(1)]
+        .iter()
+        .rev()
+        .chain(sent.iter().rev().skip(usize::from(ramp == 0)))
+        .enumerate()
+        .map(|(i, state)| {
+            let factor = (ramp + 1).saturating_sub(i).max(1);
+            (state.clone(), step * factor as u32)
+        });
+    decelerate.chain(back).collect()
+}
+
+// 연속 조그 방향
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum JogDirection {
+    Positive,
+    Negative,
+}
+
+// 연속 조그 종료 사유
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub enum JogEnd {
+    // 관절 제한에 도달
+    LimitReached,
+    // jog_stop 또는 같은 관절의 새 조그로 중지
+    Stopped,
+    // cancel_motion으로 취소
+    Cancelled,
+}
+
+// 감시 이동 중 허용 오차를 넘은 관절 정보
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct GuardedDeviation {
+    pub joint: u8,
+    pub expected: u8,
+    pub actual: u8,
+}
+
+// 감시 이동 종료 사유
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub enum GuardedMoveEnd {
+    Done,
+    // cancel_motion 또는 새 동작으로 취소
+    Cancelled,
+    // 읽은 값이 기대 위치에서 벗어나 중단 (현재 위치 정지 명령 전송 후)
+    Aborted(GuardedDeviation),
+    // reverse_motion 요청으로 시작 자세까지 되돌아감
+    Reversed,
+}
+
+// 출력 점검 단계 이벤트 페이로드
+#[derive(Serialize, Clone)]
+pub struct OutputWalkStep {
+    pub output: u8,
+    pub on: bool,
+}
+
+// 연속 조그 종료 이벤트 페이로드
+#[derive(Serialize, Clone)]
+pub struct JogStopped {
+    pub joint: u8,
+    pub reason: JogEnd,
+}
+
+impl SerialPortManager {
+    // 백그라운드 동작 시작 시 취소 토큰 발급 함수
+    pub fn motion_token(&self) -> u64 {
+        self.motion_generation.load(Ordering::SeqCst)
+    }
+
+    // 토큰 발급 이후 취소 요청 여부 확인 함수
+    pub fn is_motion_cancelled(&self, token: u64) -> bool {
+        self.motion_generation.load(Ordering::SeqCst) != token
+    }
+
+    // 진행 중인 백그라운드 동작 취소 함수
+    pub fn cancel_motion(&self) {
+        self.motion_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // 취소 요청을 확인하며 duration 동안 대기 (취소되면 바로 false 반환)
+    pub(crate) fn sleep_unless_cancelled(&self, duration: Duration, token: u64) -> bool {
+        let deadline = Instant::now() + duration;
+        while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+            if self.is_motion_cancelled(token) {
+                return false;
+            }
+            thread::sleep(left.min(MOTION_WAIT_SLICE));
+        }
+        !self.is_motion_cancelled(token)
+    }
+
+    // 디지털 출력 순차 점검 함수 (출력 1~3을 하나씩 on 동안 켰다 끄고, 끝나면 모두 끔)
+    pub fn walk_outputs<F: FnMut(u8, bool)>(
+        &self,
+        mut frame: RobotState,
+        on: Duration,
+        token: u64,
+        mut on_step: F,
+    ) -> Result<(), String> {
+        for index in 1..=3 {
+            set_digital_output(&mut frame, index, false);
+        }
+        let result = (1..=3).try_for_each(|output| {
+            for on_state in [true, false] {
+                if self.is_motion_cancelled(token) {
+                    return Ok(());
+                }
+                set_digital_output(&mut frame, output, on_state);
+                self.send_command(&frame)?;
+                on_step(output, on_state);
+                if !self.sleep_unless_cancelled(on, token) {
+                    return Ok(());
+                }
+            }
+            Ok::<(), String>(())
+        });
+
+        // 종료 시 (취소 포함) 모든 출력 끄기
+        for index in 1..=3 {
+            set_digital_output(&mut frame, index, false);
+        }
+        let _ = self.send_command(&frame);
+        result
+    }
+
+    // 명령-동작 지연 설정 함수 (감시 이동 기대 위치 계산에 사용)
+    pub fn set_actuation_latency(&self, latency: Duration) -> Result<(), String> {
+        if latency > MAX_ACTUATION_LATENCY {
+            return Err(format!(
+                "명령-동작 지연은 {}ms 이하여야 합니다.",
+                MAX_ACTUATION_LATENCY.as_millis()
+            ));
+        }
+        *lock(&self.actuation_latency) = latency;
+        Ok(())
+    }
+
+    // 보간 이동 중 고정할 관절 설정 함수 (비트 0: 관절 1 ~ 비트 5: 관절 6)
+    pub fn set_pinned_joints(&self, mask: u8) -> Result<(), String> {
+        if mask > 0b11_1111 {
+            return Err(
+                "고정 마스크는 관절 1~6에 해당하는 하위 6비트만 사용할 수 있습니다.".into(),
+            );
+        }
+        self.pinned_joints.store(mask, Ordering::SeqCst);
+        Ok(())
+    }
+
+    // 고정된 관절 비트마스크 조회 함수
+    pub fn pinned_joints(&self) -> u8 {
+        self.pinned_joints.load(Ordering::SeqCst)
+    }
+
+    // 관절별 이동 구간 설정 함수 (구간은 0.0~1.0 안에서 시작 < 끝)
+    pub fn set_joint_phases(&self, phases: [JointPhase; 6]) -> Result<(), String> {
+        for (i, phase) in phases.iter().enumerate() {
+            if !(0.0..=1.0).contains(&phase.start) || !(0.0..=1.0).contains(&phase.end) {
+                return Err(format!(
+                    "관절 {}의 이동 구간은 0.0에서 1.0 사이여야 합니다.",
+                    i + 1
+                ));
+            }
+            if phase.start >= phase.end {
+                return Err(format!(
+                    "관절 {}의 이동 구간 시작은 끝보다 앞서야 합니다.",
+                    i + 1
+                ));
+            }
+        }
+        *lock(&self.joint_phases) = phases;
+        Ok(())
+    }
+
+    // 관절별 이동 구간 조회 함수
+    pub fn joint_phases(&self) -> [JointPhase; 6] {
+        *lock(&self.joint_phases)
+    }
+
+    // 보간 이동 관절 동기화 방식 설정 함수
+    pub fn set_interp_sync(&self, mode: InterpSync) {
+        *lock(&self.interp_sync) = mode;
+    }
+
+    // 보간 이동 관절 동기화 방식 조회 함수
+    pub fn interp_sync(&self) -> InterpSync {
+        *lock(&self.interp_sync)
+    }
+
+    // 보간 이동에 적용할 경로 설정
+    fn path_plan(&self) -> PathPlan {
+        PathPlan {
+            phases: self.joint_phases(),
+            sync: self.interp_sync(),
+        }
+    }
+
+    // 마지막 명령에서 target까지의 보간 경로를 전송 없이 생성해 생성 속도 측정
+    pub fn benchmark_planner(
+        &self,
+        target: &RobotState,
+        rate_hz: f64,
+    ) -> Result<PlannerBenchmark, String> {
+        if !(rate_hz > 0.0 && rate_hz.is_finite()) {
+            return Err("프레임 속도는 0보다 커야 합니다.".into());
+        }
+        let start = self
+            .last_command()
+            .ok_or("벤치마크 전에 로봇 명령을 먼저 전송해주세요.")?;
+        // 실제 보간 이동과 같은 목표와 설정으로 생성
+        let target = self
+            .clamp_to_limits(&pin_joints(&start, target, self.pinned_joints()))
+            .0;
+        let plan = self.path_plan();
+        let started = Instant::now();
+        let frames = guarded_path(&start, &target, POSE_MOVE_MAX_DELTA, &plan).len();
+        let elapsed = started.elapsed();
+        let max_rate_hz = frames as f64 / elapsed.as_secs_f64().max(f64::MIN_POSITIVE);
+        Ok(PlannerBenchmark {
+            frames,
+            generation_us: elapsed.as_micros() as u64,
+            max_rate_hz,
+            sustainable: max_rate_hz >= rate_hz,
+        })
+    }
+
+    fn is_pinned(&self, joint: u8) -> bool {
+        self.pinned_joints() & (1 << (joint - 1)) != 0
+    }
+
+    // 저장된 자세로 보낼 명령 (관절 값만 적용, 출력과 속도는 마지막 명령 유지)
+    fn pose_command(&self, name: &str) -> Result<RobotState, String> {
+        let pose = self
+            .pose(name)
+            .ok_or_else(|| format!("저장된 자세가 없습니다: {}", name))?;
+        Ok(match self.last_command() {
+            Some(last) => RobotState {
+                joint_1: pose.joint_1,
+                joint_2: pose.joint_2,
+                joint_3: pose.joint_3,
+                joint_4: pose.joint_4,
+                joint_5: pose.joint_5,
+                joint_6: pose.joint_6,
+                ..last
+            },
+            None => pose,
+        })
+    }
+
+    // 저장된 자세로 나눠 이동 함수 (마지막 명령부터 보간, 취소되면 false 반환)
+    // 이전 명령이 없으면 시작 위치를 모르므로 한 번에 전송
+    pub fn move_to_pose(&self, name: &str, token: u64) -> Result<bool, String> {
+        let target = self.pose_command(name)?;
+        let Some(start) = self.last_command() else {
+            self.send_command(&target)?;
+            return Ok(true);
+        };
+        self.move_gently(&start, &target, token)
+    }
+
+    // from에서 target까지 단계당 조금씩 나눠 이동 (취소되면 false 반환, 고정 관절은 from 값 유지)
+    pub(crate) fn move_gently(
+        &self,
+        from: &RobotState,
+        target: &RobotState,
+        token: u64,
+    ) -> Result<bool, String> {
+        let target = pin_joints(from, target, self.pinned_joints());
+        for step in guarded_path(from, &target, POSE_MOVE_MAX_DELTA, &self.path_plan()) {
+            if self.is_motion_cancelled(token) {
+                return Ok(false);
+            }
+            self.send_command(&step)?;
+            if !self.sleep_unless_cancelled(POSE_MOVE_STEP, token) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    // 감시 이동 함수 (단계마다 명령 전송 후 상태를 읽어 기대 위치와 비교)
+    pub fn guarded_move(
+        &self,
+        target: &RobotState,
+        tolerance: u8,
+        token: u64,
+    ) -> Result<GuardedMoveEnd, String> {
+        self.reverse_requested.store(false, Ordering::SeqCst);
+        self.guarded_move_active.store(true, Ordering::SeqCst);
+        let result = self.run_guarded_move(target, tolerance, token);
+        self.guarded_move_active.store(false, Ordering::SeqCst);
+        result
+    }
+
+    // 진행 중인 감시 이동 되돌리기 요청 함수 (감속 후 시작 자세로 복귀)
+    pub fn reverse_motion(&self) -> Result<(), String> {
+        if !self.guarded_move_active.load(Ordering::SeqCst) {
+            return Err("진행 중인 감시 이동이 없습니다.".into());
+        }
+        self.reverse_requested.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    // 관절 조그 토큰 발급 함수 (같은 관절의 이전 조그는 중지됨)
+    pub fn start_jog_token(&self, joint: u8) -> Result<u64, String> {
+        if !(1..=6).contains(&joint) {
+            return Err("관절 번호는 1에서 6 사이여야 합니다.".into());
+        }
+        let mut generations = lock(&self.jog_generations);
+        generations[joint as usize - 1] += 1;
+        Ok(generations[joint as usize - 1])
+    }
+
+    // 관절 조그 중지 함수
+    pub fn jog_stop(&self, joint: u8) -> Result<(), String> {
+        self.start_jog_token(joint).map(|_| ())
+    }
+
+    // 연속 조그 함수 (가속 후 일정 속도로 관절 제한이나 중지 요청까지 이동)
+    pub fn jog(
+        &self,
+        joint: u8,
+        direction: JogDirection,
+        speed: f64,
+        jog_token: u64,
+        motion_token: u64,
+    ) -> Result<JogEnd, String> {
+        if !(speed > 0.0 && speed <= MAX_JOG_SPEED) {
+            return Err(format!(
+                "조그 속도는 0보다 크고 {} 이하여야 합니다.",
+                MAX_JOG_SPEED
+            ));
+        }
+        if self.is_pinned(joint) {
+            return Err(format!(
+                "관절 {}은(는) 고정되어 있어 조그할 수 없습니다.",
+                joint
+            ));
+        }
+        let start = self
+            .last_command()
+            .ok_or("조그 전에 로봇 명령을 먼저 전송해주세요.")?;
+        let (min, max) = lock(&self.joint_limits)[joint as usize - 1];
+        let limit = match direction {
+            JogDirection::Positive => max,
+            JogDirection::Negative => min,
+        };
+        let sign = match direction {
+            JogDirection::Positive => 1.0,
+            JogDirection::Negative => -1.0,
+        };
+
+        let started = Instant::now();
+        let mut position = self::joint(&start, joint).clamp(min, max) as f64;
+        loop {
+            if self.is_motion_cancelled(motion_token) {
+                return Ok(JogEnd::Cancelled);
+            }
+            if lock(&self.jog_generations)[joint as usize - 1] != jog_token {
+                return Ok(JogEnd::Stopped);
+            }
+            let ramp = (started.elapsed().as_secs_f64() / JOG_RAMP.as_secs_f64()).min(1.0);
+            position = (position + sign * speed * ramp * JOG_STEP.as_secs_f64())
+                .clamp(min as f64, max as f64);
+            // 다른 관절 조그나 명령과 함께 쓰도록 매번 마지막 명령 기준으로 전송
+            let mut command = self.last_command().unwrap_or_else(|| start.clone());
+            let value = round_joint(position);
+            set_joint(&mut command, joint, value);
+            self.send_command(&command)?;
+            if value == limit {
+                return Ok(JogEnd::LimitReached);
+            }
+            thread::sleep(JOG_STEP);
+        }
+    }
+
+    fn run_guarded_move(
+        &self,
+        target: &RobotState,
+        tolerance: u8,
+        token: u64,
+    ) -> Result<GuardedMoveEnd, String> {
+        let start = self
+            .last_command()
+            .ok_or("감시 이동 전에 로봇 명령을 먼저 전송해주세요.")?;
+        // 관절 범위 제한과 같은 기준으로 기대 위치 계산
+        let target = self
+            .clamp_to_limits(&pin_joints(&start, target, self.pinned_joints()))
+            .0;
+        let mut schedule: VecDeque<(RobotState, Duration)> =
+            guarded_path(&start, &target, GUARDED_MOVE_MAX_DELTA, &self.path_plan())
+                .into_iter()
+                .map(|step| (step, GUARDED_MOVE_STEP))
+                .collect();
+        let latency = *lock(&self.actuation_latency);
+        // 되돌리기 여부와 상관없이 실제 전송한 순서 (지연 보정용)
+        let mut commanded = vec![start.clone()];
+        let mut sent = vec![start];
+        let mut reversed = false;
+        while let Some((step, interval)) = schedule.pop_front() {
+            if self.is_motion_cancelled(token) {
+                return Ok(GuardedMoveEnd::Cancelled);
+            }
+            if !reversed && self.reverse_requested.swap(false, Ordering::SeqCst) {
+                reversed = true;
+                let remaining: Vec<RobotState> = std::iter::once((step, interval))
+                    .chain(schedule.drain(..))
+                    .map(|(step, _)| step)
+                    .collect();
+                schedule.extend(reversal_schedule(&sent, &remaining, GUARDED_MOVE_STEP));
+                continue;
+            }
+            self.send_command(&step)?;
+            commanded.push(step.clone());
+            thread::sleep(interval);
+            let actual = self.read_data()?;
+            let expected_state = lagged_expectation(&commanded, latency, GUARDED_MOVE_STEP);
+            let deviation = (1..=6).find_map(|index| {
+                let expected = joint(expected_state, index);
+                let value = joint(&actual, index);
+                (expected.abs_diff(value) > tolerance).then_some(GuardedDeviation {
+                    joint: index,
+                    expected,
+                    actual: value,
+                })
+            });
+            if let Some(deviation) = deviation {
+                // 다른 백그라운드 동작도 멈추고 읽은 위치에서 정지
+                self.cancel_motion();
+                let hold = RobotState {
+                    joint_1: actual.joint_1,
+                    joint_2: actual.joint_2,
+                    joint_3: actual.joint_3,
+                    joint_4: actual.joint_4,
+                    joint_5: actual.joint_5,
+                    joint_6: actual.joint_6,
+                    ..step
+                };
+                self.send_command(&hold)?;
+                return Ok(GuardedMoveEnd::Aborted(deviation));
+            }
+            if !reversed {
+                sent.push(step);
+            }
+        }
+        Ok(if reversed {
+            GuardedMoveEnd::Reversed
+        } else {
+            GuardedMoveEnd::Done
+        })
+    }
+}
+
+// 디지털 출력 순차 점검 커맨드 (on_ms는 1ms 이상, 상한을 넘으면 상한으로 제한)
+#[tauri::command]
+pub fn walk_outputs(app: AppHandle, state: State<'_, AppState>, on_ms: u64) -> Result<(), String> {
+    if on_ms == 0 {
+        return Err("출력 켜짐 시간은 0보다 커야 합니다.".into());
+    }
+    let on = Duration::from_millis(on_ms).min(MAX_OUTPUT_WALK_ON);
+    let frame = state
+        .serial_manager
+        .last_command()
+        .ok_or("출력 점검 전에 로봇 명령을 먼저 전송해주세요.")?;
+    let manager = state.serial_manager.clone();
+    let token = manager.motion_token();
+    let task = register_motion_task(&state, "출력 순차 점검");
+
+    thread::spawn(move || {
+        let _task = task;
+        let result = manager.walk_outputs(frame, on, token, |output, on| {
+            let _ = app.emit("output_walk_step", OutputWalkStep { output, on });
+        });
+
+        match result {
+            Ok(()) if manager.is_motion_cancelled(token) => {
+                let _ = app.emit("output_walk_cancelled", ());
+            }
+            Ok(()) => {
+                let _ = app.emit("output_walk_done", ());
+            }
+            Err(e) => {
+                let _ = app.emit("output_walk_error", format!("출력 점검 실패: {}", e));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// 단일 관절 사인파 왕복 동작 커맨드
+#[tauri::command]
+pub fn run_oscillation(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    joint: u8,
+    min: u8,
+    max: u8,
+    freq_hz: f64,
+    cycles: u32,
+) -> Result<(), String> {
+    if !(1..=6).contains(&joint) {
+        return Err("관절 번호는 1에서 6 사이여야 합니다.".into());
+    }
+    if min > max {
+        return Err("최솟값이 최댓값보다 큽니다.".into());
+    }
+    let max_freq_hz = 1.0 / (2.0 * OSCILLATION_STEP.as_secs_f64());
+    if !(freq_hz > 0.0 && freq_hz <= max_freq_hz) {
+        return Err(format!(
+            "주파수는 0보다 크고 {}Hz 이하여야 합니다.",
+            max_freq_hz
+        ));
+    }
+    if cycles == 0 {
+        return Err("반복 횟수는 1 이상이어야 합니다.".into());
+    }
+    let mut frame = state
+        .serial_manager
+        .last_command()
+        .ok_or("왕복 동작 전에 로봇 명령을 먼저 전송해주세요.")?;
+    let profile = oscillation_profile(min, max, freq_hz, cycles, OSCILLATION_STEP);
+    let manager = state.serial_manager.clone();
+    let token = manager.motion_token();
+    let task = register_motion_task(&state, &format!("관절 {} 왕복 동작", joint));
+
+    thread::spawn(move || {
+        let _task = task;
+        let result = profile.iter().try_for_each(|&value| {
+            if manager.is_motion_cancelled(token) {
+                return Ok(());
+            }
+            set_joint(&mut frame, joint, value);
+            manager.send_command(&frame)?;
+            thread::sleep(OSCILLATION_STEP);
+            Ok::<(), String>(())
+        });
+
+        match result {
+            Ok(()) if manager.is_motion_cancelled(token) => {
+                let _ = app.emit("oscillation_cancelled", ());
+            }
+            Ok(()) => {
+                let _ = app.emit("oscillation_done", ());
+            }
+            Err(e) => {
+                let _ = app.emit("oscillation_error", format!("왕복 동작 실패: {}", e));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// 전 관절 길들이기 동작 커맨드 (speed: raw 단위/초, 관절 제한 범위를 차례로 반복 이동)
+#[tauri::command]
+pub fn burn_in(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    cycles: u32,
+    speed: f64,
+) -> Result<(), String> {
+    if cycles == 0 {
+        return Err("반복 횟수는 1 이상이어야 합니다.".into());
+    }
+    if !(speed > 0.0 && speed <= MAX_JOG_SPEED) {
+        return Err(format!(
+            "길들이기 속도는 0보다 크고 {} 이하여야 합니다.",
+            MAX_JOG_SPEED
+        ));
+    }
+    let start = state
+        .serial_manager
+        .last_command()
+        .ok_or("길들이기 전에 로봇 명령을 먼저 전송해주세요.")?;
+    let ranges = state.serial_manager.joint_limits();
+    let limits: [(u8, u8); 6] = std::array::from_fn(|i| (ranges[i].min, ranges[i].max));
+    let sequence = burn_in_sequence(&start, &limits, cycles, speed * BURN_IN_STEP.as_secs_f64());
+    let manager = state.serial_manager.clone();
+    let token = manager.motion_token();
+    let task = register_motion_task(&state, "전 관절 길들이기");
+
+    thread::spawn(move || {
+        let _task = task;
+        let mut last_progress = None;
+        let result = sequence.iter().try_for_each(|(progress, pose)| {
+            if manager.is_motion_cancelled(token) {
+                return Ok(());
+            }
+            // 관절이나 반복이 바뀔 때만 진행 이벤트 전송
+            if last_progress != Some(*progress) {
+                last_progress = Some(*progress);
+                let _ = app.emit("burn_in_progress", *progress);
+            }
+            manager.send_command(pose)?;
+            thread::sleep(BURN_IN_STEP);
+            Ok::<(), String>(())
+        });
+
+        match result {
+            Ok(()) if manager.is_motion_cancelled(token) => {
+                let _ = app.emit("burn_in_cancelled", ());
+            }
+            Ok(()) => {
+                let _ = app.emit("burn_in_done", ());
+            }
+            Err(e) => {
+                let _ = app.emit("burn_in_error", format!("길들이기 실패: {}", e));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// 감시 이동 커맨드 (읽은 관절 값이 기대 위치에서 tolerance를 넘게 벗어나면 중단)
+#[tauri::command]
+pub fn guarded_move(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    target: RobotState,
+    tolerance: u8,
+) -> Result<(), String> {
+    if state.serial_manager.last_command().is_none() {
+        return Err("감시 이동 전에 로봇 명령을 먼저 전송해주세요.".into());
+    }
+    let manager = state.serial_manager.clone();
+    let token = manager.motion_token();
+    let task = register_motion_task(&state, "감시 이동");
+
+    thread::spawn(move || {
+        let _task = task;
+        match manager.guarded_move(&target, tolerance, token) {
+            Ok(GuardedMoveEnd::Done) => {
+                let _ = app.emit("guarded_move_done", ());
+            }
+            Ok(GuardedMoveEnd::Cancelled) => {
+                let _ = app.emit("guarded_move_cancelled", ());
+            }
+            Ok(GuardedMoveEnd::Aborted(deviation)) => {
+                let _ = app.emit("guarded_move_aborted", deviation);
+            }
+            Ok(GuardedMoveEnd::Reversed) => {
+                let _ = app.emit("guarded_move_reversed", ());
+            }
+            Err(e) => {
+                let _ = app.emit("guarded_move_error", format!("감시 이동 실패: {}", e));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// 연속 조그 시작 커맨드 (speed: raw 단위/초, 관절 제한이나 jog_stop까지 이동)
+#[tauri::command]
+pub fn jog_start(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    joint: u8,
+    direction: JogDirection,
+    speed: f64,
+) -> Result<(), String> {
+    if !(speed > 0.0 && speed <= MAX_JOG_SPEED) {
+        return Err(format!(
+            "조그 속도는 0보다 크고 {} 이하여야 합니다.",
+            MAX_JOG_SPEED
+        ));
+    }
+    if state.serial_manager.last_command().is_none() {
+        return Err("조그 전에 로봇 명령을 먼저 전송해주세요.".into());
+    }
+    let manager = state.serial_manager.clone();
+    let jog_token = manager.start_jog_token(joint)?;
+    let motion_token = manager.motion_token();
+    let task = {
+        let manager = manager.clone();
+        state
+            .tasks
+            .register(format!("관절 {} 연속 조그", joint), move || {
+                let _ = manager.jog_stop(joint);
+            })
+    };
+
+    thread::spawn(move || {
+        let _task = task;
+        match manager.jog(joint, direction, speed, jog_token, motion_token) {
+            Ok(reason) => {
+                let _ = app.emit("jog_stopped", JogStopped { joint, reason });
+            }
+            Err(e) => {
+                let _ = app.emit("jog_error", format!("연속 조그 실패: {}", e));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// 연속 조그 중지 커맨드
+#[tauri::command]
+pub fn jog_stop(state: State<'_, AppState>, joint: u8) -> Result<(), String> {
+    state.serial_manager.jog_stop(joint)
+}
+
+// 진행 중인 감시 이동 되돌리기 커맨드 (cancel_motion과 달리 시작 자세로 복귀)
+#[tauri::command]
+pub fn reverse_motion(state: State<'_, AppState>) -> Result<(), String> {
+    state.serial_manager.reverse_motion()
+}
+
+// 진행 중인 백그라운드 동작 취소 커맨드
+#[tauri::command]
+pub fn cancel_motion(state: State<'_, AppState>) -> Result<(), String> {
+    state.serial_manager.cancel_motion();
+    Ok(())
+}
+
+// 명령-동작 지연 설정 커맨드
+#[tauri::command]
+pub fn set_actuation_latency(state: State<'_, AppState>, ms: u64) -> Result<(), String> {
+    state
+        .serial_manager
+        .set_actuation_latency(Duration::from_millis(ms))
+}
+
+// 보간 이동 중 고정할 관절 설정 커맨드
+#[tauri::command]
+pub fn set_pinned_joints(state: State<'_, AppState>, mask: u8) -> Result<(), String> {
+    state.serial_manager.set_pinned_joints(mask)
+}
+
+// 관절별 이동 구간 설정 커맨드 (관절 1~6 순서)
+#[tauri::command]
+pub fn set_joint_phase(state: State<'_, AppState>, phases: [JointPhase; 6]) -> Result<(), String> {
+    state.serial_manager.set_joint_phases(phases)
+}
+
+// 보간 이동 관절 동기화 방식 설정 커맨드
+#[tauri::command]
+pub fn set_interp_sync(state: State<'_, AppState>, mode: InterpSync) -> Result<(), String> {
+    state.serial_manager.set_interp_sync(mode);
+    Ok(())
+}
+
+// 보간 경로 생성 벤치마크 커맨드 (전송하지 않고 생성 시간만 측정)
+#[tauri::command]
+pub fn benchmark_planner(
+    state: State<'_, AppState>,
+    target: RobotState,
+    rate_hz: f64,
+) -> Result<PlannerBenchmark, String> {
+    state.serial_manager.benchmark_planner(&target, rate_hz)
+}
+
+// 저장된 자세로 이동 커맨드 (마지막 명령부터 나눠 이동, cancel_motion으로 중지)
+#[tauri::command]
+pub fn goto_pose(app: AppHandle, state: State<'_, AppState>, name: String) -> Result<(), String> {
+    if state.serial_manager.pose(&name).is_none() {
+        return Err(format!("저장된 자세가 없습니다: {}", name));
+    }
+    let manager = state.serial_manager.clone();
+    let token = manager.motion_token();
+    let task = register_motion_task(&state, &format!("자세 이동: {}", name));
+
+    thread::spawn(move || {
+        let _task = task;
+        match manager.move_to_pose(&name, token) {
+            Ok(true) => {
+                let _ = app.emit("goto_pose_done", name);
+            }
+            Ok(false) => {
+                let _ = app.emit("goto_pose_cancelled", name);
+            }
+            Err(e) => {
+                let _ = app.emit("goto_pose_error", format!("자세 이동 실패: {}", e));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{encode_command, IoLayout};
+    use crate::test_support::{
+        connected_manager, joint_1_frame, received, received_before, state, wait_until,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn joint_math_clamps_at_both_ends() {
+        // 위쪽 끝에서는 아래로, 아래쪽 끝에서는 위로 확인 이동
+        assert_eq!(nudge_target(255, 0, 255, 20), 235);
+        assert_eq!(nudge_target(0, 0, 255, 20), 20);
+        // 범위가 nudge보다 좁으면 가능한 만큼만
+        assert_eq!(nudge_target(250, 245, 255, 20), 255);
+
+        // 양 끝 사이를 오가는 경로도 범위를 벗어나지 않음
+        let path = guarded_path(&state(0), &state(255), 100, &PathPlan::default());
+        assert_eq!(path.len(), 3);
+        assert_eq!(path.last().unwrap().joint_1, 255);
+        let back = guarded_path(&state(255), &state(0), 100, &PathPlan::default());
+        assert_eq!(back.last().unwrap().joint_6, 0);
+        let profile = oscillation_profile(0, 255, 1.0, 1, Duration::from_millis(10));
+        assert!(profile.contains(&0) && profile.contains(&255));
+    }
+
+    #[test]
+    fn jog_stops_at_top_of_range_without_wrapping() {
+        let (manager, rx) = connected_manager(Vec::new());
+        manager.send_command(&state(250)).unwrap();
+        let _ = received(&rx, 15);
+        let token = manager.motion_token();
+        let jog_token = manager.start_jog_token(1).unwrap();
+        let end = manager
+            .jog(1, JogDirection::Positive, MAX_JOG_SPEED, jog_token, token)
+            .unwrap();
+        assert_eq!(end, JogEnd::LimitReached);
+        assert_eq!(manager.last_command().unwrap().joint_1, 255);
+    }
+
+    #[test]
+    fn cancel_motion_halts_frames_without_latching() {
+        let (manager, rx) = connected_manager(Vec::new());
+        let manager = Arc::new(manager);
+        manager.save_pose("far", state(250)).unwrap();
+        manager.send_command(&state(0)).unwrap();
+        let token = manager.motion_token();
+        let mover = {
+            let manager = manager.clone();
+            thread::spawn(move || manager.move_to_pose("far", token))
+        };
+        let mut sent = received(&rx, 15 * 3);
+        manager.cancel_motion();
+        assert!(!mover.join().unwrap().unwrap());
+
+        // 비상 정지와 달리 다음 명령은 바로 전송 가능
+        let marker = encode_command(&state(10), IoLayout::Expanded);
+        manager.send_command(&state(10)).unwrap();
+        sent.extend(received_before(&rx, &marker));
+        // 취소 전까지 보낸 보간 단계만 있고 목표 자세는 보내지 않음
+        let joint_1: Vec<u8> = sent.chunks(15).map(|frame| frame[1]).collect();
+        assert!(joint_1.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(joint_1.iter().all(|&value| value < 250));
+    }
+
+    #[test]
+    fn oscillation_stays_in_bounds_for_requested_cycles() {
+        // 1Hz 3주기를 20ms 간격으로 생성
+        let profile = oscillation_profile(10, 50, 1.0, 3, Duration::from_millis(20));
+        assert_eq!(profile.len(), 151);
+        assert!(profile.iter().all(|value| (10..=50).contains(value)));
+        assert_eq!((profile[0], profile[150]), (30, 30));
+        // 주기마다 최댓값에 한 번씩 도달
+        let peaks = profile
+            .windows(2)
+            .filter(|pair| pair[0] < 50 && pair[1] == 50)
+            .count();
+        assert_eq!(peaks, 3);
+        assert_eq!(profile.iter().min(), Some(&10));
+    }
+
+    #[test]
+    fn guarded_move_aborts_on_injected_deviation() {
+        let path = guarded_path(&state(10), &state(20), 2, &PathPlan::default());
+        let joint_1: Vec<u8> = path.iter().map(|step| step.joint_1).collect();
+        assert_eq!(joint_1, vec![12, 14, 16, 18, 20]);
+        assert_eq!(
+            guarded_path(&state(5), &state(5), 2, &PathPlan::default()).len(),
+            1
+        );
+
+        // 세 번째 읽기에서 관절 1이 예상 16 대신 30
+        let stream: Vec<u8> = [12, 14, 30]
+            .into_iter()
+            .flat_map(|joint| encode_command(&state(joint), IoLayout::Expanded))
+            .collect();
+        let (manager, rx) = connected_manager(stream);
+        manager.send_command(&state(10)).unwrap();
+        let token = manager.motion_token();
+        let end = manager.guarded_move(&state(20), 3, token).unwrap();
+        assert_eq!(
+            end,
+            GuardedMoveEnd::Aborted(GuardedDeviation {
+                joint: 1,
+                expected: 16,
+                actual: 30,
+            })
+        );
+        assert!(manager.is_motion_cancelled(token));
+        // 중단 후 읽은 자세를 유지하도록 명령
+        assert_eq!(manager.last_command().unwrap().joint_1, 30);
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        assert_eq!(received_before(&rx, &marker).len(), 15 * 5);
+    }
+
+    #[test]
+    fn guarded_move_completes_when_read_back_follows() {
+        let stream: Vec<u8> = [12, 14, 16, 18, 20]
+            .into_iter()
+            .flat_map(|joint| encode_command(&state(joint), IoLayout::Expanded))
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        manager.send_command(&state(10)).unwrap();
+        let end = manager
+            .guarded_move(&state(20), 1, manager.motion_token())
+            .unwrap();
+        assert_eq!(end, GuardedMoveEnd::Done);
+    }
+
+    #[test]
+    fn reversal_schedule_heads_back_to_start() {
+        let sent: Vec<RobotState> = [10, 12, 14].into_iter().map(state).collect();
+        let remaining: Vec<RobotState> = [16, 18, 20, 22].into_iter().map(state).collect();
+        let schedule = reversal_schedule(&sent, &remaining, Duration::from_millis(10));
+        let joint_1: Vec<u8> = schedule.iter().map(|(step, _)| step.joint_1).collect();
+        assert_eq!(joint_1, vec![16, 18, 20, 18, 16, 14, 12, 10]);
+        let delays: Vec<u128> = schedule
+            .iter()
+            .map(|(_, delay)| delay.as_millis())
+            .collect();
+        assert_eq!(delays, vec![20, 30, 40, 40, 30, 20, 10, 10]);
+    }
+
+    #[test]
+    fn reversing_mid_move_returns_to_start() {
+        let stream = encode_command(&state(0), IoLayout::Expanded).repeat(60);
+        let (manager, rx) = connected_manager(stream);
+        let manager = Arc::new(manager);
+        assert!(manager.reverse_motion().is_err());
+        manager.send_command(&state(10)).unwrap();
+
+        let moving = manager.clone();
+        let handle =
+            thread::spawn(move || moving.guarded_move(&state(60), 255, moving.motion_token()));
+        // 시작 명령 뒤 몇 단계 나간 다음 반전
+        let mut sent = received(&rx, 15 * 4);
+        manager.reverse_motion().unwrap();
+        assert_eq!(handle.join().unwrap().unwrap(), GuardedMoveEnd::Reversed);
+        assert_eq!(manager.last_command().unwrap().joint_1, 10);
+
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        sent.extend(received_before(&rx, &marker));
+        let joint_1: Vec<u8> = sent.chunks(15).map(|frame| frame[1]).collect();
+        let peak = joint_1.iter().copied().max().unwrap();
+        assert!(peak < 60);
+        let turn = joint_1.iter().position(|&value| value == peak).unwrap();
+        assert!(joint_1[turn..].windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(manager.reverse_motion().is_err());
+    }
+
+    #[test]
+    fn continuous_jog_halts_at_joint_limit() {
+        let (manager, rx) = connected_manager(Vec::new());
+        manager.send_command(&state(100)).unwrap();
+        manager.set_joint_limit(2, 0, 110).unwrap();
+        assert!(manager.start_jog_token(7).is_err());
+        let token = manager.start_jog_token(2).unwrap();
+        assert!(manager
+            .jog(
+                2,
+                JogDirection::Positive,
+                0.0,
+                token,
+                manager.motion_token()
+            )
+            .is_err());
+
+        let end = manager
+            .jog(
+                2,
+                JogDirection::Positive,
+                400.0,
+                token,
+                manager.motion_token(),
+            )
+            .unwrap();
+        assert_eq!(end, JogEnd::LimitReached);
+        assert_eq!(manager.last_command().unwrap().joint_2, 110);
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        let joint_2: Vec<u8> = received_before(&rx, &marker)
+            .chunks(15)
+            .map(|frame| frame[2])
+            .collect();
+        assert!(joint_2.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert!(joint_2.iter().all(|&value| value <= 110));
+    }
+
+    #[test]
+    fn continuous_jog_ends_on_stop_or_cancel() {
+        let manager = Arc::new(connected_manager(Vec::new()).0);
+        manager.send_command(&state(100)).unwrap();
+        let jog = |manager: &Arc<SerialPortManager>| {
+            let token = manager.start_jog_token(1).unwrap();
+            let motion_token = manager.motion_token();
+            let jogging = manager.clone();
+            thread::spawn(move || {
+                jogging
+                    .jog(1, JogDirection::Negative, 10.0, token, motion_token)
+                    .unwrap()
+            })
+        };
+
+        // 관절이 움직이기 시작하면 정지
+        let handle = jog(&manager);
+        wait_until(|| manager.last_command().unwrap().joint_1 < 100);
+        manager.jog_stop(1).unwrap();
+        assert_eq!(handle.join().unwrap(), JogEnd::Stopped);
+        let stopped_at = manager.last_command().unwrap().joint_1;
+        assert!(stopped_at < 100);
+
+        let handle = jog(&manager);
+        wait_until(|| manager.last_command().unwrap().joint_1 < stopped_at);
+        manager.cancel_motion();
+        assert_eq!(handle.join().unwrap(), JogEnd::Cancelled);
+    }
+
+    // 명령보다 두 패킷 늦게 따라오는 팔로 관절 1을 110까지 감시 이동
+    fn lagging_guarded_move(latency: Duration) -> GuardedMoveEnd {
+        let stream: Vec<u8> = [100, 100, 102, 104, 106]
+            .into_iter()
+            .flat_map(joint_1_frame)
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        manager.set_actuation_latency(latency).unwrap();
+        manager.send_command(&state(100)).unwrap();
+        let target = RobotState {
+            joint_1: 110,
+            ..state(100)
+        };
+        manager
+            .guarded_move(&target, 0, manager.motion_token())
+            .unwrap()
+    }
+
+    #[test]
+    fn actuation_latency_shifts_guarded_expectation() {
+        let history: Vec<RobotState> = [1, 2, 3, 4].into_iter().map(state).collect();
+        let step = Duration::from_millis(20);
+        assert_eq!(
+            lagged_expectation(&history, Duration::ZERO, step).joint_1,
+            4
+        );
+        assert_eq!(
+            lagged_expectation(&history, Duration::from_millis(40), step).joint_1,
+            2
+        );
+        assert_eq!(
+            lagged_expectation(&history, Duration::from_millis(500), step).joint_1,
+            1
+        );
+
+        assert_eq!(
+            lagging_guarded_move(Duration::from_millis(40)),
+            GuardedMoveEnd::Done
+        );
+        assert!(matches!(
+            lagging_guarded_move(Duration::ZERO),
+            GuardedMoveEnd::Aborted(_)
+        ));
+        assert!(SerialPortManager::new()
+            .set_actuation_latency(Duration::from_secs(3))
+            .is_err());
+    }
+
+    #[test]
+    fn burn_in_covers_each_joint_range_per_cycle() {
+        let limits = [
+            (10, 50),
+            (0, 255),
+            (100, 120),
+            (30, 30),
+            (0, 10),
+            (200, 240),
+        ];
+        let sequence = burn_in_sequence(&state(100), &limits, 3, 7.0);
+        let order: Vec<u8> = sequence
+            .iter()
+            .map(|(progress, _)| progress.joint)
+            .collect();
+        assert!(order.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        for (i, &(min, max)) in limits.iter().enumerate() {
+            let index = i as u8 + 1;
+            let values: Vec<u8> = sequence
+                .iter()
+                .filter(|(progress, _)| progress.joint == index)
+                .map(|(_, pose)| joint(pose, index))
+                .collect();
+            assert!(values.iter().all(|value| (min..=max).contains(value)));
+            assert!(values.windows(2).all(|pair| pair[0].abs_diff(pair[1]) <= 7));
+            // 움직일 범위가 없는 관절은 건너뜀
+            if min == max {
+                continue;
+            }
+            // 시작 값(범위 밖이면 가까운 한계)으로 복귀
+            assert_eq!(values.last(), Some(&100u8.clamp(min, max)));
+            for cycle in 1..=3 {
+                let swept: Vec<u8> = sequence
+                    .iter()
+                    .filter(|(progress, _)| progress.joint == index && progress.cycle == cycle)
+                    .map(|(_, pose)| joint(pose, index))
+                    .collect();
+                assert_eq!(swept.iter().filter(|&&value| value == max).count(), 1);
+                assert_eq!(swept.last(), Some(&min));
+            }
+        }
+    }
+
+    #[test]
+    fn walk_outputs_toggles_each_output_alone() {
+        let (manager, rx) = connected_manager(Vec::new());
+        let mut steps = Vec::new();
+        manager
+            .walk_outputs(
+                state(90),
+                Duration::from_millis(5),
+                manager.motion_token(),
+                |output, on| steps.push((output, on)),
+            )
+            .unwrap();
+
+        let outputs: Vec<[u8; 3]> = received(&rx, 15 * 7)
+            .chunks(15)
+            .map(|frame| [frame[10], frame[11], frame[12]])
+            .collect();
+        assert_eq!(
+            outputs,
+            vec![
+                [1, 0, 0],
+                [0, 0, 0],
+                [0, 1, 0],
+                [0, 0, 0],
+                [0, 0, 1],
+                [0, 0, 0],
+                [0, 0, 0],
+            ]
+        );
+        assert_eq!(
+            steps,
+            vec![
+                (1, true),
+                (1, false),
+                (2, true),
+                (2, false),
+                (3, true),
+                (3, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_outputs_stops_mid_sleep_when_cancelled() {
+        let (manager, rx) = connected_manager(Vec::new());
+        let manager = Arc::new(manager);
+        let token = manager.motion_token();
+        let walker = {
+            let manager = manager.clone();
+            thread::spawn(move || {
+                let started = Instant::now();
+                manager
+                    .walk_outputs(state(90), MAX_OUTPUT_WALK_ON, token, |_, _| {})
+                    .unwrap();
+                started.elapsed()
+            })
+        };
+        // 첫 출력을 켠 패킷이 나간 뒤 대기 중에 취소
+        let first = received(&rx, 15);
+        manager.cancel_motion();
+
+        assert!(walker.join().unwrap() < Duration::from_secs(1));
+        assert_eq!(first[10..13], [1, 0, 0]);
+        // 그다음은 종료 시 모두 끈 패킷 하나뿐
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        let rest = received_before(&rx, &marker);
+        assert_eq!(rest.len(), 15);
+        assert_eq!(rest[10..13], [0, 0, 0]);
+    }
+
+    #[test]
+    fn move_to_pose_interpolates_from_last_command() {
+        let (manager, rx) = connected_manager(Vec::new());
+        manager.save_pose("lift", state(96)).unwrap();
+        assert!(manager.save_pose(" ", state(96)).is_err());
+        assert_eq!(manager.pose_names(), vec!["lift"]);
+        assert!(manager.move_to_pose("missing", 0).is_err());
+        manager.send_command(&state(90)).unwrap();
+
+        assert!(manager
+            .move_to_pose("lift", manager.motion_token())
+            .unwrap());
+        let joints: Vec<u8> = received(&rx, 15 * 4)
+            .chunks(15)
+            .map(|frame| frame[1])
+            .collect();
+        assert_eq!(joints, vec![90, 92, 94, 96]);
+        assert_eq!(manager.last_command().unwrap().joint_6, 96);
+    }
+
+    #[test]
+    fn pinned_joint_stays_constant_while_others_interpolate() {
+        let (manager, rx) = connected_manager(Vec::new());
+        assert!(manager.set_pinned_joints(0b100_0000).is_err());
+        manager.set_pinned_joints(0b10_0000).unwrap();
+        manager.save_pose("lift", state(96)).unwrap();
+        manager.send_command(&state(90)).unwrap();
+
+        assert!(manager
+            .move_to_pose("lift", manager.motion_token())
+            .unwrap());
+        let frames = received(&rx, 15 * 4);
+        let frames: Vec<&[u8]> = frames.chunks(15).collect();
+        assert_eq!(
+            frames.iter().map(|frame| frame[1]).collect::<Vec<_>>(),
+            vec![90, 92, 94, 96]
+        );
+        // 관절 6은 모든 프레임에서 시작 값 유지
+        assert!(frames.iter().all(|frame| frame[6] == 90));
+        assert_eq!(manager.last_command().unwrap().joint_6, 90);
+
+        // 고정된 관절은 조그하지 않음
+        let jog_token = manager.start_jog_token(6).unwrap();
+        assert!(manager
+            .jog(
+                6,
+                JogDirection::Positive,
+                100.0,
+                jog_token,
+                manager.motion_token()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn phased_joint_starts_moving_later() {
+        let (manager, rx) = connected_manager(Vec::new());
+        let mut phases = [JointPhase::default(); 6];
+        phases[5] = JointPhase {
+            start: 0.5,
+            end: 1.0,
+        };
+        assert!(manager
+            .set_joint_phases({
+                let mut invalid = phases;
+                invalid[0] = JointPhase {
+                    start: 0.6,
+                    end: 0.6,
+                };
+                invalid
+            })
+            .is_err());
+        assert!(manager
+            .set_joint_phases({
+                let mut invalid = phases;
+                invalid[0] = JointPhase {
+                    start: -0.1,
+                    end: 1.0,
+                };
+                invalid
+            })
+            .is_err());
+        manager.set_joint_phases(phases).unwrap();
+        manager.save_pose("lift", state(96)).unwrap();
+        manager.send_command(&state(90)).unwrap();
+
+        assert!(manager
+            .move_to_pose("lift", manager.motion_token())
+            .unwrap());
+        let frames = received(&rx, 15 * 7);
+        let frames: Vec<&[u8]> = frames.chunks(15).skip(1).collect();
+        // 관절 6은 구간이 절반이라 같은 거리를 단계당 2씩 따라잡음
+        assert_eq!(
+            frames.iter().map(|frame| frame[1]).collect::<Vec<_>>(),
+            vec![91, 92, 93, 94, 95, 96]
+        );
+        assert_eq!(
+            frames.iter().map(|frame| frame[6]).collect::<Vec<_>>(),
+            vec![90, 90, 90, 92, 94, 96]
+        );
+    }
+
+    #[test]
+    fn independent_sync_lets_short_moves_finish_first() {
+        let from = state(90);
+        let mut to = state(96);
+        to.joint_2 = 92;
+        to.joint_3 = 84;
+        let joints = |path: &[RobotState], index| -> Vec<u8> {
+            path.iter().map(|step| joint(step, index)).collect()
+        };
+
+        // 동기화: 모든 관절이 가장 먼 관절의 단계 수에 맞춰 함께 도착
+        let synced = guarded_path(&from, &to, 2, &PathPlan::default());
+        assert_eq!(joints(&synced, 1), vec![92, 94, 96]);
+        assert_eq!(joints(&synced, 2), vec![91, 91, 92]);
+        assert_eq!(joints(&synced, 3), vec![88, 86, 84]);
+
+        // 독립: 같은 단계 수 안에서 짧은 이동은 최대 속도로 먼저 끝남
+        let plan = PathPlan {
+            sync: InterpSync::Independent,
+            ..PathPlan::default()
+        };
+        let independent = guarded_path(&from, &to, 2, &plan);
+        assert_eq!(joints(&independent, 1), vec![92, 94, 96]);
+        assert_eq!(joints(&independent, 2), vec![92, 92, 92]);
+        assert_eq!(joints(&independent, 3), vec![88, 86, 84]);
+        // 마지막 단계는 두 방식 모두 목표 자세
+        for index in 1..=6 {
+            assert_eq!(joint(independent.last().unwrap(), index), joint(&to, index));
+            assert_eq!(joint(synced.last().unwrap(), index), joint(&to, index));
+        }
+
+        // 설정한 방식은 저장 자세 이동에 적용
+        let (manager, rx) = connected_manager(Vec::new());
+        manager.set_interp_sync(InterpSync::Independent);
+        manager.save_pose("mixed", to).unwrap();
+        manager.send_command(&from).unwrap();
+        assert!(manager
+            .move_to_pose("mixed", manager.motion_token())
+            .unwrap());
+        let frames = received(&rx, 15 * 4);
+        assert_eq!(
+            frames
+                .chunks(15)
+                .skip(1)
+                .map(|frame| frame[2])
+                .collect::<Vec<_>>(),
+            vec![92, 92, 92]
+        );
+    }
+
+    #[test]
+    fn planner_benchmark_reports_plausible_timing() {
+        let (manager, rx) = connected_manager(Vec::new());
+        assert!(manager.benchmark_planner(&state(250), 50.0).is_err());
+        manager.send_command(&state(0)).unwrap();
+        let _ = received(&rx, 15);
+        assert!(manager.benchmark_planner(&state(250), 0.0).is_err());
+
+        // 0에서 250까지 단계당 2씩이면 125단계
+        let benchmark = manager.benchmark_planner(&state(250), 50.0).unwrap();
+        assert_eq!(benchmark.frames, 125);
+        assert!(benchmark.generation_us < 1_000_000);
+        assert!(benchmark.max_rate_hz > 50.0);
+        assert!(benchmark.sustainable);
+        assert!(
+            !manager
+                .benchmark_planner(&state(250), 1e15)
+                .unwrap()
+                .sustainable
+        );
+
+        // 생성만 하고 전송하지 않음
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        assert!(received_before(&rx, &marker).is_empty());
+    }
+
+    #[test]
+    fn move_to_pose_stops_when_cancelled() {
+        let (manager, rx) = connected_manager(Vec::new());
+        let manager = Arc::new(manager);
+        manager.save_pose("far", state(250)).unwrap();
+        manager.send_command(&state(0)).unwrap();
+        let token = manager.motion_token();
+        let mover = {
+            let manager = manager.clone();
+            thread::spawn(move || manager.move_to_pose("far", token))
+        };
+        // 시작 명령 뒤 보간 첫 단계가 나가면 취소
+        received(&rx, 15 * 2);
+        manager.cancel_motion();
+
+        assert!(!mover.join().unwrap().unwrap());
+        assert!(manager.last_command().unwrap().joint_1 < 250);
+    }
+}
@@ -0,0 +1,129 @@
+// src-tauri/pipe.rs
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[cfg(unix)]
+use std::io::{ErrorKind, Write};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::sync::{Mutex, PoisonError};
+#[cfg(unix)]
+use std::thread;
+#[cfg(unix)]
+use std::time::Duration;
+
+// 연결 대기 확인 간격
+#[cfg(unix)]
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+
+// 유닉스 도메인 소켓으로 JSON 줄을 내보내는 스트림
+pub struct PipeStream {
+    path: PathBuf,
+    #[cfg(unix)]
+    clients: Arc<Mutex<Vec<UnixStream>>>,
+    running: Arc<AtomicBool>,
+}
+
+impl PipeStream {
+    // 소켓 생성 및 연결 대기 스레드 시작 함수
+    #[cfg(unix)]
+    pub fn start(path: &str) -> Result<Self, String> {
+        let listener = UnixListener::bind(path)
+            .map_err(|e| format!("소켓을 만들 수 없습니다 ({}): {}", path, e))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("소켓 설정 실패: {}", e))?;
+
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let running = Arc::new(AtomicBool::new(true));
+        {
+            let clients = clients.clone();
+            let running = running.clone();
+            thread::spawn(move || {
+                while running.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            // 읽지 않는 클라이언트 때문에 수신 루프가 멈추지 않도록
+                            if stream.set_nonblocking(true).is_ok() {
+                                clients
+                                    .lock()
+                                    .unwrap_or_else(PoisonError::into_inner)
+                                    .push(stream);
+                            }
+                        }
+                        Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                            thread::sleep(ACCEPT_INTERVAL)
+                        }
+                        Err(e) => {
+                            println!("Pipe stream accept failed: {}", e);
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+
+        Ok(Self {
+            path: PathBuf::from(path),
+            clients,
+            running,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn start(_path: &str) -> Result<Self, String> {
+        Err("이 플랫폼에서는 파이프 스트림을 지원하지 않습니다.".into())
+    }
+
+    // 연결된 모든 클라이언트에 한 줄 전송 (쓰기 실패나 밀린 클라이언트는 연결 해제)
+    #[cfg(unix)]
+    pub fn publish(&self, line: &str) {
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+        clients.retain_mut(|client| {
+            client
+                .write_all(line.as_bytes())
+                .and_then(|_| client.write_all(b"\n"))
+                .is_ok()
+        });
+    }
+
+    #[cfg(not(unix))]
+    pub fn publish(&self, _line: &str) {}
+}
+
+impl Drop for PipeStream {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+
+    #[test]
+    fn published_lines_reach_connected_reader() {
+        let path = std::env::temp_dir().join(format!("robot-arm-pipe-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let pipe = PipeStream::start(path.to_str().unwrap()).unwrap();
+        // 읽는 쪽이 없어도 막히지 않음
+        pipe.publish("{\"joint_1\":1}");
+
+        let reader = UnixStream::connect(&path).unwrap();
+        thread::sleep(ACCEPT_INTERVAL * 3);
+        pipe.publish("{\"joint_1\":2}");
+        pipe.publish("{\"joint_1\":3}");
+
+        let mut lines = BufReader::new(reader).lines();
+        assert_eq!(lines.next().unwrap().unwrap(), "{\"joint_1\":2}");
+        assert_eq!(lines.next().unwrap().unwrap(), "{\"joint_1\":3}");
+
+        drop(pipe);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,193 @@
+// src-tauri/poses.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::calibration::JointCalibration;
+use crate::serial::RobotState;
+
+// 공유용 자세 프리셋 파일 구조체 정의 (calibration: 내보낸 팔의 관절 보정값)
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PosePreset {
+    pub name: String,
+    pub pose: RobotState,
+    pub calibration: [Option<JointCalibration>; 6],
+    pub exported_unix_ms: u64,
+    pub note: Option<String>,
+}
+
+// 같은 팔로 볼 보정값 차이 허용치
+const CALIBRATION_EPSILON: f64 = 1e-6;
+
+impl PosePreset {
+    // 프리셋 파일 쓰기 함수 (JSON)
+    pub fn write(&self, path: &str) -> Result<(), String> {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| format!("프리셋 변환 실패: {}", e))?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("프리셋 파일을 쓸 수 없습니다 ({}): {}", path, e))
+    }
+
+    // 프리셋 파일 읽기 함수
+    pub fn read(path: &str) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("프리셋 파일을 읽을 수 없습니다 ({}): {}", path, e))?;
+        serde_json::from_str(&json).map_err(|e| format!("프리셋 파일 형식이 잘못되었습니다: {}", e))
+    }
+
+    // 내보낸 팔과 현재 팔의 보정값 비교 (양쪽 모두 보정된 관절만, 한쪽이라도 보정이 없으면 판단할 수 없음)
+    pub fn check_calibration(&self, current: &[Option<JointCalibration>; 6]) -> Result<(), String> {
+        for (i, (exported, current)) in self.calibration.iter().zip(current).enumerate() {
+            if let (Some(exported), Some(current)) = (exported, current) {
+                if (exported.slope - current.slope).abs() > CALIBRATION_EPSILON
+                    || (exported.intercept - current.intercept).abs() > CALIBRATION_EPSILON
+                {
+                    return Err(format!(
+                        "관절 {} 보정값이 현재 팔과 달라 가져올 수 없습니다.",
+                        i + 1
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct LibraryInner {
+    poses: BTreeMap<String, RobotState>,
+    // 자세를 저장할 파일 (None이면 메모리에만 보관)
+    path: Option<PathBuf>,
+}
+
+// 저장된 자세 목록 (파일을 지정하면 바뀔 때마다 저장)
+#[derive(Default)]
+pub struct PoseLibrary {
+    inner: Mutex<LibraryInner>,
+}
+
+impl PoseLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn inner(&self) -> MutexGuard<'_, LibraryInner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // 저장 파일 지정 및 이전 자세 불러오기 (파일이 없으면 새로 시작)
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let mut loaded: BTreeMap<String, RobotState> = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("자세 파일 형식이 잘못되었습니다: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => {
+                return Err(format!(
+                    "자세 파일을 읽을 수 없습니다 ({}): {}",
+                    path.display(),
+                    e
+                ))
+            }
+        };
+
+        let mut inner = self.inner();
+        // 불러오기 전에 저장한 자세가 같은 이름의 파일 자세보다 우선
+        loaded.append(&mut inner.poses);
+        inner.poses = loaded;
+        inner.path = Some(path);
+        save(&inner);
+        Ok(())
+    }
+
+    // 자세 저장 (같은 이름이 있으면 덮어씀)
+    pub fn insert(&self, name: &str, pose: RobotState) {
+        let mut inner = self.inner();
+        inner.poses.insert(name.to_string(), pose);
+        save(&inner);
+    }
+
+    pub fn get(&self, name: &str) -> Option<RobotState> {
+        self.inner().poses.get(name).cloned()
+    }
+
+    // 자세 삭제 (없는 이름이면 false)
+    pub fn remove(&self, name: &str) -> bool {
+        let mut inner = self.inner();
+        let removed = inner.poses.remove(name).is_some();
+        if removed {
+            save(&inner);
+        }
+        removed
+    }
+
+    // 전체 자세 교체 (스냅샷 복원용)
+    pub fn replace(&self, poses: BTreeMap<String, RobotState>) {
+        let mut inner = self.inner();
+        inner.poses = poses;
+        save(&inner);
+    }
+
+    // 저장된 자세 전체 (이름순)
+    pub fn all(&self) -> BTreeMap<String, RobotState> {
+        self.inner().poses.clone()
+    }
+}
+
+// 자세 파일 저장 (실패해도 자세는 메모리에 유지)
+fn save(inner: &LibraryInner) {
+    let Some(path) = &inner.path else {
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(&inner.poses).map_err(|e| e.to_string()))
+        .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        println!("Saving poses failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::state;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("robot-arm-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn library_survives_reload() {
+        let path = temp_path("poses-reload");
+        let _ = std::fs::remove_file(&path);
+
+        let library = PoseLibrary::new();
+        library.load(path.clone()).unwrap();
+        library.insert("pickup", state(40));
+        library.insert("home", state(90));
+        assert!(library.remove("home"));
+        assert!(!library.remove("home"));
+
+        let reloaded = PoseLibrary::new();
+        reloaded.insert("drop", state(10));
+        reloaded.load(path.clone()).unwrap();
+        assert_eq!(
+            reloaded.all().keys().collect::<Vec<_>>(),
+            vec!["drop", "pickup"]
+        );
+        assert_eq!(reloaded.get("pickup").unwrap().joint_1, 40);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn load_rejects_malformed_file() {
+        let path = temp_path("poses-malformed");
+        std::fs::write(&path, "not json").unwrap();
+        assert!(PoseLibrary::new().load(path.clone()).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,11 +1,43 @@
 // src-tauri/serial.rs
 
 use serde::{Deserialize, Serialize};
-use serialport;
-use std::io::{ErrorKind, Read, Write};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use tauri::State;
+use serialport::{FlowControl, Parity};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::calibration::{
+    adapt_frames, JointCalibration, JointInfo, JointMapping, JointUnit, MissingJointPolicy,
+    ResponseCurve,
+};
+use crate::codec::{
+    analyze_frames, check_frame_layout, encode_command, encode_custom, encode_delta, escape_frame,
+    frame_len, CaptureAnalysis, CustomCommand, DecodeError, DecoderConfig, ErrorFrameConfig,
+    FrameDecoder, FrameEncoding, FrameTrailingMode, IoLayout, PayloadField, ESCAPE_BYTE,
+    MAX_PAYLOAD_LEN,
+};
+use crate::limits::{
+    check_pose, clamp_pose, limit_ranges, validate_limits, ApproachSlowdown, JointRange,
+    KeepoutRegion, LimitCheck,
+};
+#[cfg(feature = "metrics-server")]
+use crate::metrics_server::MetricsServer;
+use crate::motion::{InterpSync, JointPhase, MOTION_WAIT_SLICE};
+use crate::pipe::PipeStream;
+use crate::poses::{PoseLibrary, PosePreset};
+use crate::sessions::{CloseReason, SessionHistory, SessionRecord};
+use crate::streaming::{MetricsSample, ReadErrorMode, ReadErrorPolicy};
+use crate::tasks::{TaskGuard, TaskInfo, TaskRegistry};
+#[cfg(feature = "tcp")]
+use crate::transport::TcpTransport;
+use crate::transport::{TimeoutGuard, Transport};
 
 // RobotState 구조체 정의
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -23,33 +55,1087 @@ pub struct RobotState {
     pub digital_output_2: bool,
     pub digital_output_3: bool,
     pub robot_speed: u8,
+    // 컨트롤러가 보고한 밀리초 타임스탬프 (설정된 경우에만 존재)
+    pub device_timestamp: Option<u32>,
+    // 컨트롤러가 보고한 전원 전압, 센티볼트 단위 (설정된 경우에만 존재)
+    pub supply_voltage: Option<u16>,
+    // 컨트롤러가 보고한 고장 래치 비트 (설정된 경우에만 존재, 0이면 고장 없음)
+    pub fault_bits: Option<u8>,
+}
+
+// 송수신 타임아웃
+pub(crate) const IO_TIMEOUT: Duration = Duration::from_millis(100);
+// TCP 연결 타임아웃
+#[cfg(feature = "tcp")]
+const TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+// 연결 확인 시 첫 상태 패킷 대기 시간
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(1);
+
+// 기본 수신 버퍼 크기
+const DEFAULT_READ_BUFFER_SIZE: usize = 64;
+// 입력 바인딩 디바운스 시간
+const INPUT_DEBOUNCE: Duration = Duration::from_millis(50);
+
+// 고장 해제 명령으로 등록할 이름과 해제 확인 대기 시간
+const CLEAR_FAULT_COMMAND: &str = "clear_fault";
+const FAULT_CLEAR_TIMEOUT: Duration = Duration::from_secs(1);
+// 장치 식별 명령으로 등록할 이름, 켜짐 시간 필드 이름, 켜짐 시간 상한
+const IDENTIFY_COMMAND: &str = "identify";
+const IDENTIFY_DURATION_FIELD: &str = "duration_ms";
+const MAX_IDENTIFY_DURATION: Duration = Duration::from_secs(60);
+// 펌웨어 명령 후 거부 응답(오류 패킷)을 기다리는 시간
+const COMMAND_REPLY_WINDOW: Duration = Duration::from_millis(300);
+// 연결 시 홈 이동 대기 시간 상한
+const MAX_HOME_DELAY: Duration = Duration::from_secs(30);
+// 연속 프레임 수신 시 프레임 수와 대기 시간 상한
+const MAX_BATCH_FRAMES: usize = 10_000;
+const MAX_BATCH_TIMEOUT: Duration = Duration::from_secs(30);
+// 구분자까지 원시 바이트 읽기 대기 시간 상한
+const MAX_READ_UNTIL_TIMEOUT: Duration = Duration::from_secs(10);
+
+// 수신 버퍼 크기 상한
+const MAX_READ_BUFFER_SIZE: usize = 4096;
+// 버퍼 넘침 추정 기준: 구간 안의 잘못된 패킷 수와 느린 읽기로 볼 읽기 간격
+// (serialport는 OS 수신 버퍼 넘침을 알려주지 않으므로 두 조건이 겹치면 넘침으로 추정)
+const OVERRUN_WINDOW: Duration = Duration::from_secs(1);
+const OVERRUN_FRAMING_ERRORS: usize = 3;
+const OVERRUN_READ_GAP: Duration = Duration::from_millis(100);
+
+// 뮤텍스 잠금 함수 (다른 스레드의 패닉으로 오염된 잠금도 복구)
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// 포트 설정을 읽을 수 없을 때의 바이트당 비트 수 (8N1)
+const DEFAULT_BITS_PER_BYTE: u32 = 10;
+
+// 링크가 유지할 수 있는 초당 최대 패킷 수
+pub(crate) fn max_frame_rate(baud_rate: u32, bits_per_byte: u32, frame_len: usize) -> f64 {
+    baud_rate as f64 / (bits_per_byte as f64 * frame_len as f64)
+}
+
+// 장치 분리나 원격 종료로 연결이 끊긴 읽기 오류인지 확인
+fn is_disconnect(kind: ErrorKind) -> bool {
+    matches!(
+        kind,
+        ErrorKind::ConnectionAborted
+            | ErrorKind::ConnectionReset
+            | ErrorKind::BrokenPipe
+            | ErrorKind::NotConnected
+            | ErrorKind::UnexpectedEof
+    )
+}
+
+// 디지털 출력 설정 함수 (index: 1~3)
+pub(crate) fn set_digital_output(robot_state: &mut RobotState, index: u8, on: bool) {
+    match index {
+        1 => robot_state.digital_output_1 = on,
+        2 => robot_state.digital_output_2 = on,
+        3 => robot_state.digital_output_3 = on,
+        _ => {}
+    }
+}
+
+// 디지털 출력 조회 함수 (index: 1~3)
+fn digital_output(robot_state: &RobotState, index: u8) -> bool {
+    match index {
+        1 => robot_state.digital_output_1,
+        2 => robot_state.digital_output_2,
+        3 => robot_state.digital_output_3,
+        _ => false,
+    }
+}
+
+// 관절 값 조회 함수 (index: 1~6)
+pub fn joint(robot_state: &RobotState, index: u8) -> u8 {
+    match index {
+        1 => robot_state.joint_1,
+        2 => robot_state.joint_2,
+        3 => robot_state.joint_3,
+        4 => robot_state.joint_4,
+        5 => robot_state.joint_5,
+        6 => robot_state.joint_6,
+        _ => 0,
+    }
+}
+
+// 관절 값 설정 함수 (index: 1~6)
+pub fn set_joint(robot_state: &mut RobotState, index: u8, value: u8) {
+    match index {
+        1 => robot_state.joint_1 = value,
+        2 => robot_state.joint_2 = value,
+        3 => robot_state.joint_3 = value,
+        4 => robot_state.joint_4 = value,
+        5 => robot_state.joint_5 = value,
+        6 => robot_state.joint_6 = value,
+        _ => {}
+    }
+}
+
+// 관절 값 증감 함수 (0~255를 넘으면 감싸지 않고 끝값에서 멈춤)
+pub fn offset_joint(value: u8, delta: i32) -> u8 {
+    (value as i32 + delta).clamp(u8::MIN as i32, u8::MAX as i32) as u8
+}
+
+// 두 관절 값의 부호 있는 차이 (to - from)
+pub fn joint_change(from: u8, to: u8) -> i32 {
+    to as i32 - from as i32
+}
+
+// 실수로 계산한 관절 값을 반올림해 0~255 범위로 제한하는 함수
+pub fn round_joint(value: f64) -> u8 {
+    value.round().clamp(u8::MIN as f64, u8::MAX as f64) as u8
+}
+
+// 초기 자세 설정 구조체 정의
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InitPose {
+    pub pose: RobotState,
+    pub enabled: bool,
+    pub delay_ms: u64,
+    // 상태 패킷을 한 번 정상 수신한 뒤에만 전송
+    pub require_state: bool,
+}
+
+// 연결 직후 초기 자세 전송 결과
+pub enum InitPoseOutcome {
+    // 초기 자세가 없거나 꺼져 있음
+    Disabled,
+    Sent,
+    // 첫 상태 읽기에 실패해 전송하지 않음 (사유 포함)
+    Skipped(String),
+}
+
+// 송수신 카운터 값 구조체 정의
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MetricsSnapshot {
+    pub bytes_read: u64,
+    pub frames_received: u64,
+    pub frames_sent: u64,
+    pub malformed_frames: u64,
+    pub controller_errors: u64,
+    pub buffer_overruns: u64,
+}
+
+// 버퍼 넘침 추정 정보
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct BufferOverrun {
+    // 추정 구간 안의 잘못된 패킷 수
+    pub framing_errors: u32,
+    // 구간 안에서 가장 길었던 읽기 간격
+    pub read_gap_ms: u64,
+}
+
+// 런타임 상태 전체 스냅샷 구조체 정의 (metrics는 참고용, 복원하지 않음)
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RuntimeSnapshot {
+    pub last_command: Option<RobotState>,
+    pub init_pose: Option<InitPose>,
+    pub timestamp_offset: Option<usize>,
+    pub voltage_offset: Option<usize>,
+    pub fault_offset: Option<usize>,
+    pub checksum_offset: Option<usize>,
+    pub voltage_threshold: Option<u16>,
+    pub io_layout: IoLayout,
+    pub encoding: FrameEncoding,
+    pub trailing_mode: FrameTrailingMode,
+    pub read_buffer_size: usize,
+    pub error_frames: ErrorFrameConfig,
+    pub delta_opcode: Option<u8>,
+    pub speed_scale: f32,
+    pub limit_approach: ApproachSlowdown,
+    pub actuation_latency_ms: u64,
+    pub pinned_joints: u8,
+    pub joint_phases: [JointPhase; 6],
+    pub interp_sync: InterpSync,
+    pub joint_limits: Vec<JointRange>,
+    pub calibration: [Option<JointCalibration>; 6],
+    pub response_curves: [ResponseCurve; 6],
+    pub joint_units: [JointUnit; 6],
+    pub keepout_regions: Vec<KeepoutRegion>,
+    pub poses: BTreeMap<String, RobotState>,
+    pub custom_commands: BTreeMap<String, CustomCommand>,
+    pub io_mirror: u8,
+    pub input_bindings: HashMap<u8, InputAction>,
+    pub stuck_threshold: u32,
+    pub telemetry_decimation: u32,
+    pub read_error_policy: ReadErrorPolicy,
+    pub frontend_loss: FrontendLossBehavior,
+    pub required_protocol: Option<u8>,
+    pub metrics: MetricsSnapshot,
+}
+
+// 프론트엔드 창이 닫혔을 때 동작
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum FrontendLossBehavior {
+    // 백그라운드 작업을 계속 실행 (무인 스트리밍 등)
+    Continue,
+    // 모든 백그라운드 작업 취소
+    Cancel,
+    // 모든 백그라운드 작업 취소 후 초기 자세로 이동
+    Park,
+}
+
+// 입력 상승 에지에 연결할 동작
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum InputAction {
+    // 진행 중인 백그라운드 동작 취소
+    CancelMotion,
+    // 디지털 출력 반전 (그리퍼 등, 1~3)
+    ToggleOutput(u8),
+    // 저장된 자세로 이동 (홈 자세 등)
+    GotoPose(String),
+}
+
+// 입력 에지 감지 상태
+#[derive(Default)]
+struct InputEdges {
+    // 직전 입력 값 (첫 패킷 전에는 None)
+    last: Option<[bool; 3]>,
+    // 입력별 마지막 동작 실행 시각
+    fired_at: [Option<Instant>; 3],
+}
+
+// 연결 정보 구조체 정의
+#[derive(Serialize, Clone)]
+pub struct ConnectionInfo {
+    // 시리얼 포트 이름 또는 TCP 주소
+    pub port_name: String,
+    // TCP 연결은 None
+    pub baud_rate: Option<u32>,
+}
+
+// 송수신 카운터 구조체 정의
+#[derive(Default)]
+pub struct SerialMetrics {
+    pub bytes_read: AtomicU64,
+    pub frames_received: AtomicU64,
+    pub frames_sent: AtomicU64,
+    pub malformed_frames: AtomicU64,
+    pub controller_errors: AtomicU64,
+    pub buffer_overruns: AtomicU64,
+    // 마지막 정상 수신 소요 시간 (마이크로초)
+    pub last_read_latency_us: AtomicU64,
+}
+
+// 시리얼 포트 열기 오류
+#[derive(Debug)]
+pub enum SerialError {
+    // 다른 프로그램이 포트를 사용 중이거나 접근 권한이 없음
+    PortBusy(String),
+    Open(serialport::Error),
+}
+
+impl From<serialport::Error> for SerialError {
+    fn from(e: serialport::Error) -> Self {
+        // 플랫폼과 serialport 버전마다 사용 중 오류의 종류가 달라 설명도 함께 확인
+        let description = e.description.to_lowercase();
+        let busy = matches!(
+            e.kind,
+            serialport::ErrorKind::Io(ErrorKind::PermissionDenied | ErrorKind::ResourceBusy)
+        ) || ["busy", "denied", "in use"]
+            .iter()
+            .any(|keyword| description.contains(keyword));
+        if busy {
+            SerialError::PortBusy(e.description)
+        } else {
+            SerialError::Open(e)
+        }
+    }
+}
+
+impl std::fmt::Display for SerialError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SerialError::PortBusy(description) => write!(
+                f,
+                "포트를 사용 중이거나 접근 권한이 없습니다. 다른 프로그램(시리얼 모니터 등)에서 열려 있는지 확인해주세요. ({})",
+                description
+            ),
+            SerialError::Open(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+// 마지막 수신 원시 패킷 구조체 정의 (디코딩 결과와 함께 보관)
+#[derive(Serialize, Clone)]
+pub struct LastRawFrame {
+    pub bytes: Vec<u8>,
+    pub hex: String,
+    pub received_unix_ms: u64,
+    // 직전 패킷과의 컨트롤러 시계 기준 간격 (타임스탬프가 설정된 경우에만 존재)
+    pub device_interval_ms: Option<u32>,
+    pub state: RobotState,
+}
+
+// 관절 하나의 raw 값과 보정 각도 (보정되지 않은 관절은 degrees 없음)
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct JointReading {
+    pub joint: u8,
+    pub raw: u8,
+    pub degrees: Option<f64>,
+}
+
+// raw 값과 보정 각도를 함께 담은 로봇 상태 구조체 정의
+#[derive(Serialize, Clone, Debug)]
+pub struct FullRobotState {
+    pub joints: Vec<JointReading>,
+    pub digital_inputs: [bool; 3],
+    pub digital_outputs: [bool; 3],
+    pub robot_speed: u8,
+    pub received_unix_ms: u64,
+    pub device_timestamp: Option<u32>,
+}
+
+// 모뎀 상태 신호선 구조체 정의
+#[derive(Serialize)]
+pub struct SignalLines {
+    pub clear_to_send: bool,
+    pub data_set_ready: bool,
+    pub ring_indicator: bool,
+    pub carrier_detect: bool,
 }
 
 // SerialPortManager 구조체 정의
 pub struct SerialPortManager {
-    port: Arc<Mutex<Option<Box<dyn serialport::SerialPort + Send>>>>,
+    pub(crate) port: Arc<Mutex<Option<Box<dyn Transport>>>>,
+    // 패킷 내 타임스탬프 시작 위치 (None이면 타임스탬프 없음)
+    pub(crate) timestamp_offset: Mutex<Option<usize>>,
+    // 수신 패킷 내 XOR 체크섬 위치 (None이면 체크섬 없음)
+    checksum_offset: Mutex<Option<usize>>,
+    // 수신 패킷 내 전원 전압 위치 (None이면 전압 없음)
+    voltage_offset: Mutex<Option<usize>>,
+    // 고장 래치 비트 위치 (None이면 고장 상태 없음)
+    fault_offset: Mutex<Option<usize>>,
+    // 저전압 경고 기준 (센티볼트), 현재 저전압 상태, 아직 알리지 않은 저전압 값
+    voltage_threshold: Mutex<Option<u16>>,
+    voltage_low: AtomicBool,
+    low_voltage_warning: Mutex<Option<u16>>,
+    // 마지막으로 전송한 로봇 명령
+    last_command: Mutex<Option<RobotState>>,
+    // 변경 필드 명령 패킷 헤드 (None이면 항상 전체 패킷 전송)
+    delta_opcode: Mutex<Option<u8>>,
+    // 마지막으로 전송한 전체 패킷 (이스케이프 전, 변경 필드 비교 기준)
+    // 잠금 순서: last_sent_frame → port (send_command가 이 순서로 잡음)
+    last_sent_frame: Mutex<Option<Vec<u8>>>,
+    // 포트 초기화 직후 전송할 초기 자세
+    init_pose: Mutex<Option<InitPose>>,
+    // 동작 취소 세대 번호 (cancel_motion 호출마다 증가)
+    pub(crate) motion_generation: AtomicU64,
+    // 감시 이동 진행 여부와 되돌리기 요청
+    pub(crate) guarded_move_active: AtomicBool,
+    pub(crate) reverse_requested: AtomicBool,
+    // 관절별 조그 세대 번호 (조그 시작/중지마다 증가)
+    pub(crate) jog_generations: Mutex<[u64; 6]>,
+    // 데드맨 스위치 신호 유효 시간 (None이면 사용 안 함), 마지막 신호 시각
+    pub(crate) deadman_timeout: Mutex<Option<Duration>>,
+    pub(crate) deadman_heartbeat: Mutex<Option<Instant>>,
+    // 데드맨 감시 세대 번호와 신호 끊김으로 동작을 멈췄는지 여부
+    pub(crate) deadman_generation: AtomicU64,
+    pub(crate) deadman_tripped: AtomicBool,
+    // 현재 연결 정보
+    connection: Mutex<Option<ConnectionInfo>>,
+    // 연결 시작/종료 기록
+    sessions: SessionHistory,
+    // 마지막으로 상태를 수신한 시각
+    last_state_at: Mutex<Option<SystemTime>>,
+    // 마지막 송수신 오류 메시지
+    pub(crate) last_error: Mutex<Option<String>>,
+    // 송수신 카운터
+    pub(crate) metrics: SerialMetrics,
+    // 전체 속도 배율 (0.0~1.0)
+    speed_scale: Mutex<f32>,
+    // 관절 한계 근처 감속 설정
+    limit_approach: Mutex<ApproachSlowdown>,
+    // 명령 전송부터 팔이 움직이기 시작할 때까지의 지연
+    pub(crate) actuation_latency: Mutex<Duration>,
+    // 보간 이동 중 현재 값에 고정할 관절 비트마스크 (비트 0: 관절 1)
+    pub(crate) pinned_joints: AtomicU8,
+    // 보간 이동 중 관절별 이동 구간
+    pub(crate) joint_phases: Mutex<[JointPhase; 6]>,
+    // 보간 이동 관절 동기화 방식
+    pub(crate) interp_sync: Mutex<InterpSync>,
+    // 컨트롤러 오류 패킷 설정
+    error_frames: Mutex<ErrorFrameConfig>,
+    // 한 번에 읽을 최대 바이트 수
+    read_buffer_size: AtomicUsize,
+    // 읽기 경계를 넘어 남은 수신 바이트
+    pending: Mutex<Vec<u8>>,
+    // 패킷 사이 여분 바이트 처리 방식
+    trailing_mode: Mutex<FrameTrailingMode>,
+    // 패킷 인코딩 방식
+    encoding: Mutex<FrameEncoding>,
+    // 디지털 입출력 배치 방식
+    io_layout: Mutex<IoLayout>,
+    // 이름이 붙은 저장 자세 목록
+    poses: PoseLibrary,
+    // 이름으로 등록한 사용자 정의 명령 목록
+    custom_commands: Mutex<BTreeMap<String, CustomCommand>>,
+    // 마지막으로 디코딩한 원시 패킷
+    last_raw_frame: Mutex<Option<LastRawFrame>>,
+    // 수신 상태를 내보낼 로컬 소켓 스트림
+    pub(crate) pipe_stream: Mutex<Option<PipeStream>>,
+    // 수신 상태를 한 줄씩 기록할 ndjson 로그 파일
+    pub(crate) ndjson_log: Mutex<Option<BufWriter<File>>>,
+    // 텔레메트리 출력 간격 (N개 상태마다 하나만 소켓/로그로 내보냄)과 지금까지 받은 상태 수
+    pub(crate) telemetry_decimation: AtomicU32,
+    pub(crate) telemetry_counter: AtomicU64,
+    // 상태 스트림 세대 번호 (스트림 시작/중지마다 증가)
+    pub(crate) stream_generation: AtomicU64,
+    // 상태 스트림 읽기 오류 정책
+    pub(crate) read_error_policy: Mutex<ReadErrorPolicy>,
+    // 프론트엔드 창이 닫혔을 때 동작
+    frontend_loss: Mutex<FrontendLossBehavior>,
+    // 명령 전송 전 장치 식별을 요구할 때 기대 프로토콜 버전 (None이면 요구하지 않음)
+    required_protocol: Mutex<Option<u8>>,
+    // 이번 연결에서 식별 응답으로 보고된 프로토콜 버전
+    reported_protocol: Mutex<Option<u8>>,
+    // 정지 상태 경고 기준 반복 횟수 (0이면 감지 안 함)
+    stuck_threshold: AtomicU32,
+    // 직전 패킷과 동일한 패킷이 연속으로 들어온 횟수
+    repeated_frames: AtomicU32,
+    // 아직 보고하지 않은 정지 상태 경고
+    stuck_warning: AtomicBool,
+    // 직전 읽기가 끝난 시각, 구간 안에서 가장 긴 읽기 간격과 그 시각
+    read_finished_at: Mutex<Option<Instant>>,
+    slow_read: Mutex<Option<(Instant, Duration)>>,
+    // 구간 안에서 잘못된 패킷을 받은 시각들
+    framing_errors_at: Mutex<VecDeque<Instant>>,
+    // 아직 보고하지 않은 버퍼 넘침 추정
+    overrun_warning: Mutex<Option<BufferOverrun>>,
+    // 관절별 (최솟값, 최댓값) 제한
+    pub(crate) joint_limits: Mutex<[(u8, u8); 6]>,
+    // 디지털 출력별 최소 유지 시간
+    output_dwell: Mutex<[Duration; 3]>,
+    // 디지털 출력별 마지막 전환 시각
+    output_toggled_at: Mutex<[Option<Instant>; 3]>,
+    // 관절별 각도 보정값 (보정 전에는 None)
+    calibration: Mutex<[Option<JointCalibration>; 6]>,
+    // 관절별 슬라이더 응답 곡선
+    response_curves: Mutex<[ResponseCurve; 6]>,
+    // 관절별 표시 단위
+    joint_units: Mutex<[JointUnit; 6]>,
+    // 관절 공간 금지 영역 목록
+    keepout_regions: Mutex<Vec<KeepoutRegion>>,
+    // 입력 번호별 연결된 동작
+    input_bindings: Mutex<HashMap<u8, InputAction>>,
+    // 입력 에지 감지 상태
+    input_edges: Mutex<InputEdges>,
+    // 입력 동작 처리 스레드로 보내는 큐 (처리 스레드 시작 전에는 None)
+    input_actions: Mutex<Option<Sender<(u8, InputAction)>>>,
+    // 입력을 출력으로 따라 보낼 채널 비트마스크 (비트 0: 채널 1)
+    io_mirror: AtomicU8,
+    // 지표 샘플 기록 (오래된 샘플부터 제거)
+    pub(crate) metrics_history: Mutex<VecDeque<MetricsSample>>,
+    // 지표 샘플 최대 보관 개수
+    pub(crate) metrics_history_len: AtomicUsize,
+    // 지표 샘플링 세대 번호 (샘플링 설정마다 증가)
+    pub(crate) sampling_generation: AtomicU64,
 }
 
 impl SerialPortManager {
     pub fn new() -> Self {
         Self {
             port: Arc::new(Mutex::new(None)),
+            timestamp_offset: Mutex::new(None),
+            checksum_offset: Mutex::new(None),
+            voltage_offset: Mutex::new(None),
+            fault_offset: Mutex::new(None),
+            voltage_threshold: Mutex::new(None),
+            voltage_low: AtomicBool::new(false),
+            low_voltage_warning: Mutex::new(None),
+            last_command: Mutex::new(None),
+            delta_opcode: Mutex::new(None),
+            last_sent_frame: Mutex::new(None),
+            init_pose: Mutex::new(None),
+            motion_generation: AtomicU64::new(0),
+            guarded_move_active: AtomicBool::new(false),
+            reverse_requested: AtomicBool::new(false),
+            jog_generations: Mutex::new([0; 6]),
+            deadman_timeout: Mutex::new(None),
+            deadman_heartbeat: Mutex::new(None),
+            deadman_generation: AtomicU64::new(0),
+            deadman_tripped: AtomicBool::new(false),
+            connection: Mutex::new(None),
+            sessions: SessionHistory::new(),
+            last_state_at: Mutex::new(None),
+            last_error: Mutex::new(None),
+            metrics: SerialMetrics::default(),
+            speed_scale: Mutex::new(1.0),
+            limit_approach: Mutex::new(ApproachSlowdown::default()),
+            actuation_latency: Mutex::new(Duration::ZERO),
+            pinned_joints: AtomicU8::new(0),
+            joint_phases: Mutex::new([JointPhase::default(); 6]),
+            interp_sync: Mutex::new(InterpSync::default()),
+            error_frames: Mutex::new(ErrorFrameConfig::default()),
+            read_buffer_size: AtomicUsize::new(DEFAULT_READ_BUFFER_SIZE),
+            pending: Mutex::new(Vec::new()),
+            trailing_mode: Mutex::new(FrameTrailingMode::ConsumeToNextHead),
+            encoding: Mutex::new(FrameEncoding::Plain),
+            io_layout: Mutex::new(IoLayout::Expanded),
+            poses: PoseLibrary::new(),
+            custom_commands: Mutex::new(BTreeMap::new()),
+            last_raw_frame: Mutex::new(None),
+            pipe_stream: Mutex::new(None),
+            ndjson_log: Mutex::new(None),
+            telemetry_decimation: AtomicU32::new(1),
+            telemetry_counter: AtomicU64::new(0),
+            stream_generation: AtomicU64::new(0),
+            read_error_policy: Mutex::new(ReadErrorPolicy {
+                mode: ReadErrorMode::Emit,
+                max_consecutive: 0,
+            }),
+            frontend_loss: Mutex::new(FrontendLossBehavior::Cancel),
+            required_protocol: Mutex::new(None),
+            reported_protocol: Mutex::new(None),
+            stuck_threshold: AtomicU32::new(0),
+            repeated_frames: AtomicU32::new(0),
+            stuck_warning: AtomicBool::new(false),
+            read_finished_at: Mutex::new(None),
+            slow_read: Mutex::new(None),
+            framing_errors_at: Mutex::new(VecDeque::new()),
+            overrun_warning: Mutex::new(None),
+            joint_limits: Mutex::new([(u8::MIN, u8::MAX); 6]),
+            output_dwell: Mutex::new([Duration::ZERO; 3]),
+            output_toggled_at: Mutex::new([None; 3]),
+            calibration: Mutex::new([None; 6]),
+            response_curves: Mutex::new(std::array::from_fn(|_| ResponseCurve::Linear)),
+            joint_units: Mutex::new(std::array::from_fn(|_| JointUnit::default())),
+            keepout_regions: Mutex::new(Vec::new()),
+            input_bindings: Mutex::new(HashMap::new()),
+            input_actions: Mutex::new(None),
+            input_edges: Mutex::new(InputEdges::default()),
+            io_mirror: AtomicU8::new(0),
+            metrics_history: Mutex::new(VecDeque::new()),
+            metrics_history_len: AtomicUsize::new(0),
+            sampling_generation: AtomicU64::new(0),
+        }
+    }
+
+    // 타임스탬프 위치 설정 함수
+    pub fn set_timestamp_offset(&self, offset: Option<usize>) -> Result<(), String> {
+        check_frame_layout(&DecoderConfig {
+            timestamp_offset: offset,
+            ..self.decoder_config()
+        })?;
+        *lock(&self.timestamp_offset) = offset;
+        lock(&self.pending).clear();
+        Ok(())
+    }
+
+    // 수신 체크섬 위치 설정 함수 (None이면 체크섬 없음)
+    pub fn set_checksum_offset(&self, offset: Option<usize>) -> Result<(), String> {
+        check_frame_layout(&DecoderConfig {
+            checksum_offset: offset,
+            ..self.decoder_config()
+        })?;
+        *lock(&self.checksum_offset) = offset;
+        lock(&self.pending).clear();
+        Ok(())
+    }
+
+    // 전원 전압 위치 설정 함수 (None이면 전압 없음)
+    pub fn set_voltage_offset(&self, offset: Option<usize>) -> Result<(), String> {
+        check_frame_layout(&DecoderConfig {
+            voltage_offset: offset,
+            ..self.decoder_config()
+        })?;
+        *lock(&self.voltage_offset) = offset;
+        lock(&self.pending).clear();
+        Ok(())
+    }
+
+    // 고장 래치 비트 위치 설정 함수 (None이면 고장 상태 없음)
+    pub fn set_fault_offset(&self, offset: Option<usize>) -> Result<(), String> {
+        check_frame_layout(&DecoderConfig {
+            fault_offset: offset,
+            ..self.decoder_config()
+        })?;
+        *lock(&self.fault_offset) = offset;
+        lock(&self.pending).clear();
+        Ok(())
+    }
+
+    // 저전압 경고 기준 설정 함수 (None이면 감시 안 함)
+    pub fn set_voltage_threshold(&self, centivolts: Option<u16>) {
+        *lock(&self.voltage_threshold) = centivolts;
+        self.voltage_low.store(false, Ordering::SeqCst);
+    }
+
+    // 디지털 입출력 배치 방식 설정 함수
+    pub fn set_io_layout(&self, io_layout: IoLayout) -> Result<(), String> {
+        check_frame_layout(&DecoderConfig {
+            io_layout,
+            ..self.decoder_config()
+        })?;
+        *lock(&self.io_layout) = io_layout;
+        lock(&self.pending).clear();
+        Ok(())
+    }
+
+    // 초기 자세 설정 함수 (전송 전 대기 시간은 홈 이동 대기 상한 이하)
+    pub fn set_init_pose(&self, init_pose: InitPose) -> Result<(), String> {
+        if init_pose.delay_ms > MAX_HOME_DELAY.as_millis() as u64 {
+            return Err(format!(
+                "초기 자세 대기 시간은 {}ms 이하여야 합니다.",
+                MAX_HOME_DELAY.as_millis()
+            ));
+        }
+        *lock(&self.init_pose) = Some(init_pose);
+        Ok(())
+    }
+
+    // 활성화된 초기 자세 조회 함수
+    pub fn init_pose(&self) -> Option<InitPose> {
+        lock(&self.init_pose)
+            .clone()
+            .filter(|init_pose| init_pose.enabled)
+    }
+
+    // 연결 시 홈 이동 설정 함수 (초기 자세를 홈 자세로 사용, 첫 상태 읽기 성공 시에만 전송)
+    pub fn set_home_on_connect(&self, enabled: bool, delay: Duration) -> Result<(), String> {
+        if delay > MAX_HOME_DELAY {
+            return Err(format!(
+                "홈 이동 대기 시간은 {}ms 이하여야 합니다.",
+                MAX_HOME_DELAY.as_millis()
+            ));
+        }
+        let mut init_pose = lock(&self.init_pose);
+        match init_pose.as_mut() {
+            Some(init_pose) => {
+                init_pose.enabled = enabled;
+                init_pose.delay_ms = delay.as_millis() as u64;
+                init_pose.require_state = true;
+                Ok(())
+            }
+            None if !enabled => Ok(()),
+            None => Err("연결 시 홈 이동 전에 set_init_pose로 홈 자세를 먼저 설정해주세요.".into()),
+        }
+    }
+
+    // 초기 자세 전송 함수 (연결 후 대기 시간이 지나면 전송, 필요하면 첫 상태 읽기로 응답 확인)
+    // 대기 중 동작이 취소되면 전송하지 않고 건너뜀
+    pub fn send_init_pose(&self, token: u64) -> Result<InitPoseOutcome, String> {
+        let Some(init_pose) = self.init_pose() else {
+            return Ok(InitPoseOutcome::Disabled);
+        };
+        if !self.sleep_unless_cancelled(Duration::from_millis(init_pose.delay_ms), token) {
+            return Ok(InitPoseOutcome::Skipped(
+                "대기 중 취소되어 초기 자세 전송을 건너뛰었습니다.".into(),
+            ));
+        }
+        if init_pose.require_state {
+            // 응답하지 않는 장치에는 자세를 보내지 않고 사유를 호출자에게 전달
+            if let Err(e) = self.read_data() {
+                let message = format!("상태 확인 실패로 초기 자세 전송을 건너뛰었습니다: {}", e);
+                *lock(&self.last_error) = Some(message.clone());
+                return Ok(InitPoseOutcome::Skipped(message));
+            }
+        }
+        self.send_command(&init_pose.pose)
+            .map_err(|e| format!("초기 자세 전송 실패: {}", e))?;
+        Ok(InitPoseOutcome::Sent)
+    }
+
+    // 컨트롤러 오류 패킷 설정 함수
+    pub fn set_error_frames(
+        &self,
+        opcode: Option<u8>,
+        messages: HashMap<u16, String>,
+    ) -> Result<(), String> {
+        if matches!(opcode, Some(253) | Some(254)) {
+            return Err("오류 패킷 헤드는 상태 패킷의 헤드/테일(253, 254)과 달라야 합니다.".into());
+        }
+        *lock(&self.error_frames) = ErrorFrameConfig { opcode, messages };
+        Ok(())
+    }
+
+    // 변경 필드 명령 설정 함수 (컨트롤러가 지원할 때만 opcode 지정)
+    pub fn set_delta_commands(&self, opcode: Option<u8>) -> Result<(), String> {
+        if matches!(opcode, Some(253) | Some(254) | Some(ESCAPE_BYTE)) {
+            return Err(
+                "변경 필드 명령 헤드는 헤드/테일/이스케이프 바이트(253, 254, 251)와 달라야 합니다."
+                    .into(),
+            );
+        }
+        *lock(&self.delta_opcode) = opcode;
+        *lock(&self.last_sent_frame) = None;
+        Ok(())
+    }
+
+    // 수신 버퍼 크기 설정 함수
+    pub fn set_read_buffer_size(&self, bytes: usize) -> Result<(), String> {
+        if !(1..=MAX_READ_BUFFER_SIZE).contains(&bytes) {
+            return Err(format!(
+                "수신 버퍼 크기는 1에서 {} 사이여야 합니다.",
+                MAX_READ_BUFFER_SIZE
+            ));
+        }
+        self.read_buffer_size.store(bytes, Ordering::Relaxed);
+        Ok(())
+    }
+
+    // 전체 속도 배율 설정 함수
+    pub fn set_speed_scale(&self, factor: f32) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&factor) {
+            return Err("속도 배율은 0.0에서 1.0 사이여야 합니다.".into());
+        }
+        *lock(&self.speed_scale) = factor;
+        Ok(())
+    }
+
+    // 관절 한계 접근 감속 설정 함수 (margin이 0이면 끔)
+    pub fn set_limit_approach(&self, slowdown: ApproachSlowdown) -> Result<(), String> {
+        if slowdown.margin > 0 && slowdown.min_speed == 0 {
+            return Err("한계 접근 최저 속도는 0보다 커야 합니다.".into());
+        }
+        *lock(&self.limit_approach) = slowdown;
+        Ok(())
+    }
+
+    // 패킷 사이 여분 바이트 처리 방식 설정 함수
+    pub fn set_trailing_mode(&self, mode: FrameTrailingMode) {
+        *lock(&self.trailing_mode) = mode;
+    }
+
+    // 패킷 인코딩 방식 설정 함수
+    pub fn set_encoding(&self, encoding: FrameEncoding) {
+        *lock(&self.encoding) = encoding;
+        lock(&self.pending).clear();
+    }
+
+    // 자세 저장 함수 (같은 이름이 있으면 덮어씀)
+    pub fn save_pose(&self, name: &str, pose: RobotState) -> Result<(), String> {
+        if name.trim().is_empty() {
+            return Err("자세 이름이 비어 있습니다.".into());
+        }
+        self.poses.insert(name, pose);
+        Ok(())
+    }
+
+    // 자세 저장 파일 지정 및 이전 자세 불러오기
+    pub fn load_poses(&self, path: PathBuf) -> Result<(), String> {
+        self.poses.load(path)
+    }
+
+    // 저장된 자세 조회 함수
+    pub fn pose(&self, name: &str) -> Option<RobotState> {
+        self.poses.get(name)
+    }
+
+    // 저장된 자세 이름 목록 (이름순)
+    pub fn pose_names(&self) -> Vec<String> {
+        self.poses.all().into_keys().collect()
+    }
+
+    // 저장된 자세 삭제 함수
+    pub fn delete_pose(&self, name: &str) -> Result<(), String> {
+        if self.poses.remove(name) {
+            Ok(())
+        } else {
+            Err(format!("저장된 자세가 없습니다: {}", name))
+        }
+    }
+
+    // 자세 프리셋 파일 내보내기 함수 (현재 팔의 보정값과 함께 JSON으로 저장)
+    pub fn export_pose_preset(
+        &self,
+        name: &str,
+        pose: RobotState,
+        note: Option<String>,
+        path: &str,
+    ) -> Result<PosePreset, String> {
+        if name.trim().is_empty() {
+            return Err("자세 이름이 비어 있습니다.".into());
+        }
+        let preset = PosePreset {
+            name: name.to_string(),
+            pose,
+            calibration: self.calibration(),
+            exported_unix_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            note,
+        };
+        preset.write(path)?;
+        Ok(preset)
+    }
+
+    // 자세 프리셋 파일 가져오기 함수 (보정값이 다르거나 관절 제한 밖이면 거부)
+    pub fn import_pose_preset(&self, path: &str) -> Result<PosePreset, String> {
+        let preset = PosePreset::read(path)?;
+
+        preset.check_calibration(&self.calibration())?;
+        let out_of_range: Vec<String> = self
+            .check_limits(&preset.pose)
+            .joints
+            .iter()
+            .filter(|status| !status.within)
+            .map(|status| status.joint.to_string())
+            .collect();
+        if !out_of_range.is_empty() {
+            return Err(format!(
+                "관절 {} 값이 현재 관절 제한을 벗어납니다.",
+                out_of_range.join(", ")
+            ));
+        }
+        self.save_pose(&preset.name, preset.pose.clone())?;
+        Ok(preset)
+    }
+
+    // 사용자 정의 명령 등록 함수 (같은 이름이 있으면 덮어씀)
+    pub fn register_command(&self, name: &str, command: CustomCommand) -> Result<(), String> {
+        if name.trim().is_empty() {
+            return Err("명령 이름이 비어 있습니다.".into());
+        }
+        if matches!(command.opcode, 253 | 254 | ESCAPE_BYTE) {
+            return Err("사용자 정의 명령 opcode는 253, 254, 251이 아니어야 합니다.".into());
+        }
+        for (i, field) in command.fields.iter().enumerate() {
+            if field.name.trim().is_empty() {
+                return Err("필드 이름이 비어 있습니다.".into());
+            }
+            if command.fields[..i]
+                .iter()
+                .any(|other| other.name == field.name)
+            {
+                return Err(format!("필드 이름이 중복됩니다: {}", field.name));
+            }
+        }
+        let payload_len: usize = command.fields.iter().map(|field| field.kind.len()).sum();
+        if payload_len > MAX_PAYLOAD_LEN {
+            return Err(format!(
+                "페이로드 길이는 {}바이트를 넘을 수 없습니다.",
+                MAX_PAYLOAD_LEN
+            ));
+        }
+        lock(&self.custom_commands).insert(name.to_string(), command);
+        Ok(())
+    }
+
+    // 등록된 사용자 정의 명령 목록 (이름순)
+    pub fn custom_commands(&self) -> BTreeMap<String, CustomCommand> {
+        lock(&self.custom_commands).clone()
+    }
+
+    // 사용자 정의 명령 전송 함수 (페이로드를 정의에 맞춰 검사 후 전송)
+    pub fn send_custom(&self, name: &str, payload: &HashMap<String, i64>) -> Result<(), String> {
+        let command = lock(&self.custom_commands)
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("등록된 명령이 없습니다: {}", name))?;
+        let frame = encode_custom(&command, payload)?;
+        // 펌웨어 명령도 팔을 움직일 수 있으므로 데드맨 스위치 적용
+        if self.deadman_lapsed() {
+            return Err("데드맨 스위치 신호가 끊겨 동작 명령을 전송하지 않습니다.".into());
+        }
+        // 식별 명령은 확인 절차 자체이므로 제외
+        if name != IDENTIFY_COMMAND {
+            self.check_handshake()?;
+        }
+        let data = match *lock(&self.encoding) {
+            FrameEncoding::Plain => frame,
+            FrameEncoding::Escaped => escape_frame(&frame),
+        };
+        self.send_data(&data).map_err(|e| {
+            let message = format!("데이터 전송 실패: {}", e);
+            *lock(&self.last_error) = Some(message.clone());
+            message
+        })?;
+        self.metrics.frames_sent.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    // 장치 식별 함수 (등록된 identify 명령 전송, duration_ms 필드가 있으면 켜짐 시간 전달)
+    // 오류 패킷이 설정된 컨트롤러가 거부 응답을 보내면 지원하지 않는 것으로 보고 오류 반환
+    pub fn identify_device(&self, duration: Duration) -> Result<(), String> {
+        if duration.is_zero() || duration > MAX_IDENTIFY_DURATION {
+            return Err(format!(
+                "식별 시간은 1에서 {}ms 사이여야 합니다.",
+                MAX_IDENTIFY_DURATION.as_millis()
+            ));
+        }
+        let command = lock(&self.custom_commands)
+            .get(IDENTIFY_COMMAND)
+            .cloned()
+            .ok_or("식별 명령(identify)이 등록되지 않았습니다.")?;
+        let mut payload = HashMap::new();
+        if command
+            .fields
+            .iter()
+            .any(|field| field.name == IDENTIFY_DURATION_FIELD)
+        {
+            payload.insert(
+                IDENTIFY_DURATION_FIELD.to_string(),
+                duration.as_millis() as i64,
+            );
+        }
+        *lock(&self.reported_protocol) = None;
+        self.send_custom(IDENTIFY_COMMAND, &payload)?;
+        // 오류 패킷 대기 중에도 설정을 읽으므로 잠금을 먼저 해제
+        let required = *lock(&self.required_protocol);
+        let Some(expected) = required else {
+            return match self.controller_rejection(COMMAND_REPLY_WINDOW) {
+                Some(message) => Err(format!("컨트롤러가 식별 명령을 거부했습니다: {}", message)),
+                None => Ok(()),
+            };
+        };
+        let reported = self.identity_reply(COMMAND_REPLY_WINDOW)?;
+        if reported != expected {
+            return Err(format!(
+                "장치 프로토콜 버전이 다릅니다 (기대 {}, 보고 {}).",
+                expected, reported
+            ));
+        }
+        Ok(())
+    }
+
+    // 식별 응답 대기 함수 (응답 전에 오류 패킷이 오면 거부로 보고)
+    fn identity_reply(&self, window: Duration) -> Result<u8, String> {
+        let started = Instant::now();
+        while let Some(remaining) = window.checked_sub(started.elapsed()) {
+            let controller_errors = self.metrics.controller_errors.load(Ordering::Relaxed);
+            let result = self.read_data_timeout(Some(remaining.min(MOTION_WAIT_SLICE)));
+            if let Some(version) = *lock(&self.reported_protocol) {
+                return Ok(version);
+            }
+            if let Err(e) = result {
+                if self.metrics.controller_errors.load(Ordering::Relaxed) != controller_errors {
+                    return Err(format!("컨트롤러가 식별 명령을 거부했습니다: {}", e));
+                }
+            }
+        }
+        Err("장치가 식별 응답(프로토콜 버전)을 보내지 않았습니다.".into())
+    }
+
+    // 식별 확인 요구 설정 함수 (켜면 expected_version을 보고한 장치에만 명령 전송)
+    // 식별 응답은 identify 명령과 같은 헤드 바이트에 프로토콜 버전 1바이트와 테일(254)
+    pub fn set_require_handshake(&self, enabled: bool, expected_version: u8) -> Result<(), String> {
+        if !enabled {
+            *lock(&self.required_protocol) = None;
+            return Ok(());
+        }
+        let opcode = lock(&self.custom_commands)
+            .get(IDENTIFY_COMMAND)
+            .map(|command| command.opcode)
+            .ok_or("식별 확인을 켜려면 식별 명령(identify)을 먼저 등록해주세요.")?;
+        if lock(&self.error_frames).opcode == Some(opcode) {
+            return Err(
+                "식별 명령 opcode가 오류 패킷 헤드와 같아 응답을 구분할 수 없습니다.".into(),
+            );
+        }
+        *lock(&self.required_protocol) = Some(expected_version);
+        Ok(())
+    }
+
+    // 식별 확인을 요구할 때 기대 버전을 보고한 장치인지 확인
+    fn check_handshake(&self) -> Result<(), String> {
+        let Some(expected) = *lock(&self.required_protocol) else {
+            return Ok(());
+        };
+        match *lock(&self.reported_protocol) {
+            Some(reported) if reported == expected => Ok(()),
+            Some(reported) => Err(format!(
+                "장치 프로토콜 버전 {}이(가) 기대 버전 {}과(와) 달라 명령을 전송하지 않습니다.",
+                reported, expected
+            )),
+            None => Err(format!(
+                "장치 식별 확인(프로토콜 버전 {}) 전에는 명령을 전송하지 않습니다.",
+                expected
+            )),
+        }
+    }
+
+    // 컨트롤러 고장 래치 해제 함수 (등록된 clear_fault 명령 전송 후 고장 비트가 0이 될 때까지 확인)
+    // 호스트 쪽 비상 정지 해제와 별개, 해제되지 않으면 남은 고장 비트를 오류로 반환
+    pub fn clear_controller_fault(&self) -> Result<(), String> {
+        if lock(&self.fault_offset).is_none() {
+            return Err("고장 상태 위치가 설정되지 않아 해제를 확인할 수 없습니다.".into());
+        }
+        if !lock(&self.custom_commands).contains_key(CLEAR_FAULT_COMMAND) {
+            return Err("고장 해제 명령(clear_fault)이 등록되지 않았습니다.".into());
+        }
+        self.send_custom(CLEAR_FAULT_COMMAND, &HashMap::new())?;
+
+        let started = Instant::now();
+        let mut remaining_bits = None;
+        while let Some(remaining) = FAULT_CLEAR_TIMEOUT.checked_sub(started.elapsed()) {
+            if let Ok(robot_state) = self.read_data_timeout(Some(remaining)) {
+                match robot_state.fault_bits {
+                    Some(0) => return Ok(()),
+                    bits => remaining_bits = bits,
+                }
+            }
+        }
+        Err(match remaining_bits {
+            Some(bits) => format!("고장이 해제되지 않았습니다 (남은 고장 비트 0x{:02X})", bits),
+            None => "고장 해제 후 상태를 읽지 못했습니다.".into(),
+        })
+    }
+
+    // window 동안 상태를 읽으며 컨트롤러 오류 패킷(거부 응답)을 기다림 (받으면 메시지 반환)
+    fn controller_rejection(&self, window: Duration) -> Option<String> {
+        // 오류 패킷을 쓰지 않는 컨트롤러는 거부 여부를 알 수 없음
+        lock(&self.error_frames).opcode?;
+        let started = Instant::now();
+        while let Some(remaining) = window.checked_sub(started.elapsed()) {
+            let controller_errors = self.metrics.controller_errors.load(Ordering::Relaxed);
+            if let Err(e) = self.read_data_timeout(Some(remaining)) {
+                if self.metrics.controller_errors.load(Ordering::Relaxed) != controller_errors {
+                    return Some(e);
+                }
+            }
         }
+        None
     }
 
     // 시리얼 포트 초기화 함수
-    pub fn initialize(&self, port_name: &str, baud_rate: u32) -> Result<(), serialport::Error> {
+    pub fn initialize(&self, port_name: &str, baud_rate: u32) -> Result<(), SerialError> {
         let s = serialport::new(port_name, baud_rate)
-            .timeout(Duration::from_millis(100))
+            .timeout(IO_TIMEOUT)
             .open()?;
-        let mut port_lock = self.port.lock().unwrap();
-        *port_lock = Some(s);
+        // send_command와 같은 순서로 잠가 교착 상태를 피함
+        let mut last_sent_frame = lock(&self.last_sent_frame);
+        let mut port_lock = lock(&self.port);
+        *port_lock = Some(Box::new(s));
+        lock(&self.pending).clear();
+        *last_sent_frame = None;
+        *lock(&self.reported_protocol) = None;
+        *lock(&self.connection) = Some(ConnectionInfo {
+            port_name: port_name.to_string(),
+            baud_rate: Some(baud_rate),
+        });
+        self.sessions
+            .open(port_name, Some(baud_rate), self.frame_counts());
+        Ok(())
+    }
+
+    // TCP 전송 계층 초기화 함수
+    #[cfg(feature = "tcp")]
+    pub fn initialize_tcp(&self, addr: &str) -> Result<(), String> {
+        let mut transport = TcpTransport::connect(addr, TCP_CONNECT_TIMEOUT)?;
+        transport
+            .set_timeout(IO_TIMEOUT)
+            .map_err(|e| format!("TCP 타임아웃 설정 실패: {}", e))?;
+        // send_command와 같은 순서로 잠가 교착 상태를 피함
+        let mut last_sent_frame = lock(&self.last_sent_frame);
+        let mut port_lock = lock(&self.port);
+        *port_lock = Some(Box::new(transport));
+        lock(&self.pending).clear();
+        *last_sent_frame = None;
+        *lock(&self.reported_protocol) = None;
+        *lock(&self.connection) = Some(ConnectionInfo {
+            port_name: addr.to_string(),
+            baud_rate: None,
+        });
+        self.sessions.open(addr, None, self.frame_counts());
         Ok(())
     }
 
     // 데이터 전송 함수
     pub fn send_data(&self, data: &[u8]) -> Result<(), serialport::Error> {
-        let mut port_lock = self.port.lock().unwrap();
+        let mut port_lock = lock(&self.port);
         if let Some(ref mut port) = *port_lock {
             port.write_all(data)?;
             // 데이터 전송 로그
@@ -63,149 +1149,3628 @@ impl SerialPortManager {
         }
     }
 
-    // 데이터 수신 함수
-    pub fn read_data(&self) -> Result<RobotState, String> {
-        let mut port_lock = self.port.lock().unwrap();
-        if let Some(ref mut port) = *port_lock {
-            let mut buffer: Vec<u8> = Vec::new();
-            let mut byte: u8;
-
-            // 헤드 바이트(253) 찾기
-            loop {
-                let mut single_byte = [0u8; 1];
-                match port.read_exact(&mut single_byte) {
-                    Ok(_) => {
-                        byte = single_byte[0];
-                        if byte == 253 {
-                            buffer.push(byte);
-                            break;
-                        }
-                    },
-                    Err(ref e) if e.kind() == ErrorKind::TimedOut => {
-                        return Err("데이터를 기다리는 동안 타임아웃이 발생했습니다.".into());
-                    },
-                    Err(e) => {
-                        return Err(format!("시리얼 포트 읽기 오류: {}", e));
-                    },
-                }
-            }
+    // 로봇 명령 전송 함수
+    pub fn send_command(&self, robot_state: &RobotState) -> Result<CommandReport, String> {
+        // 데드맨 스위치 신호가 끊기면 모든 동작 명령 거부
+        if self.deadman_lapsed() {
+            return Err("데드맨 스위치 신호가 끊겨 동작 명령을 전송하지 않습니다.".into());
+        }
+        self.check_handshake()?;
 
-            // 나머지 14바이트 읽기
-            let mut remaining_bytes = [0u8; 14];
-            match port.read_exact(&mut remaining_bytes) {
-                Ok(_) => {
-                    buffer.extend_from_slice(&remaining_bytes);
-                    // 수신 데이터 로그
-                    println!("Received data: {:?}", buffer);
+        // 관절 제한 밖의 값은 한계로 조정
+        let (robot_state, clamped_joints) = self.clamp_to_limits(robot_state);
+        let robot_state = &robot_state;
 
-                    if buffer.len() != 15 || buffer[14] != 254 {
-                        return Err("유효하지 않은 데이터 패킷: 잘못된 테일 바이트".into());
-                    }
+        // 금지 영역에 들어가는 명령은 전송하지 않음
+        if let Some(region) = lock(&self.keepout_regions)
+            .iter()
+            .find(|region| region.contains(robot_state))
+        {
+            return Err(format!(
+                "금지 영역 '{}'에 들어가는 명령입니다.",
+                region.name
+            ));
+        }
 
-                    Ok(RobotState {
-                        joint_1: buffer[1],
-                        joint_2: buffer[2],
-                        joint_3: buffer[3],
-                        joint_4: buffer[4],
-                        joint_5: buffer[5],
-                        joint_6: buffer[6],
-                        digital_input_1: buffer[7] != 0,
-                        digital_input_2: buffer[8] != 0,
-                        digital_input_3: buffer[9] != 0,
-                        digital_output_1: buffer[10] != 0,
-                        digital_output_2: buffer[11] != 0,
-                        digital_output_3: buffer[12] != 0,
-                        robot_speed: buffer[13],
-                    })
-                },
-                Err(e) => {
-                    return Err(format!("나머지 데이터 읽기 오류: {}", e));
-                },
-            }
-        } else {
-            Err("시리얼 포트가 초기화되지 않았습니다.".into())
+        // 최소 유지 시간이 지나지 않은 출력은 다시 전환하지 않음
+        let toggled_outputs = self.check_output_dwell(robot_state)?;
+
+        // 관절 한계 근처에서는 감속한 뒤 전체 속도 배율 적용
+        let requested_speed =
+            lock(&self.limit_approach).speed(robot_state, &lock(&self.joint_limits));
+        let effective_speed = (requested_speed as f32 * *lock(&self.speed_scale)).round() as u8;
+        let frame = encode_command(
+            &RobotState {
+                robot_speed: effective_speed,
+                ..robot_state.clone()
+            },
+            *lock(&self.io_layout),
+        );
+        // 변경 필드 명령을 지원하는 컨트롤러에는 직전 패킷과 다른 필드만 전송
+        let mut last_sent_frame = lock(&self.last_sent_frame);
+        let outgoing = lock(&self.delta_opcode)
+            .zip(last_sent_frame.as_deref())
+            .and_then(|(opcode, previous)| encode_delta(previous, &frame, opcode))
+            .unwrap_or_else(|| frame.clone());
+        let data = match *lock(&self.encoding) {
+            FrameEncoding::Plain => outgoing,
+            FrameEncoding::Escaped => escape_frame(&outgoing),
+        };
+
+        // 데이터 전송 로그
+        println!("Sending robot commands: {:?}", data);
+
+        if let Err(e) = self.send_data(&data) {
+            // 컨트롤러가 받은 상태를 알 수 없으므로 다음에는 전체 패킷 전송
+            *last_sent_frame = None;
+            let message = format!("데이터 전송 실패: {}", e);
+            *lock(&self.last_error) = Some(message.clone());
+            return Err(message);
         }
+        *last_sent_frame = Some(frame);
+        drop(last_sent_frame);
+        self.metrics.frames_sent.fetch_add(1, Ordering::Relaxed);
+        let now = Instant::now();
+        let mut toggled_at = lock(&self.output_toggled_at);
+        for output in toggled_outputs {
+            toggled_at[output as usize - 1] = Some(now);
+        }
+        drop(toggled_at);
+        *lock(&self.last_command) = Some(robot_state.clone());
+        Ok(CommandReport {
+            requested_speed,
+            effective_speed,
+            clamped_joints,
+        })
     }
 
-    // 시리얼 포트 목록 가져오기 함수
-    pub fn list_ports() -> Result<Vec<serialport::SerialPortInfo>, serialport::Error> {
-        serialport::available_ports()
+    // 마지막 전송 명령 조회 함수
+    pub fn last_command(&self) -> Option<RobotState> {
+        lock(&self.last_command).clone()
     }
-}
 
-// AppState 구조체 정의
-#[derive(Clone)]
-pub struct AppState {
-    pub serial_manager: Arc<SerialPortManager>,
-}
+    // 데이터 수신 함수
+    pub fn read_data(&self) -> Result<RobotState, String> {
+        self.read_data_timeout(None)
+    }
 
-// 시리얼 포트 목록 커맨드
-#[tauri::command]
-pub fn list_serial_ports() -> Result<Vec<String>, String> {
-    match SerialPortManager::list_ports() {
-        Ok(ports) => {
-            let port_names = ports
-                .into_iter()
-                .map(|port| port.port_name)
-                .collect::<Vec<String>>();
-            Ok(port_names)
+    // 호출 단위 타임아웃을 적용한 데이터 수신 함수 (None이면 포트 기본값)
+    pub fn read_data_timeout(&self, timeout: Option<Duration>) -> Result<RobotState, String> {
+        let started = Instant::now();
+        self.track_read_gap(started);
+        let result = self.read_frame(timeout);
+        *lock(&self.read_finished_at) = Some(Instant::now());
+        match &result {
+            Ok(robot_state) => {
+                self.metrics
+                    .last_read_latency_us
+                    .store(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+                *lock(&self.last_state_at) = Some(SystemTime::now());
+                self.mirror_inputs(robot_state);
+                self.run_input_bindings(robot_state);
+                self.publish_state(robot_state);
+            }
+            Err(e) => *lock(&self.last_error) = Some(e.clone()),
         }
-        Err(e) => Err(format!("시리얼 포트 목록 가져오기 실패: {}", e)),
+        result
     }
-}
 
-// 시리얼 포트 초기화 커맨드
-#[tauri::command]
-pub fn initialize_serial(
-    state: State<'_, AppState>,
-    port: String,
-    baud_rate: u32,
-) -> Result<String, String> {
-    match state.serial_manager.initialize(&port, baud_rate) {
-        Ok(_) => Ok("시리얼 포트가 성공적으로 초기화되었습니다.".into()),
-        Err(e) => Err(format!("시리얼 포트 열기 실패: {}", e)),
+    // 정지 상태 감지 기준 설정 함수
+    pub fn set_stuck_threshold(&self, threshold: u32) {
+        self.stuck_threshold.store(threshold, Ordering::SeqCst);
+        self.repeated_frames.store(0, Ordering::SeqCst);
+        self.stuck_warning.store(false, Ordering::SeqCst);
     }
-}
 
-// 로봇 명령 전송 커맨드
-#[tauri::command]
-pub fn send_robot_commands(
-    state: State<'_, AppState>,
+    // 직전 패킷과 바이트 단위로 같은지 세는 함수 (기준을 넘는 순간 한 번 경고)
+    fn track_repeated_frame(&self, frame: &[u8]) {
+        let is_repeat = lock(&self.last_raw_frame)
+            .as_ref()
+            .is_some_and(|last| last.bytes == frame);
+        if !is_repeat {
+            self.repeated_frames.store(0, Ordering::SeqCst);
+            return;
+        }
+        let repeats = self.repeated_frames.fetch_add(1, Ordering::SeqCst) + 1;
+        let threshold = self.stuck_threshold.load(Ordering::SeqCst);
+        if threshold > 0 && repeats == threshold {
+            self.stuck_warning.store(true, Ordering::SeqCst);
+        }
+    }
+
+    // 직전 읽기 이후 다음 읽기까지 걸린 간격 기록 (길면 그 사이 OS 수신 버퍼에 바이트가 쌓임)
+    fn track_read_gap(&self, started: Instant) {
+        let Some(gap) = lock(&self.read_finished_at).map(|at| started.duration_since(at)) else {
+            return;
+        };
+        if gap < OVERRUN_READ_GAP {
+            return;
+        }
+        let mut slow_read = lock(&self.slow_read);
+        let keep = slow_read.is_some_and(|(at, longest)| {
+            started.duration_since(at) <= OVERRUN_WINDOW && longest >= gap
+        });
+        if !keep {
+            *slow_read = Some((started, gap));
+        }
+    }
+
+    // 잘못된 패킷 기록 (구간 안에 잘못된 패킷이 몰리고 느린 읽기가 있었으면 버퍼 넘침으로 추정)
+    fn track_framing_error(&self) {
+        let now = Instant::now();
+        let mut errors_at = lock(&self.framing_errors_at);
+        errors_at.push_back(now);
+        while errors_at
+            .front()
+            .is_some_and(|&at| now.duration_since(at) > OVERRUN_WINDOW)
+        {
+            errors_at.pop_front();
+        }
+        if errors_at.len() < OVERRUN_FRAMING_ERRORS {
+            return;
+        }
+        let mut slow_read = lock(&self.slow_read);
+        let Some((_, gap)) = slow_read.filter(|&(at, _)| now.duration_since(at) <= OVERRUN_WINDOW)
+        else {
+            return;
+        };
+        // 같은 넘침을 한 번만 보고하도록 구간 기록 초기화
+        let framing_errors = errors_at.len() as u32;
+        errors_at.clear();
+        *slow_read = None;
+        self.metrics.buffer_overruns.fetch_add(1, Ordering::Relaxed);
+        *lock(&self.overrun_warning) = Some(BufferOverrun {
+            framing_errors,
+            read_gap_ms: gap.as_millis() as u64,
+        });
+    }
+
+    // 아직 알리지 않은 버퍼 넘침 추정 가져오기 (가져가면 초기화)
+    pub fn take_buffer_overrun_warning(&self) -> Option<BufferOverrun> {
+        lock(&self.overrun_warning).take()
+    }
+
+    // 버퍼 넘침 추정 횟수 조회 함수
+    pub fn buffer_overrun_count(&self) -> u64 {
+        self.metrics.buffer_overruns.load(Ordering::Relaxed)
+    }
+
+    // 보고할 정지 상태 경고 확인 함수 (반복 횟수 반환, 확인 후 초기화)
+    pub fn take_stuck_warning(&self) -> Option<u32> {
+        self.stuck_warning
+            .swap(false, Ordering::SeqCst)
+            .then(|| self.repeated_frames.load(Ordering::SeqCst))
+    }
+
+    // 마지막 원시 패킷 조회 함수
+    pub fn last_raw_frame(&self) -> Option<LastRawFrame> {
+        lock(&self.last_raw_frame).clone()
+    }
+
+    // 상태를 읽어 관절별 raw 값과 보정 각도를 함께 반환하는 함수
+    pub fn read_full_state(&self, timeout: Option<Duration>) -> Result<FullRobotState, String> {
+        let robot_state = self.read_data_timeout(timeout)?;
+        let calibration = self.calibration();
+        Ok(FullRobotState {
+            joints: (1..=6)
+                .map(|index| {
+                    let raw = joint(&robot_state, index);
+                    JointReading {
+                        joint: index,
+                        raw,
+                        degrees: calibration[index as usize - 1]
+                            .map(|calibration| calibration.degrees(raw)),
+                    }
+                })
+                .collect(),
+            digital_inputs: [
+                robot_state.digital_input_1,
+                robot_state.digital_input_2,
+                robot_state.digital_input_3,
+            ],
+            digital_outputs: [
+                robot_state.digital_output_1,
+                robot_state.digital_output_2,
+                robot_state.digital_output_3,
+            ],
+            robot_speed: robot_state.robot_speed,
+            received_unix_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            device_timestamp: robot_state.device_timestamp,
+        })
+    }
+
+    // 프론트엔드 창이 닫혔을 때 동작 설정 함수
+    pub fn set_frontend_loss_behavior(&self, behavior: FrontendLossBehavior) {
+        *lock(&self.frontend_loss) = behavior;
+    }
+
+    // 프론트엔드 창이 닫혔을 때 동작 조회 함수
+    pub fn frontend_loss_behavior(&self) -> FrontendLossBehavior {
+        *lock(&self.frontend_loss)
+    }
+
+    // 입력 미러링 채널 설정 함수
+    pub fn set_io_mirror(&self, mask: u8) -> Result<(), String> {
+        if mask > 0b111 {
+            return Err(
+                "미러링 마스크는 채널 1~3에 해당하는 하위 3비트만 사용할 수 있습니다.".into(),
+            );
+        }
+        self.io_mirror.store(mask, Ordering::SeqCst);
+        Ok(())
+    }
+
+    // 미러링 대상 입력을 출력에 반영하는 함수 (출력이 바뀔 때만 명령 전송)
+    fn mirror_inputs(&self, robot_state: &RobotState) {
+        let mask = self.io_mirror.load(Ordering::SeqCst);
+        if mask == 0 {
+            return;
+        }
+        let Some(mut command) = self.last_command() else {
+            return;
+        };
+        let inputs = [
+            robot_state.digital_input_1,
+            robot_state.digital_input_2,
+            robot_state.digital_input_3,
+        ];
+        let before = command.clone();
+        for (i, &input) in inputs.iter().enumerate() {
+            if mask & (1 << i) != 0 {
+                set_digital_output(&mut command, i as u8 + 1, input);
+            }
+        }
+        let changed = command.digital_output_1 != before.digital_output_1
+            || command.digital_output_2 != before.digital_output_2
+            || command.digital_output_3 != before.digital_output_3;
+        if changed {
+            // 전송 실패는 send_command가 last_error에 기록
+            let _ = self.send_command(&command);
+        }
+    }
+
+    // 관절 제한 설정 함수 (joint: 1~6)
+    pub fn set_joint_limit(&self, joint: u8, min: u8, max: u8) -> Result<(), String> {
+        if !(1..=6).contains(&joint) {
+            return Err("관절 번호는 1에서 6 사이여야 합니다.".into());
+        }
+        if min > max {
+            return Err(format!("관절 {} 제한의 최솟값이 최댓값보다 큽니다.", joint));
+        }
+        lock(&self.joint_limits)[joint as usize - 1] = (min, max);
+        Ok(())
+    }
+
+    // 관절 제한 일괄 교체 함수 (모두 검사한 뒤 한 번에 적용, 하나라도 잘못되면 그대로 유지)
+    pub fn set_all_joint_limits(&self, limits: [(u8, u8); 6]) -> Result<(), String> {
+        validate_limits(&limits)?;
+        *lock(&self.joint_limits) = limits;
+        Ok(())
+    }
+
+    // 관절 보정값 저장 함수 (joint: 1~6)
+    pub fn set_calibration(&self, joint: u8, calibration: JointCalibration) -> Result<(), String> {
+        if !(1..=6).contains(&joint) {
+            return Err("관절 번호는 1에서 6 사이여야 합니다.".into());
+        }
+        lock(&self.calibration)[joint as usize - 1] = Some(calibration);
+        Ok(())
+    }
+
+    // 관절 응답 곡선 설정 함수 (joint: 1~6)
+    pub fn set_joint_response_curve(&self, joint: u8, curve: ResponseCurve) -> Result<(), String> {
+        if !(1..=6).contains(&joint) {
+            return Err("관절 번호는 1에서 6 사이여야 합니다.".into());
+        }
+        curve.validate()?;
+        lock(&self.response_curves)[joint as usize - 1] = curve;
+        Ok(())
+    }
+
+    // 관절 응답 곡선 조회 함수 (관절 1~6 순서)
+    pub fn response_curves(&self) -> [ResponseCurve; 6] {
+        lock(&self.response_curves).clone()
+    }
+
+    // 정규화 값 명령 전송 함수 (응답 곡선을 적용해 관절 제한 범위의 raw 값으로 변환, 출력은 마지막 명령 유지)
+    pub fn send_normalized(&self, values: [f32; 6], speed: u8) -> Result<CommandReport, String> {
+        if values.iter().any(|value| !(0.0..=1.0).contains(value)) {
+            return Err("정규화 값은 0.0에서 1.0 사이여야 합니다.".into());
+        }
+        let curves = self.response_curves();
+        let limits = *lock(&self.joint_limits);
+        let mut command = self.command_base();
+        for (i, (value, curve)) in values.iter().zip(&curves).enumerate() {
+            let (min, max) = limits[i];
+            let raw = min as f32 + curve.apply(*value) * (max - min) as f32;
+            set_joint(&mut command, i as u8 + 1, round_joint(raw as f64));
+        }
+        command.robot_speed = speed;
+        self.send_command(&command)
+    }
+
+    // 관절 값만 바꿔 보낼 명령의 기준 상태 (마지막 명령, 없으면 모든 값 0)
+    fn command_base(&self) -> RobotState {
+        self.last_command().unwrap_or(RobotState {
+            joint_1: 0,
+            joint_2: 0,
+            joint_3: 0,
+            joint_4: 0,
+            joint_5: 0,
+            joint_6: 0,
+            digital_input_1: false,
+            digital_input_2: false,
+            digital_input_3: false,
+            digital_output_1: false,
+            digital_output_2: false,
+            digital_output_3: false,
+            robot_speed: 0,
+            device_timestamp: None,
+            supply_voltage: None,
+            fault_bits: None,
+        })
+    }
+
+    // 관절 표시 단위 설정 함수 (joint: 1~6)
+    pub fn set_joint_unit(&self, joint: u8, unit: JointUnit) -> Result<(), String> {
+        if !(1..=6).contains(&joint) {
+            return Err("관절 번호는 1에서 6 사이여야 합니다.".into());
+        }
+        unit.validate()?;
+        lock(&self.joint_units)[joint as usize - 1] = unit;
+        Ok(())
+    }
+
+    // 관절 표시 단위 조회 함수 (관절 1~6 순서)
+    pub fn joint_units(&self) -> [JointUnit; 6] {
+        lock(&self.joint_units).clone()
+    }
+
+    // 관절별 단위와 제한 범위 조회 함수
+    pub fn joint_info(&self) -> Vec<JointInfo> {
+        let units = self.joint_units();
+        let limits = *lock(&self.joint_limits);
+        units
+            .into_iter()
+            .zip(limits)
+            .enumerate()
+            .map(|(i, (unit, (min, max)))| JointInfo {
+                joint: i as u8 + 1,
+                unit,
+                min,
+                max,
+            })
+            .collect()
+    }
+
+    // 관절별 단위 값 명령 전송 함수 (출력은 마지막 명령 유지)
+    // units_check: 호출 측이 가정한 관절별 단위 이름 (설정과 다르면 전송하지 않음)
+    pub fn send_in_units(
+        &self,
+        values: [f64; 6],
+        units_check: &[String; 6],
+        speed: u8,
+    ) -> Result<CommandReport, String> {
+        let units = self.joint_units();
+        let mut command = self.command_base();
+        for (i, ((value, unit), expected)) in values.iter().zip(&units).zip(units_check).enumerate()
+        {
+            if unit.label != *expected {
+                return Err(format!(
+                    "관절 {}의 단위가 다릅니다: 설정 {}, 요청 {}",
+                    i + 1,
+                    unit.label,
+                    expected
+                ));
+            }
+            let raw = unit
+                .raw(*value)
+                .map_err(|e| format!("관절 {}: {}", i + 1, e))?;
+            set_joint(&mut command, i as u8 + 1, raw);
+        }
+        command.robot_speed = speed;
+        self.send_command(&command)
+    }
+
+    // 관절 보정값 조회 함수 (관절 1~6 순서)
+    pub fn calibration(&self) -> [Option<JointCalibration>; 6] {
+        *lock(&self.calibration)
+    }
+
+    // 디지털 출력 최소 유지 시간 설정 함수 (index: 1~3)
+    pub fn set_output_dwell(&self, index: u8, dwell: Duration) -> Result<(), String> {
+        if !(1..=3).contains(&index) {
+            return Err("출력 번호는 1에서 3 사이여야 합니다.".into());
+        }
+        lock(&self.output_dwell)[index as usize - 1] = dwell;
+        Ok(())
+    }
+
+    // 마지막 명령 대비 전환되는 출력 확인 함수 (유지 시간 내 재전환이면 오류)
+    fn check_output_dwell(&self, robot_state: &RobotState) -> Result<Vec<u8>, String> {
+        let Some(last) = self.last_command() else {
+            return Ok(Vec::new());
+        };
+        let dwell = *lock(&self.output_dwell);
+        let toggled_at = *lock(&self.output_toggled_at);
+        let mut toggled_outputs = Vec::new();
+        for output in 1..=3 {
+            if digital_output(robot_state, output) == digital_output(&last, output) {
+                continue;
+            }
+            let index = output as usize - 1;
+            if let Some(at) = toggled_at[index] {
+                let elapsed = at.elapsed();
+                if elapsed < dwell[index] {
+                    return Err(format!(
+                        "디지털 출력 {}은(는) {}ms 후에 다시 전환할 수 있습니다.",
+                        output,
+                        (dwell[index] - elapsed).as_millis()
+                    ));
+                }
+            }
+            toggled_outputs.push(output);
+        }
+        Ok(toggled_outputs)
+    }
+
+    // 관절 제한 조회 함수
+    pub fn joint_limits(&self) -> Vec<JointRange> {
+        limit_ranges(&lock(&self.joint_limits))
+    }
+
+    // 자세의 관절 제한 확인 함수 (전송하지 않음)
+    pub fn check_limits(&self, robot_state: &RobotState) -> LimitCheck {
+        check_pose(robot_state, &lock(&self.joint_limits))
+    }
+
+    // 관절 값을 제한 범위로 조정하는 함수 (조정된 관절 번호 함께 반환)
+    pub(crate) fn clamp_to_limits(&self, robot_state: &RobotState) -> (RobotState, Vec<u8>) {
+        clamp_pose(robot_state, &lock(&self.joint_limits))
+    }
+
+    // 금지 영역 추가 함수
+    pub fn add_keepout_region(&self, region: KeepoutRegion) -> Result<(), String> {
+        region.validate()?;
+        let mut regions = lock(&self.keepout_regions);
+        if regions.iter().any(|existing| existing.name == region.name) {
+            return Err(format!("이미 있는 금지 영역 이름입니다: {}", region.name));
+        }
+        regions.push(region);
+        Ok(())
+    }
+
+    // 금지 영역 삭제 함수
+    pub fn remove_keepout_region(&self, name: &str) -> Result<(), String> {
+        let mut regions = lock(&self.keepout_regions);
+        let index = regions
+            .iter()
+            .position(|region| region.name == name)
+            .ok_or_else(|| format!("금지 영역이 없습니다: {}", name))?;
+        regions.remove(index);
+        Ok(())
+    }
+
+    // 금지 영역 목록 조회 함수
+    pub fn keepout_regions(&self) -> Vec<KeepoutRegion> {
+        lock(&self.keepout_regions).clone()
+    }
+
+    // 런타임 상태 스냅샷 생성 함수
+    pub fn snapshot(&self) -> RuntimeSnapshot {
+        let error_frames = lock(&self.error_frames).clone();
+        RuntimeSnapshot {
+            last_command: self.last_command(),
+            init_pose: lock(&self.init_pose).clone(),
+            timestamp_offset: *lock(&self.timestamp_offset),
+            voltage_offset: *lock(&self.voltage_offset),
+            fault_offset: *lock(&self.fault_offset),
+            checksum_offset: *lock(&self.checksum_offset),
+            voltage_threshold: *lock(&self.voltage_threshold),
+            io_layout: *lock(&self.io_layout),
+            encoding: *lock(&self.encoding),
+            trailing_mode: *lock(&self.trailing_mode),
+            read_buffer_size: self.read_buffer_size.load(Ordering::Relaxed),
+            error_frames,
+            delta_opcode: *lock(&self.delta_opcode),
+            speed_scale: *lock(&self.speed_scale),
+            limit_approach: *lock(&self.limit_approach),
+            actuation_latency_ms: lock(&self.actuation_latency).as_millis() as u64,
+            pinned_joints: self.pinned_joints(),
+            joint_phases: self.joint_phases(),
+            interp_sync: self.interp_sync(),
+            joint_limits: self.joint_limits(),
+            calibration: self.calibration(),
+            response_curves: self.response_curves(),
+            joint_units: self.joint_units(),
+            keepout_regions: self.keepout_regions(),
+            poses: self.poses.all(),
+            custom_commands: self.custom_commands(),
+            io_mirror: self.io_mirror.load(Ordering::SeqCst),
+            input_bindings: lock(&self.input_bindings).clone(),
+            stuck_threshold: self.stuck_threshold.load(Ordering::SeqCst),
+            telemetry_decimation: self.telemetry_decimation.load(Ordering::SeqCst),
+            read_error_policy: *lock(&self.read_error_policy),
+            frontend_loss: self.frontend_loss_behavior(),
+            required_protocol: *lock(&self.required_protocol),
+            metrics: MetricsSnapshot {
+                bytes_read: self.metrics.bytes_read.load(Ordering::Relaxed),
+                frames_received: self.metrics.frames_received.load(Ordering::Relaxed),
+                frames_sent: self.metrics.frames_sent.load(Ordering::Relaxed),
+                malformed_frames: self.metrics.malformed_frames.load(Ordering::Relaxed),
+                controller_errors: self.metrics.controller_errors.load(Ordering::Relaxed),
+                buffer_overruns: self.metrics.buffer_overruns.load(Ordering::Relaxed),
+            },
+        }
+    }
+
+    // 런타임 상태 복원 함수 (빈 관리자에 먼저 적용해 검증한 뒤 반영)
+    pub fn restore(&self, snapshot: &RuntimeSnapshot) -> Result<(), String> {
+        SerialPortManager::new()
+            .apply_snapshot(snapshot)
+            .map_err(|e| format!("스냅샷 검증 실패: {}", e))?;
+        self.apply_snapshot(snapshot)
+    }
+
+    // 스냅샷 설정 적용 함수 (각 설정 함수의 검증을 그대로 사용)
+    fn apply_snapshot(&self, snapshot: &RuntimeSnapshot) -> Result<(), String> {
+        // 배치 방식과 선택 필드 위치는 서로 검증하므로 위치를 먼저 비움
+        self.set_timestamp_offset(None)?;
+        self.set_voltage_offset(None)?;
+        self.set_fault_offset(None)?;
+        self.set_checksum_offset(None)?;
+        self.set_io_layout(snapshot.io_layout)?;
+        self.set_timestamp_offset(snapshot.timestamp_offset)?;
+        self.set_voltage_offset(snapshot.voltage_offset)?;
+        self.set_fault_offset(snapshot.fault_offset)?;
+        self.set_checksum_offset(snapshot.checksum_offset)?;
+        self.set_voltage_threshold(snapshot.voltage_threshold);
+        self.set_encoding(snapshot.encoding);
+        self.set_trailing_mode(snapshot.trailing_mode);
+        self.set_read_buffer_size(snapshot.read_buffer_size)?;
+        self.set_error_frames(
+            snapshot.error_frames.opcode,
+            snapshot.error_frames.messages.clone(),
+        )?;
+        self.set_delta_commands(snapshot.delta_opcode)?;
+        self.set_speed_scale(snapshot.speed_scale)?;
+        self.set_limit_approach(snapshot.limit_approach)?;
+        self.set_actuation_latency(Duration::from_millis(snapshot.actuation_latency_ms))?;
+        self.set_pinned_joints(snapshot.pinned_joints)?;
+        self.set_joint_phases(snapshot.joint_phases)?;
+        self.set_interp_sync(snapshot.interp_sync);
+        for range in &snapshot.joint_limits {
+            self.set_joint_limit(range.joint, range.min, range.max)?;
+        }
+        *lock(&self.calibration) = [None; 6];
+        for (i, calibration) in snapshot.calibration.iter().enumerate() {
+            if let Some(calibration) = calibration {
+                self.set_calibration(i as u8 + 1, *calibration)?;
+            }
+        }
+        for (i, curve) in snapshot.response_curves.iter().enumerate() {
+            self.set_joint_response_curve(i as u8 + 1, curve.clone())?;
+        }
+        for (i, unit) in snapshot.joint_units.iter().enumerate() {
+            self.set_joint_unit(i as u8 + 1, unit.clone())?;
+        }
+        lock(&self.keepout_regions).clear();
+        for region in &snapshot.keepout_regions {
+            self.add_keepout_region(region.clone())?;
+        }
+        if snapshot.poses.keys().any(|name| name.trim().is_empty()) {
+            return Err("자세 이름이 비어 있습니다.".into());
+        }
+        self.poses.replace(snapshot.poses.clone());
+        lock(&self.custom_commands).clear();
+        for (name, command) in &snapshot.custom_commands {
+            self.register_command(name, command.clone())?;
+        }
+        self.set_io_mirror(snapshot.io_mirror)?;
+        lock(&self.input_bindings).clear();
+        for (&input_index, action) in &snapshot.input_bindings {
+            self.set_input_binding(input_index, Some(action.clone()))?;
+        }
+        self.set_stuck_threshold(snapshot.stuck_threshold);
+        self.set_telemetry_decimation(snapshot.telemetry_decimation)?;
+        self.set_read_error_policy(snapshot.read_error_policy)?;
+        self.set_frontend_loss_behavior(snapshot.frontend_loss);
+        match snapshot.required_protocol {
+            Some(version) => self.set_require_handshake(true, version)?,
+            None => self.set_require_handshake(false, 0)?,
+        }
+        match &snapshot.init_pose {
+            Some(init_pose) => self.set_init_pose(init_pose.clone())?,
+            None => *lock(&self.init_pose) = None,
+        }
+        *lock(&self.last_command) = snapshot.last_command.clone();
+        Ok(())
+    }
+
+    // 입력 바인딩 설정 함수 (action이 None이면 해제)
+    pub fn set_input_binding(
+        &self,
+        input_index: u8,
+        action: Option<InputAction>,
+    ) -> Result<(), String> {
+        if !(1..=3).contains(&input_index) {
+            return Err("입력 번호는 1에서 3 사이여야 합니다.".into());
+        }
+        if let Some(InputAction::ToggleOutput(output)) = action {
+            if !(1..=3).contains(&output) {
+                return Err("출력 번호는 1에서 3 사이여야 합니다.".into());
+            }
+        }
+        let mut bindings = lock(&self.input_bindings);
+        match action {
+            Some(action) => bindings.insert(input_index, action),
+            None => bindings.remove(&input_index),
+        };
+        Ok(())
+    }
+
+    // 바인딩된 입력의 상승 에지에서 동작 실행 함수
+    // 취소는 바로 적용하고 전송이 필요한 동작은 큐에 넣어 수신 경로를 막지 않음
+    fn run_input_bindings(&self, robot_state: &RobotState) {
+        let inputs = [
+            robot_state.digital_input_1,
+            robot_state.digital_input_2,
+            robot_state.digital_input_3,
+        ];
+        let mut rising = Vec::new();
+        {
+            let mut edges = lock(&self.input_edges);
+            if let Some(last) = edges.last {
+                let now = Instant::now();
+                for i in 0..3 {
+                    let debounced =
+                        edges.fired_at[i].is_some_and(|at| now.duration_since(at) < INPUT_DEBOUNCE);
+                    if inputs[i] && !last[i] && !debounced {
+                        edges.fired_at[i] = Some(now);
+                        rising.push(i as u8 + 1);
+                    }
+                }
+            }
+            edges.last = Some(inputs);
+        }
+
+        for input_index in rising {
+            let Some(action) = lock(&self.input_bindings).get(&input_index).cloned() else {
+                continue;
+            };
+            if action == InputAction::CancelMotion {
+                self.cancel_motion();
+                continue;
+            }
+            let queued = lock(&self.input_actions)
+                .as_ref()
+                .is_some_and(|sender| sender.send((input_index, action)).is_ok());
+            if !queued {
+                *lock(&self.last_error) = Some(format!(
+                    "입력 {} 동작을 처리할 스레드가 실행 중이 아닙니다.",
+                    input_index
+                ));
+            }
+        }
+    }
+
+    // 현재 설정 기준 패킷 해석 설정 조회 함수
+    pub fn decoder_config(&self) -> DecoderConfig {
+        // 오류 패킷 설정을 다시 잠그므로 구조체를 만들기 전에 계산
+        let identity_opcode = self.identity_opcode();
+        DecoderConfig {
+            timestamp_offset: *lock(&self.timestamp_offset),
+            voltage_offset: *lock(&self.voltage_offset),
+            fault_offset: *lock(&self.fault_offset),
+            checksum_offset: *lock(&self.checksum_offset),
+            io_layout: *lock(&self.io_layout),
+            trailing_mode: *lock(&self.trailing_mode),
+            encoding: *lock(&self.encoding),
+            error_frames: lock(&self.error_frames).clone(),
+            identity_opcode,
+        }
+    }
+
+    // 식별 확인을 요구할 때만 식별 응답 패킷 해석 (오류 패킷 헤드와 겹치면 오류 패킷 우선)
+    fn identity_opcode(&self) -> Option<u8> {
+        lock(&self.required_protocol).as_ref()?;
+        let error_opcode = lock(&self.error_frames).opcode;
+        lock(&self.custom_commands)
+            .get(IDENTIFY_COMMAND)
+            .map(|command| command.opcode)
+            .filter(|&opcode| error_opcode != Some(opcode))
+    }
+
+    // 원시 캡처 파일을 현재 패킷 설정으로 분석하는 함수 (끝이 잘린 파일은 남은 바이트 수로 보고)
+    pub fn analyze_capture(&self, path: &str) -> Result<CaptureAnalysis, String> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| format!("캡처 파일을 읽을 수 없습니다 ({}): {}", path, e))?;
+        analyze_frames(self.decoder_config(), &bytes)
+    }
+
+    // 상태 패킷 하나를 읽어 디코딩하는 함수
+    fn read_frame(&self, timeout: Option<Duration>) -> Result<RobotState, String> {
+        let config = self.decoder_config();
+        let mut port_lock = lock(&self.port);
+        let mut port_lost = false;
+        let result = if let Some(ref mut port) = *port_lock {
+            // 반환 시 (오류 포함) 기존 타임아웃으로 복원
+            let mut port = TimeoutGuard::new(port.as_mut(), timeout)
+                .map_err(|e| format!("타임아웃 설정 오류: {}", e))?;
+            let mut pending = lock(&self.pending);
+            // 설정은 setter에서 검증되었으므로 남은 바이트를 넘겨 바로 디코더 구성
+            let mut decoder = FrameDecoder::with_pending(config, std::mem::take(&mut *pending));
+            let mut chunk = vec![0u8; self.read_buffer_size.load(Ordering::Relaxed)];
+
+            let result = loop {
+                // 이미 수신한 바이트에서 먼저 패킷 찾기
+                match decoder.next_raw_frame() {
+                    Some(Ok((frame, robot_state))) => {
+                        // 수신 데이터 로그
+                        println!("Received data: {:?}", frame);
+                        self.metrics.frames_received.fetch_add(1, Ordering::Relaxed);
+                        self.track_repeated_frame(&frame);
+                        self.track_supply_voltage(&robot_state);
+                        let mut last_raw_frame = lock(&self.last_raw_frame);
+                        let device_interval_ms = last_raw_frame
+                            .as_ref()
+                            .and_then(|last| last.state.device_timestamp)
+                            .zip(robot_state.device_timestamp)
+                            .map(|(previous, current)| current.wrapping_sub(previous));
+                        *last_raw_frame = Some(LastRawFrame {
+                            hex: frame
+                                .iter()
+                                .map(|byte| format!("{:02X}", byte))
+                                .collect::<Vec<_>>()
+                                .join(" "),
+                            bytes: frame,
+                            received_unix_ms: SystemTime::now()
+                                .duration_since(UNIX_EPOCH)
+                                .map(|d| d.as_millis() as u64)
+                                .unwrap_or(0),
+                            device_interval_ms,
+                            state: robot_state.clone(),
+                        });
+                        drop(last_raw_frame);
+                        break Ok(robot_state);
+                    }
+                    Some(Err(DecodeError::Controller(message))) => {
+                        self.metrics
+                            .controller_errors
+                            .fetch_add(1, Ordering::Relaxed);
+                        break Err(message);
+                    }
+                    Some(Err(e)) => {
+                        if let DecodeError::InvalidFrame(frame) = &e {
+                            println!("Received data: {:?}", frame);
+                        }
+                        self.metrics
+                            .malformed_frames
+                            .fetch_add(1, Ordering::Relaxed);
+                        self.track_framing_error();
+                        break Err(e.to_string());
+                    }
+                    None => {}
+                }
+
+                match port.read(&mut chunk) {
+                    Ok(0) => {
+                        break Err("데이터를 기다리는 동안 타임아웃이 발생했습니다.".into());
+                    }
+                    Ok(n) => {
+                        self.metrics
+                            .bytes_read
+                            .fetch_add(n as u64, Ordering::Relaxed);
+                        decoder.extend(&chunk[..n]);
+                    }
+                    Err(ref e) if e.kind() == ErrorKind::TimedOut => {
+                        break Err("데이터를 기다리는 동안 타임아웃이 발생했습니다.".into());
+                    }
+                    Err(e) => {
+                        port_lost = is_disconnect(e.kind());
+                        break Err(format!("시리얼 포트 읽기 오류: {}", e));
+                    }
+                }
+            };
+            if let Some(version) = decoder.take_identity() {
+                *lock(&self.reported_protocol) = Some(version);
+            }
+            *pending = decoder.into_pending();
+            result
+        } else {
+            Err("시리얼 포트가 초기화되지 않았습니다.".into())
+        };
+        drop(port_lock);
+        // 끊긴 연결은 닫아서 연결 기록을 끝내고 재연결할 수 있게 함
+        if port_lost {
+            self.close(CloseReason::Disconnected);
+        }
+        result
+    }
+
+    // 구분자까지 원시 바이트를 읽는 함수 (디버그 텍스트용, 구분자 포함 반환)
+    // 패킷 디코더와 같은 수신 버퍼를 쓰므로 구분자 뒤 바이트는 다음 패킷 읽기에 그대로 남음
+    pub fn read_until(
+        &self,
+        delimiter: u8,
+        max_len: usize,
+        timeout: Duration,
+        token: u64,
+    ) -> Result<Vec<u8>, String> {
+        if !(1..=MAX_READ_BUFFER_SIZE).contains(&max_len) {
+            return Err(format!(
+                "최대 길이는 1에서 {} 사이여야 합니다.",
+                MAX_READ_BUFFER_SIZE
+            ));
+        }
+        if timeout > MAX_READ_UNTIL_TIMEOUT {
+            return Err(format!(
+                "대기 시간은 {}ms 이하여야 합니다.",
+                MAX_READ_UNTIL_TIMEOUT.as_millis()
+            ));
+        }
+        let mut port_lock = lock(&self.port);
+        let Some(ref mut port) = *port_lock else {
+            return Err("시리얼 포트가 초기화되지 않았습니다.".into());
+        };
+        let mut pending = lock(&self.pending);
+        let mut chunk = vec![0u8; self.read_buffer_size.load(Ordering::Relaxed)];
+        let started = Instant::now();
+        loop {
+            if let Some(position) = pending.iter().take(max_len).position(|&b| b == delimiter) {
+                return Ok(pending.drain(..=position).collect());
+            }
+            if pending.len() >= max_len {
+                return Err(format!(
+                    "구분자 없이 최대 길이({}바이트)를 넘었습니다.",
+                    max_len
+                ));
+            }
+            if self.is_motion_cancelled(token) {
+                return Err("구분자 읽기가 취소되었습니다.".into());
+            }
+            let Some(remaining) = timeout.checked_sub(started.elapsed()) else {
+                return Err("구분자를 기다리는 동안 타임아웃이 발생했습니다.".into());
+            };
+            let mut port = TimeoutGuard::new(port.as_mut(), Some(remaining.min(MOTION_WAIT_SLICE)))
+                .map_err(|e| format!("타임아웃 설정 오류: {}", e))?;
+            match port.read(&mut chunk) {
+                Ok(n) if n > 0 => {
+                    self.metrics
+                        .bytes_read
+                        .fetch_add(n as u64, Ordering::Relaxed);
+                    pending.extend_from_slice(&chunk[..n]);
+                }
+                Ok(_) => {}
+                Err(ref e) if e.kind() == ErrorKind::TimedOut => {}
+                Err(e) => return Err(format!("시리얼 포트 읽기 오류: {}", e)),
+            }
+        }
+    }
+
+    // 시리얼 포트 연결 여부 확인 함수
+    pub fn is_connected(&self) -> bool {
+        lock(&self.port).is_some()
+    }
+
+    // 포트 닫기 함수 (열린 연결이 있으면 종료 사유와 함께 기록)
+    pub fn close(&self, reason: CloseReason) {
+        let was_open = lock(&self.port).take().is_some();
+        *lock(&self.connection) = None;
+        lock(&self.pending).clear();
+        if was_open {
+            self.sessions.close(reason, self.frame_counts());
+        }
+    }
+
+    // 사용자 요청으로 연결 종료 함수
+    pub fn disconnect(&self) -> Result<(), String> {
+        if !self.is_connected() {
+            return Err("시리얼 포트가 초기화되지 않았습니다.".into());
+        }
+        self.close(CloseReason::Manual);
+        Ok(())
+    }
+
+    // 누적 (수신, 송신) 패킷 수
+    fn frame_counts(&self) -> (u64, u64) {
+        (
+            self.metrics.frames_received.load(Ordering::Relaxed),
+            self.metrics.frames_sent.load(Ordering::Relaxed),
+        )
+    }
+
+    // 연결 기록 저장 파일 지정 및 이전 기록 불러오기
+    pub fn load_session_history(&self, path: PathBuf) -> Result<(), String> {
+        self.sessions.load(path)
+    }
+
+    // 연결 기록 조회 함수 (오래된 순)
+    pub fn session_history(&self) -> Vec<SessionRecord> {
+        self.sessions.list()
+    }
+
+    // 연결 확인 함수 (제한 시간 안에 유효한 상태 패킷이 없으면 포트를 닫음)
+    pub fn verify_connection(&self, timeout: Duration) -> Result<(), String> {
+        let deadline = Instant::now() + timeout;
+        let mut last_error = None;
+        while Instant::now() < deadline {
+            match self.read_data() {
+                Ok(_) => return Ok(()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        self.close(CloseReason::Error);
+        // 반복 중 오류는 출력하지 않고 마지막 오류만 함께 보고
+        Err(format!(
+            "{}ms 안에 유효한 상태 패킷을 받지 못했습니다. 장치 연결과 전원을 확인해주세요. (마지막 오류: {})",
+            timeout.as_millis(),
+            last_error.unwrap_or_else(|| "없음".into())
+        ))
+    }
+
+    // 연속 프레임 수신 함수 (타임아웃 시 수집된 프레임까지 반환, 토큰이 취소되면 중단)
+    pub fn read_batch(
+        &self,
+        count: usize,
+        timeout: Duration,
+        token: u64,
+    ) -> Result<Vec<RobotState>, String> {
+        if !(1..=MAX_BATCH_FRAMES).contains(&count) {
+            return Err(format!(
+                "프레임 수는 1에서 {} 사이여야 합니다.",
+                MAX_BATCH_FRAMES
+            ));
+        }
+        if timeout > MAX_BATCH_TIMEOUT {
+            return Err(format!(
+                "대기 시간은 {}ms 이하여야 합니다.",
+                MAX_BATCH_TIMEOUT.as_millis()
+            ));
+        }
+        if !self.is_connected() {
+            return Err("시리얼 포트가 초기화되지 않았습니다.".into());
+        }
+
+        let deadline = Instant::now() + timeout;
+        let mut states = Vec::with_capacity(count);
+        while states.len() < count && Instant::now() < deadline {
+            if self.is_motion_cancelled(token) {
+                return Err("연속 읽기가 취소되었습니다.".into());
+            }
+            // 손상된 프레임은 건너뛰고 다음 헤드 바이트부터 다시 읽기
+            if let Ok(robot_state) = self.read_data() {
+                states.push(robot_state);
+            }
+        }
+        Ok(states)
+    }
+
+    // 상태 요약 조회 함수 (하드웨어 입출력 없음)
+    pub fn health(&self) -> HealthReport {
+        let last_state_at = *lock(&self.last_state_at);
+        let timestamp_offset = *lock(&self.timestamp_offset);
+        HealthReport {
+            connected: self.is_connected(),
+            connection: lock(&self.connection).clone(),
+            last_state_unix_ms: last_state_at.and_then(|at| {
+                at.duration_since(UNIX_EPOCH)
+                    .ok()
+                    .map(|d| d.as_millis() as u64)
+            }),
+            staleness_ms: last_state_at
+                .and_then(|at| at.elapsed().ok())
+                .map(|d| d.as_millis() as u64),
+            frame_len: frame_len(&self.decoder_config()),
+            timestamp_offset,
+            last_error: lock(&self.last_error).clone(),
+        }
+    }
+
+    // Prometheus 텍스트 형식 지표 생성 함수
+    #[cfg(feature = "metrics-server")]
+    pub fn prometheus_metrics(&self) -> String {
+        let counters = [
+            (
+                "frames_sent",
+                "전송한 명령 패킷 수",
+                &self.metrics.frames_sent,
+            ),
+            (
+                "frames_received",
+                "수신한 상태 패킷 수",
+                &self.metrics.frames_received,
+            ),
+            (
+                "malformed_packets",
+                "잘못된 패킷 수",
+                &self.metrics.malformed_frames,
+            ),
+            (
+                "controller_errors",
+                "컨트롤러 오류 패킷 수",
+                &self.metrics.controller_errors,
+            ),
+            (
+                "buffer_overruns",
+                "수신 버퍼 넘침 추정 횟수",
+                &self.metrics.buffer_overruns,
+            ),
+            ("bytes_read", "수신 바이트 수", &self.metrics.bytes_read),
+        ];
+        let mut text = String::new();
+        for (name, help, counter) in counters {
+            text.push_str(&format!(
+                "# HELP robot_arm_{name}_total {help}\n# TYPE robot_arm_{name}_total counter\nrobot_arm_{name}_total {}\n",
+                counter.load(Ordering::Relaxed)
+            ));
+        }
+
+        let health = self.health();
+        let latency_ms = self.metrics.last_read_latency_us.load(Ordering::Relaxed) as f64 / 1000.0;
+        let gauges = [
+            ("connected", "연결 여부", health.connected as u8 as f64),
+            ("last_latency_ms", "마지막 상태 수신 소요 시간", latency_ms),
+            (
+                "staleness_ms",
+                "마지막 상태 수신 이후 경과 시간",
+                health.staleness_ms.map_or(f64::NAN, |ms| ms as f64),
+            ),
+        ];
+        for (name, help, value) in gauges {
+            text.push_str(&format!(
+                "# HELP robot_arm_{name} {help}\n# TYPE robot_arm_{name} gauge\nrobot_arm_{name} {}\n",
+                value
+            ));
+        }
+        text
+    }
+
+    // 전원 전압이 기준 아래로 내려가는 순간 한 번 경고 (기준 이상으로 회복하면 다시 감시)
+    fn track_supply_voltage(&self, robot_state: &RobotState) {
+        let (Some(voltage), Some(threshold)) =
+            (robot_state.supply_voltage, *lock(&self.voltage_threshold))
+        else {
+            return;
+        };
+        let low = voltage < threshold;
+        if low && !self.voltage_low.swap(true, Ordering::SeqCst) {
+            *lock(&self.low_voltage_warning) = Some(voltage);
+        } else if !low {
+            self.voltage_low.store(false, Ordering::SeqCst);
+        }
+    }
+
+    // 아직 알리지 않은 저전압 경고 값 가져오기 (가져가면 초기화)
+    pub fn take_low_voltage_warning(&self) -> Option<u16> {
+        lock(&self.low_voltage_warning).take()
+    }
+
+    // 현재 연결의 보드레이트 조회 함수 (TCP 연결이나 미연결 시 None)
+    pub(crate) fn baud_rate(&self) -> Option<u32> {
+        lock(&self.connection)
+            .as_ref()
+            .and_then(|connection| connection.baud_rate)
+    }
+
+    // 바이트당 전송 비트 수 조회 함수 (시작 비트 + 데이터 비트 + 패리티 비트 + 정지 비트)
+    pub(crate) fn line_bits_per_byte(&self) -> u32 {
+        let mut port_lock = lock(&self.port);
+        port_lock
+            .as_mut()
+            .and_then(|port| port.as_serial())
+            .and_then(|serial| {
+                let data_bits = u8::from(serial.data_bits().ok()?) as u32;
+                let parity_bits = match serial.parity().ok()? {
+                    Parity::None => 0,
+                    _ => 1,
+                };
+                let stop_bits = u8::from(serial.stop_bits().ok()?) as u32;
+                Some(1 + data_bits + parity_bits + stop_bits)
+            })
+            .unwrap_or(DEFAULT_BITS_PER_BYTE)
+    }
+
+    // 현재 포트 설정 조회 함수 (가능하면 열린 포트에서 직접 읽음)
+    pub fn port_config(&self) -> Result<PortConfig, String> {
+        let connection = lock(&self.connection)
+            .clone()
+            .ok_or("시리얼 포트가 초기화되지 않았습니다.")?;
+        let mut port_lock = lock(&self.port);
+        let port = port_lock
+            .as_mut()
+            .ok_or("시리얼 포트가 초기화되지 않았습니다.")?;
+        let timeout_ms = port.timeout().as_millis() as u64;
+        let mut config = PortConfig {
+            port_name: connection.port_name,
+            baud_rate: connection.baud_rate,
+            timeout_ms,
+            parity: None,
+            data_bits: None,
+            stop_bits: None,
+            flow_control: None,
+        };
+        if let Some(serial) = port.as_serial() {
+            config.baud_rate = serial.baud_rate().ok().or(config.baud_rate);
+            config.parity = serial.parity().ok().map(|parity| {
+                match parity {
+                    Parity::None => "none",
+                    Parity::Odd => "odd",
+                    Parity::Even => "even",
+                }
+                .to_string()
+            });
+            config.data_bits = serial.data_bits().ok().map(u8::from);
+            config.stop_bits = serial.stop_bits().ok().map(u8::from);
+            config.flow_control = serial.flow_control().ok().map(|flow_control| {
+                match flow_control {
+                    FlowControl::None => "none",
+                    FlowControl::Software => "software",
+                    FlowControl::Hardware => "hardware",
+                }
+                .to_string()
+            });
+        }
+        Ok(config)
+    }
+
+    // 명령 패킷 최대 전송률 계산 함수 (처리량 측정과 같은 수신 패킷 길이 기준)
+    pub fn max_command_rate(&self) -> Result<f64, String> {
+        if !self.is_connected() {
+            return Err("시리얼 포트가 초기화되지 않았습니다.".into());
+        }
+        let baud_rate = self
+            .baud_rate()
+            .ok_or("TCP 연결은 보드레이트로 전송률을 계산할 수 없습니다.")?;
+        Ok(max_frame_rate(
+            baud_rate,
+            self.line_bits_per_byte(),
+            frame_len(&self.decoder_config()),
+        ))
+    }
+
+    // 모뎀 상태 신호선 읽기 함수
+    pub fn read_signal_lines(&self) -> Result<SignalLines, String> {
+        let mut port_lock = lock(&self.port);
+        let port = port_lock
+            .as_mut()
+            .ok_or("시리얼 포트가 초기화되지 않았습니다.")?;
+        let serial = port
+            .as_serial()
+            .ok_or("현재 연결은 시리얼 포트가 아니어서 신호선을 읽을 수 없습니다.")?;
+
+        let read_error = |e: serialport::Error| format!("신호선 읽기 오류: {}", e);
+        Ok(SignalLines {
+            clear_to_send: serial.read_clear_to_send().map_err(read_error)?,
+            data_set_ready: serial.read_data_set_ready().map_err(read_error)?,
+            ring_indicator: serial.read_ring_indicator().map_err(read_error)?,
+            carrier_detect: serial.read_carrier_detect().map_err(read_error)?,
+        })
+    }
+
+    // 시리얼 포트 목록 가져오기 함수
+    pub fn list_ports() -> Result<Vec<serialport::SerialPortInfo>, serialport::Error> {
+        serialport::available_ports()
+    }
+}
+
+// USB 장치 식별자 (VID, PID)
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct UsbId {
+    pub vid: u16,
+    pub pid: u16,
+}
+
+// 자동 연결 대상 포트 선택 함수 (후보가 정확히 하나일 때만 선택)
+pub fn select_auto_port(
+    ports: &[serialport::SerialPortInfo],
+    allowlist: &[UsbId],
+) -> Result<String, String> {
+    let candidates: Vec<&str> = ports
+        .iter()
+        .filter(|port| {
+            allowlist.is_empty()
+                || matches!(&port.port_type, serialport::SerialPortType::UsbPort(usb)
+                    if allowlist.contains(&UsbId { vid: usb.vid, pid: usb.pid }))
+        })
+        .map(|port| port.port_name.as_str())
+        .collect();
+
+    match candidates.as_slice() {
+        [port_name] => Ok(port_name.to_string()),
+        [] => Err("자동 연결할 시리얼 포트가 없습니다.".into()),
+        _ => Err(format!(
+            "자동 연결할 시리얼 포트가 여러 개입니다: {}",
+            candidates.join(", ")
+        )),
+    }
+}
+
+// 현재 포트 설정 구조체 정의 (시리얼 전용 항목은 TCP 연결 시 None)
+#[derive(Serialize)]
+pub struct PortConfig {
+    pub port_name: String,
+    pub baud_rate: Option<u32>,
+    pub timeout_ms: u64,
+    pub parity: Option<String>,
+    pub data_bits: Option<u8>,
+    pub stop_bits: Option<u8>,
+    pub flow_control: Option<String>,
+}
+
+// 명령 전송 결과 구조체 정의
+#[derive(Serialize)]
+pub struct CommandReport {
+    pub requested_speed: u8,
+    pub effective_speed: u8,
+    // 관절 제한으로 값이 조정된 관절 번호
+    pub clamped_joints: Vec<u8>,
+}
+
+// 상태 요약 구조체 정의
+#[derive(Serialize)]
+pub struct HealthReport {
+    pub connected: bool,
+    pub connection: Option<ConnectionInfo>,
+    pub last_state_unix_ms: Option<u64>,
+    pub staleness_ms: Option<u64>,
+    pub frame_len: usize,
+    pub timestamp_offset: Option<usize>,
+    pub last_error: Option<String>,
+}
+
+// 바이트열 해석 결과 구조체 정의
+#[derive(Serialize)]
+pub struct DecodeReport {
+    pub states: Vec<RobotState>,
+    pub errors: Vec<String>,
+    // 패킷이 되지 못하고 남은 바이트 수
+    pub remaining_bytes: usize,
+}
+
+// 빌드 기능 지원 여부 구조체 정의
+#[derive(Serialize, PartialEq, Debug)]
+pub struct Capabilities {
+    pub version: String,
+    pub os: String,
+    pub tcp: bool,
+    pub metrics_server: bool,
+    // 유닉스 도메인 소켓 기반이라 유닉스 계열에서만 지원
+    pub pipe_stream: bool,
+}
+
+// 현재 빌드의 기능 지원 여부 조회 함수
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        tcp: cfg!(feature = "tcp"),
+        metrics_server: cfg!(feature = "metrics-server"),
+        pipe_stream: cfg!(unix),
+    }
+}
+
+// AppState 구조체 정의
+#[derive(Clone)]
+pub struct AppState {
+    pub serial_manager: Arc<SerialPortManager>,
+    // 실행 중인 지표 HTTP 서버
+    #[cfg(feature = "metrics-server")]
+    pub metrics_server: Arc<Mutex<Option<MetricsServer>>>,
+    // 실행 중인 백그라운드 작업 목록
+    pub tasks: Arc<TaskRegistry>,
+}
+
+// 연결 종료 커맨드
+#[tauri::command]
+pub fn disconnect_port(state: State<'_, AppState>) -> Result<(), String> {
+    state.serial_manager.disconnect()
+}
+
+// 연결 기록 조회 커맨드
+#[tauri::command]
+pub fn get_session_history(state: State<'_, AppState>) -> Result<Vec<SessionRecord>, String> {
+    Ok(state.serial_manager.session_history())
+}
+
+// 시리얼 포트 목록 커맨드
+#[tauri::command]
+pub fn list_serial_ports() -> Result<Vec<String>, String> {
+    match SerialPortManager::list_ports() {
+        Ok(ports) => {
+            let port_names = ports
+                .into_iter()
+                .map(|port| port.port_name)
+                .collect::<Vec<String>>();
+            Ok(port_names)
+        }
+        Err(e) => Err(format!("시리얼 포트 목록 가져오기 실패: {}", e)),
+    }
+}
+
+// 시리얼 포트 초기화 커맨드 (초기 자세 대기는 백그라운드 작업으로 실행, cancel_motion으로 중지)
+#[tauri::command]
+pub async fn initialize_serial(
+    state: State<'_, AppState>,
+    port: String,
+    baud_rate: u32,
+    verify_on_connect: Option<bool>,
+) -> Result<String, String> {
+    run_motion_task(
+        &state,
+        "시리얼 연결 후 초기 자세 전송",
+        move |manager, token| {
+            if let Err(e) = manager.initialize(&port, baud_rate) {
+                return Err(format!("시리얼 포트 열기 실패: {}", e));
+            }
+            if verify_on_connect.unwrap_or(false) {
+                manager.verify_connection(VERIFY_TIMEOUT)?;
+            }
+            send_init_pose(
+                manager,
+                "시리얼 포트가 성공적으로 초기화되었습니다.".into(),
+                token,
+            )
+        },
+    )
+    .await
+}
+
+// 단일 포트 자동 연결 커맨드 (usb_ids 지정 시 해당 VID/PID만 후보)
+#[tauri::command]
+pub async fn auto_connect(
+    state: State<'_, AppState>,
+    baud_rate: u32,
+    usb_ids: Option<Vec<UsbId>>,
+) -> Result<String, String> {
+    let ports = SerialPortManager::list_ports()
+        .map_err(|e| format!("시리얼 포트 목록 가져오기 실패: {}", e))?;
+    let port = select_auto_port(&ports, usb_ids.as_deref().unwrap_or_default())?;
+
+    run_motion_task(
+        &state,
+        "자동 연결 후 초기 자세 전송",
+        move |manager, token| {
+            if let Err(e) = manager.initialize(&port, baud_rate) {
+                return Err(format!("시리얼 포트 열기 실패: {}", e));
+            }
+            // 자동 연결은 포트 이름을 반환하므로 건너뛴 사유는 last_error로 전달
+            manager.send_init_pose(token)?;
+            Ok(port)
+        },
+    )
+    .await
+}
+
+// TCP 연결 초기화 커맨드
+#[cfg(feature = "tcp")]
+#[tauri::command]
+pub async fn initialize_tcp(state: State<'_, AppState>, addr: String) -> Result<String, String> {
+    run_motion_task(
+        &state,
+        "TCP 연결 후 초기 자세 전송",
+        move |manager, token| {
+            manager
+                .initialize_tcp(&addr)
+                .map_err(|e| format!("TCP 연결 실패: {}", e))?;
+            send_init_pose(manager, format!("{}에 TCP로 연결되었습니다.", addr), token)
+        },
+    )
+    .await
+}
+
+#[cfg(not(feature = "tcp"))]
+#[tauri::command]
+pub async fn initialize_tcp(_addr: String) -> Result<String, String> {
+    Err("이 빌드에서는 TCP 연결을 지원하지 않습니다.".into())
+}
+
+// 초기 자세 전송 후 연결 결과 메시지 작성 함수 (전송을 건너뛰면 사유를 덧붙임)
+fn send_init_pose(
+    manager: &SerialPortManager,
+    message: String,
+    token: u64,
+) -> Result<String, String> {
+    Ok(match manager.send_init_pose(token)? {
+        InitPoseOutcome::Skipped(reason) => format!("{} ({})", message, reason),
+        InitPoseOutcome::Disabled | InitPoseOutcome::Sent => message,
+    })
+}
+
+// 입력 동작 처리 스레드 시작 함수 (큐에 들어온 입력 동작을 차례로 실행)
+// 스레드는 관리자를 약하게 참조하므로 관리자가 해제되면 큐가 닫히며 종료
+pub fn spawn_input_worker(state: &AppState) {
+    let (sender, receiver) = mpsc::channel();
+    *lock(&state.serial_manager.input_actions) = Some(sender);
+    let manager = Arc::downgrade(&state.serial_manager);
+    let tasks = state.tasks.clone();
+    thread::spawn(move || {
+        for (input_index, action) in receiver {
+            let Some(manager) = manager.upgrade() else {
+                break;
+            };
+            if let Err(e) = run_input_action(&manager, &tasks, action) {
+                println!("Input {} binding failed: {}", input_index, e);
+                *lock(&manager.last_error) = Some(e);
+            }
+        }
+    });
+}
+
+// 입력 동작 하나 실행 함수 (자세 이동은 새 동작 토큰으로 나눠 이동하고 작업 목록에 등록)
+fn run_input_action(
+    manager: &Arc<SerialPortManager>,
+    tasks: &Arc<TaskRegistry>,
+    action: InputAction,
+) -> Result<(), String> {
+    match action {
+        InputAction::CancelMotion => {
+            manager.cancel_motion();
+            Ok(())
+        }
+        InputAction::ToggleOutput(output) => {
+            let mut command = manager
+                .last_command()
+                .ok_or("출력 반전 전에 로봇 명령을 먼저 전송해주세요.")?;
+            let on = !digital_output(&command, output);
+            set_digital_output(&mut command, output, on);
+            manager.send_command(&command).map(|_| ())
+        }
+        InputAction::GotoPose(name) => {
+            let token = manager.motion_token();
+            let _task = {
+                let manager = manager.clone();
+                tasks.register(format!("입력 자세 이동: {}", name), move || {
+                    manager.cancel_motion()
+                })
+            };
+            manager.move_to_pose(&name, token).map(|_| ())
+        }
+    }
+}
+
+// 프론트엔드 창 종료 처리 함수 (설정에 따라 작업 취소 및 초기 자세로 이동)
+pub fn handle_frontend_loss(state: &AppState) {
+    let manager = &state.serial_manager;
+    let behavior = manager.frontend_loss_behavior();
+    if behavior == FrontendLossBehavior::Continue {
+        return;
+    }
+
+    println!("Frontend lost, cancelling background tasks");
+    state.tasks.cancel_all();
+    manager.cancel_motion();
+
+    if behavior == FrontendLossBehavior::Park {
+        let Some(init_pose) = manager.init_pose() else {
+            println!("No init pose configured, skipping park");
+            return;
+        };
+        if let Err(e) = manager.send_command(&init_pose.pose) {
+            println!("Park on frontend loss failed: {}", e);
+        }
+    }
+}
+
+// 초기 자세 설정 커맨드
+#[tauri::command]
+pub fn set_init_pose(
+    state: State<'_, AppState>,
+    pose: RobotState,
+    enabled: bool,
+    delay_ms: Option<u64>,
+    require_state: Option<bool>,
+) -> Result<(), String> {
+    state.serial_manager.set_init_pose(InitPose {
+        pose,
+        enabled,
+        delay_ms: delay_ms.unwrap_or(0),
+        require_state: require_state.unwrap_or(false),
+    })
+}
+
+// 연결 시 홈 이동 설정 커맨드 (delay_ms: 연결 후 홈 자세 전송까지 대기 시간)
+#[tauri::command]
+pub fn set_home_on_connect(
+    state: State<'_, AppState>,
+    enabled: bool,
+    delay_ms: u64,
+) -> Result<(), String> {
+    state
+        .serial_manager
+        .set_home_on_connect(enabled, Duration::from_millis(delay_ms))
+}
+
+// 타임스탬프 위치 설정 커맨드
+#[tauri::command]
+pub fn set_device_timestamp(
+    state: State<'_, AppState>,
+    offset: Option<usize>,
+) -> Result<(), String> {
+    state.serial_manager.set_timestamp_offset(offset)
+}
+
+// 로봇 명령 전송 커맨드
+#[tauri::command]
+pub fn send_robot_commands(
+    state: State<'_, AppState>,
+    robot_state: RobotState,
+) -> Result<CommandReport, String> {
+    state.serial_manager.send_command(&robot_state)
+}
+
+// 동작 작업 등록 함수 (동작 작업 취소는 진행 중인 모든 동작을 함께 취소)
+pub(crate) fn register_motion_task(state: &State<'_, AppState>, description: &str) -> TaskGuard {
+    let manager = state.serial_manager.clone();
+    state
+        .tasks
+        .register(description, move || manager.cancel_motion())
+}
+
+// 오래 걸리는 커맨드를 작업 목록에 등록하고 메인 스레드 밖에서 실행하는 함수
+// 작업에는 시작 시점의 동작 토큰을 넘기므로 cancel_task, cancel_motion으로 중지 가능
+pub(crate) async fn run_motion_task<T, F>(
+    state: &State<'_, AppState>,
+    description: &str,
+    work: F,
+) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&SerialPortManager, u64) -> Result<T, String> + Send + 'static,
+{
+    let manager = state.serial_manager.clone();
+    let token = manager.motion_token();
+    let task = register_motion_task(state, description);
+    tauri::async_runtime::spawn_blocking(move || {
+        let _task = task;
+        work(&manager, token)
+    })
+    .await
+    .map_err(|e| format!("백그라운드 작업 실행 실패: {}", e))?
+}
+
+// 실행 중인 백그라운드 작업 목록 커맨드
+#[tauri::command]
+pub fn list_active_tasks(state: State<'_, AppState>) -> Result<Vec<TaskInfo>, String> {
+    Ok(state.tasks.list())
+}
+
+// 백그라운드 작업 취소 커맨드
+#[tauri::command]
+pub fn cancel_task(state: State<'_, AppState>, id: u64) -> Result<(), String> {
+    state.tasks.cancel(id)
+}
+
+// 모든 백그라운드 작업 취소 커맨드
+#[tauri::command]
+pub fn cancel_all_tasks(state: State<'_, AppState>) -> Result<(), String> {
+    state.tasks.cancel_all();
+    Ok(())
+}
+
+// 동일 패킷 반복 감지 설정 커맨드 (threshold 0이면 해제)
+#[tauri::command]
+pub fn set_stuck_detection(state: State<'_, AppState>, threshold: u32) -> Result<(), String> {
+    state.serial_manager.set_stuck_threshold(threshold);
+    Ok(())
+}
+
+// 구분자까지 원시 바이트 읽기 커맨드 (텍스트 디버그 출력 확인용, 백그라운드 작업으로 실행)
+#[tauri::command]
+pub async fn read_until(
+    state: State<'_, AppState>,
+    delimiter: u8,
+    max_len: usize,
+    timeout_ms: u64,
+) -> Result<Vec<u8>, String> {
+    run_motion_task(&state, "구분자까지 읽기", move |manager, token| {
+        manager.read_until(delimiter, max_len, Duration::from_millis(timeout_ms), token)
+    })
+    .await
+}
+
+// 마지막 원시 패킷 조회 커맨드
+#[tauri::command]
+pub fn get_last_raw_frame(state: State<'_, AppState>) -> Result<LastRawFrame, String> {
+    state
+        .serial_manager
+        .last_raw_frame()
+        .ok_or_else(|| "아직 수신한 상태 패킷이 없습니다.".into())
+}
+
+// 프론트엔드 창이 닫혔을 때 동작 설정 커맨드
+#[tauri::command]
+pub fn set_frontend_loss_behavior(
+    state: State<'_, AppState>,
+    mode: FrontendLossBehavior,
+) -> Result<(), String> {
+    state.serial_manager.set_frontend_loss_behavior(mode);
+    Ok(())
+}
+
+// 관절 제한 설정 커맨드
+#[tauri::command]
+pub fn set_joint_limits(
+    state: State<'_, AppState>,
+    joint: u8,
+    min: u8,
+    max: u8,
+) -> Result<(), String> {
+    state.serial_manager.set_joint_limit(joint, min, max)
+}
+
+// 관절 제한 일괄 설정 커맨드 (관절 1~6 순서의 (최솟값, 최댓값))
+#[tauri::command]
+pub fn set_all_joint_limits(
+    state: State<'_, AppState>,
+    limits: [(u8, u8); 6],
+) -> Result<(), String> {
+    state.serial_manager.set_all_joint_limits(limits)
+}
+
+// 두 측정점 기반 관절 보정 커맨드
+#[tauri::command]
+pub fn calibrate_joint(
+    state: State<'_, AppState>,
+    joint: u8,
+    point_a_deg: f64,
+    point_a_raw: u8,
+    point_b_deg: f64,
+    point_b_raw: u8,
+) -> Result<JointCalibration, String> {
+    let calibration = JointCalibration::fit(point_a_deg, point_a_raw, point_b_deg, point_b_raw)?;
+    state.serial_manager.set_calibration(joint, calibration)?;
+    Ok(calibration)
+}
+
+// 녹화 프레임을 다른 팔의 관절 대응으로 변환하는 커맨드
+#[tauri::command]
+pub fn adapt_recording(
+    frames: Vec<RobotState>,
+    source_mapping: [Option<JointMapping>; 6],
+    target_mapping: [Option<JointMapping>; 6],
+    missing_joints: MissingJointPolicy,
+) -> Result<Vec<RobotState>, String> {
+    adapt_frames(&frames, &source_mapping, &target_mapping, missing_joints)
+        .map_err(|e| format!("녹화 변환 실패: {}", e))
+}
+
+// 관절 응답 곡선 설정 커맨드
+#[tauri::command]
+pub fn set_joint_response_curve(
+    state: State<'_, AppState>,
+    joint: u8,
+    curve: ResponseCurve,
+) -> Result<(), String> {
+    state.serial_manager.set_joint_response_curve(joint, curve)
+}
+
+// 정규화 슬라이더 값 전송 커맨드 (values: 관절 1~6, 0.0~1.0)
+#[tauri::command]
+pub fn send_normalized(
+    state: State<'_, AppState>,
+    values: [f32; 6],
+    speed: u8,
+) -> Result<CommandReport, String> {
+    state.serial_manager.send_normalized(values, speed)
+}
+
+// 관절 표시 단위 설정 커맨드
+#[tauri::command]
+pub fn set_joint_unit(
+    state: State<'_, AppState>,
+    joint: u8,
+    label: String,
+    scale: f64,
+) -> Result<(), String> {
+    state
+        .serial_manager
+        .set_joint_unit(joint, JointUnit { label, scale })
+}
+
+// 관절 정보 조회 커맨드
+#[tauri::command]
+pub fn get_joint_info(state: State<'_, AppState>) -> Result<Vec<JointInfo>, String> {
+    Ok(state.serial_manager.joint_info())
+}
+
+// 관절별 단위 값 전송 커맨드 (values: 관절 1~6, 각 관절의 표시 단위)
+#[tauri::command]
+pub fn send_in_units(
+    state: State<'_, AppState>,
+    values: [f64; 6],
+    units_check: [String; 6],
+    speed: u8,
+) -> Result<CommandReport, String> {
+    state
+        .serial_manager
+        .send_in_units(values, &units_check, speed)
+}
+
+// 관절 보정값 조회 커맨드
+#[tauri::command]
+pub fn get_calibration(
+    state: State<'_, AppState>,
+) -> Result<[Option<JointCalibration>; 6], String> {
+    Ok(state.serial_manager.calibration())
+}
+
+// 디지털 출력 최소 유지 시간 설정 커맨드
+#[tauri::command]
+pub fn set_output_dwell(state: State<'_, AppState>, index: u8, ms: u64) -> Result<(), String> {
+    state
+        .serial_manager
+        .set_output_dwell(index, Duration::from_millis(ms))
+}
+
+// 관절 제한 조회 커맨드
+#[tauri::command]
+pub fn get_joint_limits(state: State<'_, AppState>) -> Result<Vec<JointRange>, String> {
+    Ok(state.serial_manager.joint_limits())
+}
+
+// 자세의 관절 제한 확인 커맨드 (전송하지 않음)
+#[tauri::command]
+pub fn is_pose_within_limits(
+    state: State<'_, AppState>,
     robot_state: RobotState,
+) -> Result<LimitCheck, String> {
+    Ok(state.serial_manager.check_limits(&robot_state))
+}
+
+// 관절 공간 금지 영역 추가 커맨드
+#[tauri::command]
+pub fn add_keepout_region(
+    state: State<'_, AppState>,
+    name: String,
+    constraints: Vec<JointRange>,
+) -> Result<(), String> {
+    state
+        .serial_manager
+        .add_keepout_region(KeepoutRegion { name, constraints })
+}
+
+// 관절 공간 금지 영역 삭제 커맨드
+#[tauri::command]
+pub fn remove_keepout_region(state: State<'_, AppState>, name: String) -> Result<(), String> {
+    state.serial_manager.remove_keepout_region(&name)
+}
+
+// 관절 공간 금지 영역 목록 커맨드
+#[tauri::command]
+pub fn list_keepout_regions(state: State<'_, AppState>) -> Result<Vec<KeepoutRegion>, String> {
+    Ok(state.serial_manager.keepout_regions())
+}
+
+// 런타임 상태 스냅샷 커맨드
+#[tauri::command]
+pub fn snapshot_state(state: State<'_, AppState>) -> Result<RuntimeSnapshot, String> {
+    Ok(state.serial_manager.snapshot())
+}
+
+// 런타임 상태 복원 커맨드 (recommand 지정 시 마지막 명령 다시 전송)
+#[tauri::command]
+pub fn restore_state(
+    state: State<'_, AppState>,
+    snapshot: RuntimeSnapshot,
+    recommand: Option<bool>,
+) -> Result<Option<CommandReport>, String> {
+    state.serial_manager.restore(&snapshot)?;
+    match snapshot.last_command {
+        Some(last_command) if recommand.unwrap_or(false) => {
+            state.serial_manager.send_command(&last_command).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+// 입력 바인딩 설정 커맨드 (action 생략 시 해제)
+#[tauri::command]
+pub fn set_input_binding(
+    state: State<'_, AppState>,
+    input_index: u8,
+    action: Option<InputAction>,
+) -> Result<(), String> {
+    state.serial_manager.set_input_binding(input_index, action)
+}
+
+// 입력→출력 미러링 설정 커맨드 (mask 0이면 해제)
+#[tauri::command]
+pub fn set_io_mirror(state: State<'_, AppState>, mask: u8) -> Result<(), String> {
+    state.serial_manager.set_io_mirror(mask)
+}
+
+// Prometheus 지표 HTTP 서버 시작 커맨드 (대기 중인 포트 반환)
+#[cfg(feature = "metrics-server")]
+#[tauri::command]
+pub fn start_metrics_server(state: State<'_, AppState>, port: u16) -> Result<u16, String> {
+    let mut metrics_server = lock(&state.metrics_server);
+    // 같은 포트로 다시 시작할 수 있도록 기존 서버를 먼저 정리
+    *metrics_server = None;
+    let manager = state.serial_manager.clone();
+    let server = MetricsServer::start(port, move || manager.prometheus_metrics())?;
+    let port = server.port();
+    *metrics_server = Some(server);
+    Ok(port)
+}
+
+// Prometheus 지표 HTTP 서버 중지 커맨드
+#[cfg(feature = "metrics-server")]
+#[tauri::command]
+pub fn stop_metrics_server(state: State<'_, AppState>) -> Result<(), String> {
+    lock(&state.metrics_server)
+        .take()
+        .map(|_| ())
+        .ok_or_else(|| "실행 중인 지표 서버가 없습니다.".into())
+}
+
+#[cfg(not(feature = "metrics-server"))]
+#[tauri::command]
+pub fn start_metrics_server(_port: u16) -> Result<u16, String> {
+    Err("이 빌드에서는 지표 서버를 지원하지 않습니다.".into())
+}
+
+#[cfg(not(feature = "metrics-server"))]
+#[tauri::command]
+pub fn stop_metrics_server() -> Result<(), String> {
+    Err("이 빌드에서는 지표 서버를 지원하지 않습니다.".into())
+}
+
+// 수신 버퍼 넘침 추정 횟수 조회 커맨드
+#[tauri::command]
+pub fn get_buffer_overrun_count(state: State<'_, AppState>) -> Result<u64, String> {
+    Ok(state.serial_manager.buffer_overrun_count())
+}
+
+// 로봇 상태 읽기 커맨드
+#[tauri::command]
+pub fn read_robot_state(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    timeout_ms: Option<u64>,
+) -> Result<RobotState, String> {
+    let result = match state
+        .serial_manager
+        .read_data_timeout(timeout_ms.map(Duration::from_millis))
+    {
+        Ok(robot_state) => Ok(robot_state),
+        Err(e) => Err(format!("로봇 상태 읽기 실패: {}", e)),
+    };
+    emit_stuck_warning(&app, &state.serial_manager);
+    result
+}
+
+// raw 값과 보정 각도를 함께 읽는 커맨드
+#[tauri::command]
+pub fn read_robot_state_full(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    timeout_ms: Option<u64>,
+) -> Result<FullRobotState, String> {
+    let result = state
+        .serial_manager
+        .read_full_state(timeout_ms.map(Duration::from_millis))
+        .map_err(|e| format!("로봇 상태 읽기 실패: {}", e));
+    emit_stuck_warning(&app, &state.serial_manager);
+    result
+}
+
+// 동일 패킷 반복 경고 이벤트 전송 함수
+pub(crate) fn emit_stuck_warning(app: &AppHandle, manager: &SerialPortManager) {
+    if let Some(repeats) = manager.take_stuck_warning() {
+        let _ = app.emit("stuck_state", repeats);
+    }
+    if let Some(centivolts) = manager.take_low_voltage_warning() {
+        let _ = app.emit("low_voltage", centivolts);
+    }
+    if let Some(overrun) = manager.take_buffer_overrun_warning() {
+        let _ = app.emit("buffer_overrun", overrun);
+    }
+}
+
+// 로봇 상태 연속 읽기 커맨드 (백그라운드 작업으로 실행, cancel_task로 중지)
+#[tauri::command]
+pub async fn read_robot_states(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    n: usize,
+    timeout_ms: u64,
+) -> Result<Vec<RobotState>, String> {
+    let result = run_motion_task(
+        &state,
+        "로봇 상태 연속 읽기",
+        move |manager, token| manager.read_batch(n, Duration::from_millis(timeout_ms), token),
+    )
+    .await
+    .map_err(|e| format!("로봇 상태 읽기 실패: {}", e));
+    emit_stuck_warning(&app, &state.serial_manager);
+    result
+}
+
+// 상태 요약 조회 커맨드
+#[tauri::command]
+pub fn get_health(state: State<'_, AppState>) -> Result<HealthReport, String> {
+    Ok(state.serial_manager.health())
+}
+
+// 빌드 기능 지원 여부 조회 커맨드
+#[tauri::command]
+pub fn get_capabilities() -> Result<Capabilities, String> {
+    Ok(capabilities())
+}
+
+// 현재 패킷 설정으로 바이트열 해석 커맨드 (포트와 무관, 수신 로그 확인용)
+#[tauri::command]
+pub fn decode_frames(state: State<'_, AppState>, bytes: Vec<u8>) -> Result<DecodeReport, String> {
+    let mut decoder = FrameDecoder::new(state.serial_manager.decoder_config())?;
+    let mut report = DecodeReport {
+        states: Vec::new(),
+        errors: Vec::new(),
+        remaining_bytes: 0,
+    };
+    let mut record = |result: Result<RobotState, DecodeError>| match result {
+        Ok(robot_state) => report.states.push(robot_state),
+        Err(e) => report.errors.push(e.to_string()),
+    };
+    for byte in bytes {
+        if let Some(result) = decoder.push_byte(byte) {
+            record(result);
+            while let Some(result) = decoder.next_frame() {
+                record(result);
+            }
+        }
+    }
+    report.remaining_bytes = decoder.pending_len();
+    Ok(report)
+}
+
+// 원시 캡처 파일 분석 커맨드 (포트와 무관, 현재 패킷 설정 사용)
+#[tauri::command]
+pub fn analyze_capture(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<CaptureAnalysis, String> {
+    state
+        .serial_manager
+        .analyze_capture(&path)
+        .map_err(|e| format!("캡처 분석 실패: {}", e))
+}
+
+// 컨트롤러 오류 패킷 설정 커맨드
+#[tauri::command]
+pub fn set_error_frames(
+    state: State<'_, AppState>,
+    opcode: Option<u8>,
+    messages: HashMap<u16, String>,
+) -> Result<(), String> {
+    state.serial_manager.set_error_frames(opcode, messages)
+}
+
+// 변경 필드 명령 설정 커맨드 (opcode 생략 시 항상 전체 패킷 전송)
+#[tauri::command]
+pub fn set_delta_commands(state: State<'_, AppState>, opcode: Option<u8>) -> Result<(), String> {
+    state.serial_manager.set_delta_commands(opcode)
+}
+
+// 수신 버퍼 크기 설정 커맨드
+#[tauri::command]
+pub fn set_read_buffer_size(state: State<'_, AppState>, bytes: usize) -> Result<(), String> {
+    state.serial_manager.set_read_buffer_size(bytes)
+}
+
+// 모뎀 상태 신호선 읽기 커맨드
+#[tauri::command]
+pub fn read_signal_lines(state: State<'_, AppState>) -> Result<SignalLines, String> {
+    state
+        .serial_manager
+        .read_signal_lines()
+        .map_err(|e| format!("신호선 읽기 실패: {}", e))
+}
+
+// 관절 한계 접근 감속 설정 커맨드 (한계까지 margin 안쪽이면 min_speed까지 비례 감속)
+#[tauri::command]
+pub fn set_limit_approach_margin(
+    state: State<'_, AppState>,
+    margin: u8,
+    min_speed: u8,
+) -> Result<(), String> {
+    state
+        .serial_manager
+        .set_limit_approach(ApproachSlowdown { margin, min_speed })
+}
+
+// 전체 속도 배율 설정 커맨드
+#[tauri::command]
+pub fn set_global_speed_scale(state: State<'_, AppState>, factor: f32) -> Result<(), String> {
+    state.serial_manager.set_speed_scale(factor)
+}
+
+// 명령 최대 전송률 조회 커맨드
+#[tauri::command]
+pub fn max_command_rate(state: State<'_, AppState>) -> Result<f64, String> {
+    state.serial_manager.max_command_rate()
+}
+
+// 패킷 사이 여분 바이트 처리 방식 설정 커맨드
+#[tauri::command]
+pub fn set_frame_trailing_mode(
+    state: State<'_, AppState>,
+    mode: FrameTrailingMode,
+) -> Result<(), String> {
+    state.serial_manager.set_trailing_mode(mode);
+    Ok(())
+}
+
+// 전원 전압 위치 설정 커맨드 (offset 생략 시 전압 없음)
+#[tauri::command]
+pub fn set_voltage_offset(state: State<'_, AppState>, offset: Option<usize>) -> Result<(), String> {
+    state.serial_manager.set_voltage_offset(offset)
+}
+
+// 고장 래치 비트 위치 설정 커맨드 (offset 생략 시 고장 상태 없음)
+#[tauri::command]
+pub fn set_fault_offset(state: State<'_, AppState>, offset: Option<usize>) -> Result<(), String> {
+    state.serial_manager.set_fault_offset(offset)
+}
+
+// 저전압 경고 기준 설정 커맨드 (centivolts 생략 시 감시 안 함)
+#[tauri::command]
+pub fn set_voltage_threshold(
+    state: State<'_, AppState>,
+    centivolts: Option<u16>,
+) -> Result<(), String> {
+    state.serial_manager.set_voltage_threshold(centivolts);
+    Ok(())
+}
+
+// 수신 체크섬 위치 설정 커맨드 (offset 생략 시 체크섬 없음)
+#[tauri::command]
+pub fn set_checksum_offset(
+    state: State<'_, AppState>,
+    offset: Option<usize>,
+) -> Result<(), String> {
+    state.serial_manager.set_checksum_offset(offset)
+}
+
+// 디지털 입출력 배치 방식 설정 커맨드
+#[tauri::command]
+pub fn set_io_layout(state: State<'_, AppState>, io_layout: IoLayout) -> Result<(), String> {
+    state.serial_manager.set_io_layout(io_layout)
+}
+
+// 패킷 인코딩 방식 설정 커맨드
+#[tauri::command]
+pub fn set_frame_encoding(
+    state: State<'_, AppState>,
+    encoding: FrameEncoding,
+) -> Result<(), String> {
+    state.serial_manager.set_encoding(encoding);
+    Ok(())
+}
+
+// 현재 자세 저장 커맨드 (로봇에서 읽은 상태 기준)
+#[tauri::command]
+pub fn save_pose(state: State<'_, AppState>, name: String) -> Result<RobotState, String> {
+    let pose = state.serial_manager.read_data()?;
+    state.serial_manager.save_pose(&name, pose.clone())?;
+    Ok(pose)
+}
+
+// 저장된 자세 목록 커맨드
+#[tauri::command]
+pub fn list_poses(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    Ok(state.serial_manager.pose_names())
+}
+
+// 저장된 자세 삭제 커맨드
+#[tauri::command]
+pub fn delete_pose(state: State<'_, AppState>, name: String) -> Result<(), String> {
+    state.serial_manager.delete_pose(&name)
+}
+
+// 자세 프리셋 내보내기 커맨드 (pose 생략 시 로봇에서 읽은 현재 자세)
+#[tauri::command]
+pub fn export_pose_preset(
+    state: State<'_, AppState>,
+    name: String,
+    path: String,
+    pose: Option<RobotState>,
+    note: Option<String>,
+) -> Result<PosePreset, String> {
+    let pose = match pose {
+        Some(pose) => pose,
+        None => state.serial_manager.read_data()?,
+    };
+    state
+        .serial_manager
+        .export_pose_preset(&name, pose, note, &path)
+}
+
+// 자세 프리셋 가져오기 커맨드 (저장 자세 목록에 추가)
+#[tauri::command]
+pub fn import_pose_preset(state: State<'_, AppState>, path: String) -> Result<PosePreset, String> {
+    state.serial_manager.import_pose_preset(&path)
+}
+
+// 사용자 정의 명령 등록 커맨드
+#[tauri::command]
+pub fn register_command(
+    state: State<'_, AppState>,
+    name: String,
+    opcode: u8,
+    payload_schema: Vec<PayloadField>,
 ) -> Result<(), String> {
-    let mut data = [0u8; 15];
-    data[0] = 253;
-    data[1] = robot_state.joint_1;
-    data[2] = robot_state.joint_2;
-    data[3] = robot_state.joint_3;
-    data[4] = robot_state.joint_4;
-    data[5] = robot_state.joint_5;
-    data[6] = robot_state.joint_6;
-    data[7] = robot_state.digital_input_1 as u8;
-    data[8] = robot_state.digital_input_2 as u8;
-    data[9] = robot_state.digital_input_3 as u8;
-    data[10] = robot_state.digital_output_1 as u8;
-    data[11] = robot_state.digital_output_2 as u8;
-    data[12] = robot_state.digital_output_3 as u8;
-    data[13] = robot_state.robot_speed;
-    data[14] = 254;
-
-    // 데이터 전송 로그
-    println!("Sending robot commands: {:?}", data);
+    state.serial_manager.register_command(
+        &name,
+        CustomCommand {
+            opcode,
+            fields: payload_schema,
+        },
+    )
+}
 
+// 식별 확인 요구 설정 커맨드 (켜면 identify_device로 기대 버전을 확인한 뒤에만 명령 전송)
+#[tauri::command]
+pub fn set_require_handshake(
+    state: State<'_, AppState>,
+    enabled: bool,
+    expected_version: u8,
+) -> Result<(), String> {
     state
         .serial_manager
-        .send_data(&data)
-        .map_err(|e| format!("데이터 전송 실패: {}", e))?;
+        .set_require_handshake(enabled, expected_version)
+}
 
-    Ok(())
+// 장치 식별 커맨드 (컨트롤러 LED를 duration_ms 동안 깜빡여 연결된 장치 확인)
+#[tauri::command]
+pub fn identify_device(state: State<'_, AppState>, duration_ms: u64) -> Result<(), String> {
+    state
+        .serial_manager
+        .identify_device(Duration::from_millis(duration_ms))
+        .map_err(|e| format!("장치 식별 실패: {}", e))
 }
 
-// 로봇 상태 읽기 커맨드
+// 컨트롤러 고장 래치 해제 커맨드 (해제를 확인하지 못하면 남은 고장 비트와 함께 오류)
 #[tauri::command]
-pub fn read_robot_state(state: State<'_, AppState>) -> Result<RobotState, String> {
-    match state.serial_manager.read_data() {
-        Ok(robot_state) => Ok(robot_state),
-        Err(e) => Err(format!("로봇 상태 읽기 실패: {}", e)),
+pub fn clear_controller_fault(state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .serial_manager
+        .clear_controller_fault()
+        .map_err(|e| format!("고장 해제 실패: {}", e))
+}
+
+// 사용자 정의 명령 목록 커맨드
+#[tauri::command]
+pub fn list_custom_commands(
+    state: State<'_, AppState>,
+) -> Result<BTreeMap<String, CustomCommand>, String> {
+    Ok(state.serial_manager.custom_commands())
+}
+
+// 사용자 정의 명령 전송 커맨드
+#[tauri::command]
+pub fn send_custom(
+    state: State<'_, AppState>,
+    name: String,
+    payload: HashMap<String, i64>,
+) -> Result<(), String> {
+    state.serial_manager.send_custom(&name, &payload)
+}
+
+// 현재 포트 설정 조회 커맨드
+#[tauri::command]
+pub fn get_serial_config(state: State<'_, AppState>) -> Result<PortConfig, String> {
+    state.serial_manager.port_config()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::PayloadFieldType;
+    use crate::test_support::{
+        connected_manager, echo_responder, mock_controller, mock_responder, pty_manager,
+        pty_received, received, received_before, state, voltage_frame, wait_until,
+    };
+    use std::io::Write;
+
+    #[test]
+    fn joint_arithmetic_saturates_instead_of_wrapping() {
+        // 255 + delta, 0 - delta는 끝값에서 멈춤
+        assert_eq!(offset_joint(255, 1), 255);
+        assert_eq!(offset_joint(255, 200), 255);
+        assert_eq!(offset_joint(250, 10), 255);
+        assert_eq!(offset_joint(0, -1), 0);
+        assert_eq!(offset_joint(0, -200), 0);
+        assert_eq!(offset_joint(5, -10), 0);
+        assert_eq!(offset_joint(100, -30), 70);
+
+        assert_eq!(joint_change(0, 255), 255);
+        assert_eq!(joint_change(255, 0), -255);
+
+        assert_eq!(round_joint(255.6), 255);
+        assert_eq!(round_joint(300.0), 255);
+        assert_eq!(round_joint(-0.6), 0);
+        assert_eq!(round_joint(127.5), 128);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn device_timestamp_follows_configured_offset() {
+        let (manager, mut controller) = pty_manager();
+        assert!(manager.set_timestamp_offset(Some(13)).is_err());
+        manager.set_timestamp_offset(Some(16)).unwrap();
+
+        // 속도 뒤 2바이트 패딩, 이어서 리틀 엔디언 타임스탬프
+        let mut frame = vec![253, 1, 2, 3, 4, 5, 6, 0, 0, 0, 0, 0, 0, 50, 0, 0];
+        frame.extend(0x1234_5678u32.to_le_bytes());
+        frame.push(254);
+        controller.write_all(&frame).unwrap();
+
+        let robot_state = manager.read_data().unwrap();
+        assert_eq!(robot_state.joint_6, 6);
+        assert_eq!(robot_state.robot_speed, 50);
+        assert_eq!(robot_state.device_timestamp, Some(0x1234_5678));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn timestamp_offset_change_drops_partial_frame() {
+        let (manager, mut controller) = pty_manager();
+        manager.set_timestamp_offset(Some(16)).unwrap();
+
+        // 이전 배치로 보낸 패킷 앞부분만 버퍼에 남긴 채 배치 변경
+        controller
+            .write_all(&[253, 1, 2, 3, 4, 5, 6, 0, 0, 0, 0, 0, 0, 50, 0, 0, 0x78])
+            .unwrap();
+        assert!(manager
+            .read_data_timeout(Some(Duration::from_millis(100)))
+            .is_err());
+        manager.set_timestamp_offset(None).unwrap();
+
+        controller
+            .write_all(&encode_command(&state(7), IoLayout::Expanded))
+            .unwrap();
+        let robot_state = manager.read_data().unwrap();
+        assert_eq!(robot_state.joint_1, 7);
+        assert_eq!(robot_state.device_timestamp, None);
+        assert_eq!(manager.metrics.malformed_frames.load(Ordering::Relaxed), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sent_command_is_kept_for_output_walk() {
+        let (manager, mut controller) = pty_manager();
+        assert!(manager.last_command().is_none());
+
+        let mut frame = state(90);
+        set_digital_output(&mut frame, 2, true);
+        set_digital_output(&mut frame, 4, true);
+        manager.send_command(&frame).unwrap();
+
+        let sent = pty_received(&mut controller, 15);
+        assert_eq!(sent[..], encode_command(&frame, IoLayout::Expanded));
+        assert_eq!(sent[10..13], [0, 1, 0]);
+        assert!(manager.last_command().unwrap().digital_output_2);
+    }
+
+    #[test]
+    fn init_pose_is_sent_after_successful_init() {
+        let (addr, rx) = mock_controller(Vec::new());
+        let manager = SerialPortManager::new();
+        manager
+            .set_init_pose(InitPose {
+                pose: state(42),
+                enabled: true,
+                delay_ms: 0,
+                require_state: false,
+            })
+            .unwrap();
+        manager.initialize_tcp(&addr).unwrap();
+
+        let message = send_init_pose(&manager, "연결됨".into(), manager.motion_token()).unwrap();
+        assert_eq!(message, "연결됨");
+        assert_eq!(
+            received(&rx, 15),
+            encode_command(&state(42), IoLayout::Expanded)
+        );
+        assert_eq!(manager.last_command().unwrap().joint_1, 42);
+    }
+
+    #[test]
+    fn batch_read_returns_requested_frame_count() {
+        let stream: Vec<u8> = [1, 2, 3, 4]
+            .into_iter()
+            .flat_map(|joint| encode_command(&state(joint), IoLayout::Expanded))
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        assert!(SerialPortManager::new()
+            .read_batch(3, Duration::from_millis(100), 0)
+            .is_err());
+        assert!(manager.read_batch(0, Duration::from_secs(1), 0).is_err());
+        assert!(manager
+            .read_batch(MAX_BATCH_FRAMES + 1, Duration::from_secs(1), 0)
+            .is_err());
+        assert!(manager
+            .read_batch(3, MAX_BATCH_TIMEOUT + Duration::from_millis(1), 0)
+            .is_err());
+
+        let states = manager
+            .read_batch(3, Duration::from_secs(1), manager.motion_token())
+            .unwrap();
+        let joints: Vec<u8> = states.iter().map(|s| s.joint_1).collect();
+        assert_eq!(joints, vec![1, 2, 3]);
+        // 남은 패킷은 다음 읽기에서 사용
+        assert_eq!(manager.read_data().unwrap().joint_1, 4);
+
+        // 취소되면 대기 시간이 남아 있어도 바로 중단
+        let token = manager.motion_token();
+        manager.cancel_motion();
+        let started = Instant::now();
+        assert!(manager
+            .read_batch(3, MAX_BATCH_TIMEOUT, token)
+            .unwrap_err()
+            .contains("취소"));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn health_report_reflects_connection_and_last_read() {
+        let manager = SerialPortManager::new();
+        let idle = manager.health();
+        assert!(!idle.connected);
+        assert!(idle.connection.is_none());
+        assert_eq!(idle.last_state_unix_ms, None);
+        assert_eq!(idle.frame_len, 15);
+
+        manager.set_timestamp_offset(Some(14)).unwrap();
+        let mut frame = encode_command(&state(20), IoLayout::Expanded);
+        frame.splice(14..14, [0, 0, 0, 7]);
+        let (addr, _rx) = mock_controller(frame);
+        manager.initialize_tcp(&addr).unwrap();
+        manager.read_data().unwrap();
+
+        let health = manager.health();
+        assert!(health.connected);
+        assert!(health.connection.is_some());
+        assert_eq!((health.frame_len, health.timestamp_offset), (19, Some(14)));
+        assert!(health.last_state_unix_ms.is_some());
+        assert!(health.staleness_ms.unwrap() < 1000);
+    }
+
+    #[test]
+    fn tcp_transport_round_trips_through_echo_server() {
+        let (addr, _rx) = mock_responder(Vec::new(), echo_responder);
+        let manager = SerialPortManager::new();
+        manager.initialize_tcp(&addr).unwrap();
+        let command = RobotState {
+            joint_6: 60,
+            digital_output_1: true,
+            robot_speed: 77,
+            ..state(10)
+        };
+
+        manager.send_command(&command).unwrap();
+        let echoed = manager.read_data().unwrap();
+        assert_eq!((echoed.joint_1, echoed.joint_6), (10, 60));
+        assert!(echoed.digital_output_1);
+        assert_eq!(echoed.robot_speed, 77);
+
+        // 대기 중인 서버가 없는 주소는 연결 오류
+        assert!(SerialPortManager::new()
+            .initialize_tcp("127.0.0.1:1")
+            .is_err());
+    }
+
+    #[test]
+    fn large_read_buffer_decodes_every_buffered_frame() {
+        let frame = |joint| encode_command(&state(joint), IoLayout::Expanded);
+        let mut stream = vec![0, 1, 2];
+        for joint in 1..=5 {
+            stream.extend(frame(joint));
+        }
+        let mut corrupted = frame(7);
+        corrupted[14] = 0;
+        stream.extend(corrupted);
+        stream.extend(frame(8));
+        let (manager, _rx) = connected_manager(stream);
+        manager.set_read_buffer_size(4096).unwrap();
+        assert!(manager.set_read_buffer_size(0).is_err());
+
+        for joint in 1..=5 {
+            assert_eq!(manager.read_data().unwrap().joint_1, joint);
+        }
+        assert!(manager.read_data().is_err());
+        assert_eq!(manager.read_data().unwrap().joint_1, 8);
+    }
+
+    #[test]
+    fn signal_lines_require_a_serial_connection() {
+        let manager = SerialPortManager::new();
+        let error = manager.read_signal_lines().err().unwrap();
+        assert!(error.contains("초기화"));
+        let (manager, _rx) = connected_manager(Vec::new());
+        let error = manager.read_signal_lines().err().unwrap();
+        assert!(error.contains("시리얼 포트가 아니어서"));
+    }
+
+    #[test]
+    fn commands_keep_working_after_port_lock_is_poisoned() {
+        let (manager, rx) = connected_manager(Vec::new());
+        // 포트 잠금을 쥔 채 패닉한 스레드로 잠금을 오염시킴
+        thread::scope(|scope| {
+            let poisoner = scope.spawn(|| {
+                let _guard = manager.port.lock().unwrap();
+                panic!("poison port lock");
+            });
+            assert!(poisoner.join().is_err());
+        });
+        assert!(manager.port.is_poisoned());
+
+        manager.send_command(&state(90)).unwrap();
+        let sent = received(&rx, 15);
+        assert_eq!(sent, encode_command(&state(90), IoLayout::Expanded));
+
+        manager.disconnect().unwrap();
+        assert!(manager.send_command(&state(90)).is_err());
+    }
+
+    #[test]
+    fn speed_scale_halves_sent_speed() {
+        let (manager, rx) = connected_manager(Vec::new());
+        assert!(manager.set_speed_scale(1.5).is_err());
+        manager.set_speed_scale(0.5).unwrap();
+        let command = RobotState {
+            robot_speed: 80,
+            ..state(10)
+        };
+        manager.send_command(&command).unwrap();
+
+        let sent = received(&rx, 15);
+        assert_eq!(sent.len(), 15);
+
+        // 관절 값은 그대로, 속도만 절반
+        assert_eq!((sent[1], sent[13]), (10, 40));
+    }
+
+    #[test]
+    fn max_command_rate_follows_baud_and_frame_length() {
+        // 115200bps, 8N1(10비트), 15바이트 → 768fps
+        assert_eq!(max_frame_rate(115_200, 10, 15), 768.0);
+        assert_eq!(max_frame_rate(9600, 11, 15), 9600.0 / 165.0);
+
+        assert!(SerialPortManager::new().max_command_rate().is_err());
+        let (manager, _rx) = connected_manager(Vec::new());
+        assert!(manager.max_command_rate().unwrap_err().contains("TCP"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn max_command_rate_uses_opened_port_settings() {
+        use serialport::SerialPort;
+
+        let (_controller, device) = serialport::TTYPort::pair().unwrap();
+        let manager = SerialPortManager::new();
+        manager.initialize(&device.name().unwrap(), 57600).unwrap();
+        // 8N1 기본 설정, 15바이트 패킷
+        assert_eq!(manager.max_command_rate().unwrap(), 384.0);
+
+        // 타임스탬프를 붙인 21바이트 패킷은 처리량 측정과 같은 길이로 계산
+        manager.set_timestamp_offset(Some(16)).unwrap();
+        assert_eq!(manager.max_command_rate().unwrap(), 5760.0 / 21.0);
+    }
+
+    #[test]
+    fn per_call_timeout_is_restored_after_read() {
+        let (manager, _rx) = connected_manager(Vec::new());
+
+        let started = Instant::now();
+        assert!(manager
+            .read_data_timeout(Some(Duration::from_millis(400)))
+            .is_err());
+        assert!(started.elapsed() >= Duration::from_millis(390));
+
+        // 다음 읽기는 포트에 설정된 짧은 타임아웃으로 돌아감
+        let started = Instant::now();
+        assert!(manager.read_data().is_err());
+        assert!(started.elapsed() < Duration::from_millis(200));
+    }
+
+    #[test]
+    fn bytes_between_frames_are_skipped_or_reported() {
+        let frame = |joint| encode_command(&state(joint), IoLayout::Expanded);
+        let mut stream = frame(1);
+        stream.extend([0, 0, 7]);
+        stream.extend(frame(2));
+        stream.push(9);
+        stream.extend(frame(3));
+
+        let (manager, _rx) = connected_manager(stream.clone());
+        for joint in 1..=3 {
+            assert_eq!(manager.read_data().unwrap().joint_1, joint);
+        }
+
+        let (manager, _rx) = connected_manager(stream);
+        manager.set_trailing_mode(FrameTrailingMode::Strict);
+        assert_eq!(manager.read_data().unwrap().joint_1, 1);
+        assert!(manager.read_data().unwrap_err().contains("여분 바이트 3개"));
+        assert_eq!(manager.read_data().unwrap().joint_1, 2);
+        assert!(manager.read_data().unwrap_err().contains("여분 바이트 1개"));
+        assert_eq!(manager.read_data().unwrap().joint_1, 3);
+    }
+
+    fn usb_port(name: &str, vid: u16, pid: u16) -> serialport::SerialPortInfo {
+        serialport::SerialPortInfo {
+            port_name: name.into(),
+            port_type: serialport::SerialPortType::UsbPort(serialport::UsbPortInfo {
+                vid,
+                pid,
+                serial_number: None,
+                manufacturer: None,
+                product: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn auto_port_selection_handles_zero_one_and_many_ports() {
+        assert!(select_auto_port(&[], &[]).is_err());
+        assert_eq!(select_auto_port(&[usb_port("a", 1, 2)], &[]).unwrap(), "a");
+
+        let many = [
+            usb_port("a", 1, 2),
+            usb_port("b", 3, 4),
+            serialport::SerialPortInfo {
+                port_name: "c".into(),
+                port_type: serialport::SerialPortType::Unknown,
+            },
+        ];
+        assert!(select_auto_port(&many, &[]).is_err());
+        assert_eq!(
+            select_auto_port(&many, &[UsbId { vid: 3, pid: 4 }]).unwrap(),
+            "b"
+        );
+        assert!(select_auto_port(&many, &[UsbId { vid: 9, pid: 9 }]).is_err());
+    }
+
+    #[test]
+    fn port_config_reflects_tcp_connection() {
+        assert!(SerialPortManager::new().port_config().is_err());
+        let (manager, _rx) = connected_manager(Vec::new());
+        let config = manager.port_config().unwrap();
+        assert_eq!(config.timeout_ms, 100);
+        assert_eq!(config.baud_rate, None);
+        assert!(config.parity.is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn port_config_reflects_initialize_serial_settings() {
+        use serialport::SerialPort;
+
+        let (_controller, device) = serialport::TTYPort::pair().unwrap();
+        let manager = SerialPortManager::new();
+        manager.initialize(&device.name().unwrap(), 57600).unwrap();
+        let config = manager.port_config().unwrap();
+        assert_eq!(config.port_name, device.name().unwrap());
+        assert_eq!(config.baud_rate, Some(57600));
+        assert_eq!(config.parity, Some("none".into()));
+    }
+
+    // 디지털 입력 1만 설정한 상태 패킷
+    fn input_frame(input_1: bool) -> Vec<u8> {
+        let robot_state = RobotState {
+            digital_input_1: input_1,
+            ..state(1)
+        };
+        encode_command(&robot_state, IoLayout::Expanded)
+    }
+
+    #[test]
+    fn mirrored_input_toggle_sets_matching_output() {
+        let stream: Vec<u8> = [true, true, false]
+            .into_iter()
+            .flat_map(input_frame)
+            .collect();
+        let (manager, rx) = connected_manager(stream);
+        assert!(manager.set_io_mirror(8).is_err());
+        manager.send_command(&state(1)).unwrap();
+        received(&rx, 15);
+        manager.set_io_mirror(0b001).unwrap();
+
+        manager.read_data().unwrap();
+        let mirrored = received(&rx, 15);
+        assert_eq!(mirrored[10], 1);
+        // 입력이 그대로면 다시 보내지 않음 (같은 명령을 표식으로 재전송)
+        manager.read_data().unwrap();
+        manager
+            .send_command(&manager.last_command().unwrap())
+            .unwrap();
+        assert!(received_before(&rx, &mirrored).is_empty());
+        manager.read_data().unwrap();
+        assert_eq!(received(&rx, 15)[10], 0);
+    }
+
+    #[test]
+    fn last_raw_frame_matches_decoded_bytes() {
+        let frame = encode_command(&state(0x1A), IoLayout::Expanded);
+        let (manager, _rx) = connected_manager(frame.clone());
+        assert!(manager.last_raw_frame().is_none());
+
+        manager.read_data().unwrap();
+        let last = manager.last_raw_frame().unwrap();
+        assert_eq!(last.bytes, frame);
+        assert!(last.hex.starts_with("FD 1A 1A"));
+        assert_eq!(last.state.joint_1, 0x1A);
+    }
+
+    #[test]
+    fn stuck_frame_warning_fires_after_threshold() {
+        let frame = |joint| encode_command(&state(joint), IoLayout::Expanded);
+        let stream: Vec<u8> = [4, 4, 4, 4, 4, 4, 5, 5]
+            .into_iter()
+            .flat_map(frame)
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        manager.set_stuck_threshold(3);
+
+        for _ in 0..3 {
+            manager.read_data().unwrap();
+            assert!(manager.take_stuck_warning().is_none());
+        }
+        manager.read_data().unwrap();
+        assert_eq!(manager.take_stuck_warning(), Some(3));
+        // 같은 반복 구간에서는 한 번만 경고
+        manager.read_data().unwrap();
+        manager.read_data().unwrap();
+        assert!(manager.take_stuck_warning().is_none());
+        manager.read_data().unwrap();
+        manager.read_data().unwrap();
+        assert!(manager.take_stuck_warning().is_none());
+    }
+
+    #[test]
+    fn verify_connection_fails_when_no_frame_arrives() {
+        let (manager, _rx) = connected_manager(vec![1, 2, 3]);
+        let error = manager
+            .verify_connection(Duration::from_millis(300))
+            .unwrap_err();
+        assert!(error.contains("300ms"), "{}", error);
+        assert!(!manager.is_connected());
+
+        let (manager, _rx) = connected_manager(encode_command(&state(1), IoLayout::Expanded));
+        manager
+            .verify_connection(Duration::from_millis(300))
+            .unwrap();
+        assert!(manager.is_connected());
+    }
+
+    // 입력 동작 처리 스레드를 띄운 관리자
+    fn with_input_worker(manager: SerialPortManager) -> Arc<SerialPortManager> {
+        let app_state = AppState {
+            serial_manager: Arc::new(manager),
+            #[cfg(feature = "metrics-server")]
+            metrics_server: Arc::new(Mutex::new(None)),
+            tasks: Arc::new(TaskRegistry::new()),
+        };
+        spawn_input_worker(&app_state);
+        app_state.serial_manager
+    }
+
+    #[test]
+    fn rising_edge_runs_bound_action_once() {
+        let stream: Vec<u8> = [false, true, true, false, true]
+            .into_iter()
+            .flat_map(input_frame)
+            .collect();
+        let (manager, rx) = connected_manager(stream);
+        let manager = with_input_worker(manager);
+        assert!(manager
+            .set_input_binding(4, Some(InputAction::CancelMotion))
+            .is_err());
+        assert!(manager
+            .set_input_binding(1, Some(InputAction::ToggleOutput(9)))
+            .is_err());
+        manager
+            .set_input_binding(1, Some(InputAction::ToggleOutput(2)))
+            .unwrap();
+        let released = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        received(&rx, 15);
+
+        // 떼어진 입력은 동작 없음 (같은 명령을 표식으로 재전송)
+        manager.read_data().unwrap();
+        manager.send_command(&state(1)).unwrap();
+        assert!(received_before(&rx, &released).is_empty());
+        manager.read_data().unwrap();
+        let pressed = received(&rx, 15);
+        assert_eq!(pressed[11], 1);
+        // 처리 스레드가 마지막 명령을 갱신할 때까지 대기
+        wait_until(|| manager.last_command().unwrap().digital_output_2);
+        // 눌린 채 유지되거나 떼는 것은 동작 없음
+        manager.read_data().unwrap();
+        manager.read_data().unwrap();
+        manager
+            .send_command(&manager.last_command().unwrap())
+            .unwrap();
+        assert!(received_before(&rx, &pressed).is_empty());
+        // 떨림 방지 시간이 지난 뒤 다시 누르면 한 번 더 반전
+        thread::sleep(INPUT_DEBOUNCE);
+        manager.read_data().unwrap();
+        assert_eq!(received(&rx, 15)[11], 0);
+    }
+
+    #[test]
+    fn bound_pose_moves_gently_off_the_read_path() {
+        let stream: Vec<u8> = [false, true, false, true]
+            .into_iter()
+            .flat_map(input_frame)
+            .collect();
+        let (manager, rx) = connected_manager(stream);
+        manager.save_pose("far", state(21)).unwrap();
+        manager
+            .set_input_binding(1, Some(InputAction::GotoPose("far".into())))
+            .unwrap();
+        manager.send_command(&state(1)).unwrap();
+        received(&rx, 15);
+
+        // 처리 스레드가 없으면 동작하지 않고 오류만 남김
+        manager.read_data().unwrap();
+        manager.read_data().unwrap();
+        assert!(lock(&manager.last_error)
+            .as_ref()
+            .unwrap()
+            .contains("스레드"));
+
+        let manager = with_input_worker(manager);
+        manager.read_data().unwrap();
+        thread::sleep(INPUT_DEBOUNCE);
+        let started = Instant::now();
+        manager.read_data().unwrap();
+        // 수신 경로는 이동이 끝나기를 기다리지 않음
+        assert!(started.elapsed() < Duration::from_millis(100));
+
+        // 마지막 명령에서 단계당 2씩 나눠 이동
+        let sent = received(&rx, 15 * 10);
+        let joint_1: Vec<u8> = sent.chunks(15).map(|frame| frame[1]).collect();
+        assert_eq!(joint_1, (3..=21).step_by(2).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn prometheus_metrics_report_frame_counters() {
+        let (manager, _rx) = connected_manager(encode_command(&state(1), IoLayout::Expanded));
+        manager.read_data().unwrap();
+        manager.send_command(&state(1)).unwrap();
+
+        let metrics = manager.prometheus_metrics();
+        for expected in [
+            "robot_arm_frames_sent_total 1",
+            "robot_arm_frames_received_total 1",
+            "robot_arm_malformed_packets_total 0",
+            "# TYPE robot_arm_last_latency_ms gauge",
+        ] {
+            assert!(metrics.contains(expected), "{}", expected);
+        }
+    }
+
+    #[test]
+    fn command_inside_keepout_region_is_rejected() {
+        let (manager, rx) = connected_manager(Vec::new());
+        let region = KeepoutRegion {
+            name: "clash".into(),
+            constraints: vec![
+                JointRange {
+                    joint: 2,
+                    min: 150,
+                    max: 255,
+                },
+                JointRange {
+                    joint: 3,
+                    min: 150,
+                    max: 255,
+                },
+            ],
+        };
+        manager.add_keepout_region(region.clone()).unwrap();
+        assert!(manager.add_keepout_region(region).is_err());
+
+        let error = manager.send_command(&state(160)).err().unwrap();
+        assert!(error.contains("clash"), "{}", error);
+        let nearby = RobotState {
+            joint_3: 149,
+            ..state(160)
+        };
+        manager.send_command(&nearby).unwrap();
+        let marker = encode_command(&nearby, IoLayout::Expanded);
+        assert!(received_before(&rx, &marker).is_empty());
+
+        manager.remove_keepout_region("clash").unwrap();
+        manager.send_command(&state(160)).unwrap();
+        assert!(manager.keepout_regions().is_empty());
+    }
+
+    #[test]
+    fn snapshot_restores_into_fresh_manager() {
+        let manager = SerialPortManager::new();
+        manager.set_io_layout(IoLayout::Packed).unwrap();
+        manager.set_timestamp_offset(Some(9)).unwrap();
+        manager.set_joint_limit(3, 5, 200).unwrap();
+        manager.save_pose("home", state(7)).unwrap();
+        manager
+            .set_input_binding(2, Some(InputAction::GotoPose("home".into())))
+            .unwrap();
+        manager.set_speed_scale(0.5).unwrap();
+        manager
+            .set_limit_approach(ApproachSlowdown {
+                margin: 10,
+                min_speed: 5,
+            })
+            .unwrap();
+        manager
+            .set_calibration(1, JointCalibration::fit(0.0, 0, 90.0, 100).unwrap())
+            .unwrap();
+
+        let json = serde_json::to_string(&manager.snapshot()).unwrap();
+        let snapshot: RuntimeSnapshot = serde_json::from_str(&json).unwrap();
+        let restored = SerialPortManager::new();
+        restored.restore(&snapshot).unwrap();
+        assert_eq!(serde_json::to_string(&restored.snapshot()).unwrap(), json);
+
+        // 잘못된 스냅샷은 일부도 적용하지 않음
+        let mut invalid = snapshot;
+        invalid.speed_scale = 3.0;
+        invalid.poses.clear();
+        let untouched = SerialPortManager::new();
+        untouched.save_pose("keep", state(1)).unwrap();
+        assert!(untouched.restore(&invalid).is_err());
+        assert_eq!(untouched.pose_names(), vec!["keep"]);
+    }
+
+    #[test]
+    fn output_toggle_inside_dwell_window_is_rejected() {
+        let (manager, _rx) = connected_manager(Vec::new());
+        assert!(manager
+            .set_output_dwell(0, Duration::from_millis(1))
+            .is_err());
+        manager
+            .set_output_dwell(1, Duration::from_millis(200))
+            .unwrap();
+
+        let mut command = state(1);
+        manager.send_command(&command).unwrap();
+        command.digital_output_1 = true;
+        manager.send_command(&command).unwrap();
+        command.digital_output_1 = false;
+        assert!(manager.send_command(&command).is_err());
+
+        // 다른 출력은 대기 시간과 무관
+        command.digital_output_1 = true;
+        command.digital_output_2 = true;
+        manager.send_command(&command).unwrap();
+        thread::sleep(Duration::from_millis(210));
+        command.digital_output_1 = false;
+        manager.send_command(&command).unwrap();
+    }
+
+    #[test]
+    fn frontend_loss_cancels_active_motion() {
+        let (manager, rx) = connected_manager(Vec::new());
+        let app_state = AppState {
+            serial_manager: Arc::new(manager),
+            #[cfg(feature = "metrics-server")]
+            metrics_server: Arc::new(Mutex::new(None)),
+            tasks: Arc::new(TaskRegistry::new()),
+        };
+        let manager = app_state.serial_manager.clone();
+        let token = manager.motion_token();
+        let _task = app_state.tasks.register("motion", || {});
+
+        manager.set_frontend_loss_behavior(FrontendLossBehavior::Continue);
+        handle_frontend_loss(&app_state);
+        assert!(!manager.is_motion_cancelled(token));
+        assert_eq!(app_state.tasks.list().len(), 1);
+
+        manager.set_frontend_loss_behavior(FrontendLossBehavior::Cancel);
+        handle_frontend_loss(&app_state);
+        assert!(manager.is_motion_cancelled(token));
+        assert!(app_state.tasks.list().is_empty());
+
+        // 정지 자세가 있으면 취소 후 그 자세로 이동
+        manager
+            .set_init_pose(InitPose {
+                pose: state(77),
+                enabled: true,
+                delay_ms: 0,
+                require_state: false,
+            })
+            .unwrap();
+        manager.set_frontend_loss_behavior(FrontendLossBehavior::Park);
+        handle_frontend_loss(&app_state);
+        assert_eq!(manager.last_command().unwrap().joint_1, 77);
+        // 앞선 취소에서는 아무것도 보내지 않았으므로 첫 패킷이 정지 자세
+        assert_eq!(
+            received(&rx, 15),
+            encode_command(&state(77), IoLayout::Expanded)
+        );
+    }
+
+    #[test]
+    fn default_build_reports_baseline_capabilities() {
+        let capabilities = capabilities();
+        assert_eq!(capabilities.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(capabilities.os, std::env::consts::OS);
+        // 기본 기능(tcp, metrics-server)으로 빌드하면 둘 다 포함됨
+        assert!(capabilities.tcp);
+        assert!(capabilities.metrics_server);
+        assert_eq!(capabilities.pipe_stream, cfg!(unix));
+    }
+
+    #[test]
+    fn busy_port_errors_map_to_dedicated_variant() {
+        use serialport::{Error, ErrorKind as PortErrorKind};
+
+        let busy: SerialError =
+            Error::new(PortErrorKind::NoDevice, "Device or resource busy").into();
+        assert!(matches!(busy, SerialError::PortBusy(_)));
+        assert!(busy.to_string().contains("다른 프로그램"));
+        let denied: SerialError = Error::new(
+            PortErrorKind::Io(std::io::ErrorKind::PermissionDenied),
+            "Permission denied",
+        )
+        .into();
+        assert!(matches!(denied, SerialError::PortBusy(_)));
+        let missing: SerialError =
+            Error::new(PortErrorKind::NoDevice, "No such file or directory").into();
+        assert!(matches!(missing, SerialError::Open(_)));
+    }
+
+    #[test]
+    fn low_voltage_warning_fires_once_per_dip() {
+        let stream: Vec<u8> = [(1, 1200), (2, 1000), (3, 900), (4, 1300), (5, 950)]
+            .into_iter()
+            .flat_map(|(joint, millivolts)| voltage_frame(joint, millivolts))
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        manager.set_timestamp_offset(Some(14)).unwrap();
+        let error = manager.set_voltage_offset(Some(16)).unwrap_err();
+        assert!(error.contains("겹칩니다"), "{}", error);
+        manager.set_timestamp_offset(None).unwrap();
+        assert!(manager.set_voltage_offset(Some(13)).is_err());
+        manager.set_voltage_offset(Some(14)).unwrap();
+        manager.set_voltage_threshold(Some(1100));
+
+        let robot_state = manager.read_data().unwrap();
+        assert_eq!(
+            (robot_state.joint_1, robot_state.supply_voltage),
+            (1, Some(1200))
+        );
+        assert_eq!(manager.take_low_voltage_warning(), None);
+        manager.read_data().unwrap();
+        assert_eq!(manager.take_low_voltage_warning(), Some(1000));
+        // 계속 낮은 동안은 다시 경고하지 않음
+        manager.read_data().unwrap();
+        assert_eq!(manager.take_low_voltage_warning(), None);
+        manager.read_data().unwrap();
+        manager.read_data().unwrap();
+        assert_eq!(manager.take_low_voltage_warning(), Some(950));
+    }
+
+    #[test]
+    fn invalid_limit_pair_rejects_whole_set() {
+        let manager = SerialPortManager::new();
+        let limits = [(10, 20), (0, 255), (5, 5), (1, 2), (3, 4), (6, 7)];
+        manager.set_all_joint_limits(limits).unwrap();
+
+        let error = manager
+            .set_all_joint_limits([(0, 1), (0, 1), (0, 1), (9, 8), (0, 1), (0, 1)])
+            .unwrap_err();
+        assert!(error.contains("관절 4"), "{}", error);
+        let applied: Vec<(u8, u8)> = manager
+            .joint_limits()
+            .iter()
+            .map(|range| (range.min, range.max))
+            .collect();
+        assert_eq!(applied, limits);
+    }
+
+    #[test]
+    fn registered_custom_command_sends_encoded_payload() {
+        let (manager, rx) = connected_manager(Vec::new());
+        let field = |name: &str, kind| PayloadField {
+            name: name.into(),
+            kind,
+        };
+        assert!(manager
+            .register_command(
+                "tail",
+                CustomCommand {
+                    opcode: 254,
+                    fields: Vec::new(),
+                },
+            )
+            .is_err());
+        assert!(manager
+            .register_command(
+                "twice",
+                CustomCommand {
+                    opcode: 200,
+                    fields: vec![
+                        field("a", PayloadFieldType::U8),
+                        field("a", PayloadFieldType::U8),
+                    ],
+                },
+            )
+            .is_err());
+        manager
+            .register_command(
+                "gripper",
+                CustomCommand {
+                    opcode: 200,
+                    fields: vec![
+                        field("force", PayloadFieldType::U8),
+                        field("offset", PayloadFieldType::I16),
+                        field("on", PayloadFieldType::Bool),
+                    ],
+                },
+            )
+            .unwrap();
+
+        let payload: HashMap<String, i64> = [
+            ("force".to_string(), 42),
+            ("offset".to_string(), -2),
+            ("on".to_string(), 1),
+        ]
+        .into();
+        assert!(manager.send_custom("missing", &payload).is_err());
+        let mut out_of_range = payload.clone();
+        out_of_range.insert("force".into(), 300);
+        assert!(manager
+            .send_custom("gripper", &out_of_range)
+            .unwrap_err()
+            .contains("force"));
+        let mut incomplete = payload.clone();
+        incomplete.remove("on");
+        assert!(manager.send_custom("gripper", &incomplete).is_err());
+        let mut extra = payload.clone();
+        extra.insert("extra".into(), 1);
+        assert!(manager.send_custom("gripper", &extra).is_err());
+
+        manager.send_custom("gripper", &payload).unwrap();
+        assert_eq!(received(&rx, 6), vec![200, 42, 0xFE, 0xFF, 1, 254]);
+        assert_eq!(manager.snapshot().custom_commands.len(), 1);
+    }
+
+    #[test]
+    fn read_until_returns_text_up_to_delimiter() {
+        let mut stream = b"boot ok\n".to_vec();
+        stream.extend(encode_command(&state(5), IoLayout::Expanded));
+        stream.extend(b"abcdef");
+        let (manager, _rx) = connected_manager(stream);
+
+        let line = manager
+            .read_until(b'\n', 64, Duration::from_millis(300), 0)
+            .unwrap();
+        assert_eq!(line, b"boot ok\n");
+        // 남은 바이트는 상태 패킷 읽기에 그대로 사용
+        assert_eq!(manager.read_data().unwrap().joint_1, 5);
+        let error = manager
+            .read_until(b'\n', 4, Duration::from_millis(300), 0)
+            .unwrap_err();
+        assert!(error.contains("최대 길이"), "{}", error);
+
+        let started = Instant::now();
+        let error = manager
+            .read_until(b'\n', 64, Duration::from_millis(200), 0)
+            .unwrap_err();
+        assert!(error.contains("타임아웃"), "{}", error);
+        assert!(started.elapsed() >= Duration::from_millis(190));
+        let rest = manager
+            .read_until(b'd', 64, Duration::from_millis(100), 0)
+            .unwrap();
+        assert_eq!(rest, b"abcd");
+        assert!(manager
+            .read_until(b'\n', 0, Duration::from_millis(100), 0)
+            .is_err());
+        assert!(manager
+            .read_until(b'\n', 64, MAX_READ_UNTIL_TIMEOUT * 2, 0)
+            .is_err());
+
+        // 취소되면 구분자를 기다리지 않고 바로 중단
+        let token = manager.motion_token();
+        manager.cancel_motion();
+        let started = Instant::now();
+        assert!(manager
+            .read_until(b'\n', 64, MAX_READ_UNTIL_TIMEOUT, token)
+            .unwrap_err()
+            .contains("취소"));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn pose_preset_round_trips_through_export_and_import() {
+        let path =
+            std::env::temp_dir().join(format!("robot-arm-preset-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        let calibration = JointCalibration {
+            slope: 0.5,
+            intercept: -10.0,
+        };
+        let exporter = SerialPortManager::new();
+        exporter.set_calibration(1, calibration).unwrap();
+        let preset = exporter
+            .export_pose_preset("pick", state(120), Some("gripper open".into()), path)
+            .unwrap();
+        assert!(preset.exported_unix_ms > 0);
+
+        let importer = SerialPortManager::new();
+        let imported = importer.import_pose_preset(path).unwrap();
+        assert_eq!(imported.note.as_deref(), Some("gripper open"));
+        assert_eq!(importer.pose("pick").unwrap().joint_3, 120);
+
+        // 보정값이 다른 팔이나 제한을 넘는 자세는 가져오지 않음
+        let recalibrated = SerialPortManager::new();
+        recalibrated
+            .set_calibration(
+                1,
+                JointCalibration {
+                    slope: 0.6,
+                    ..calibration
+                },
+            )
+            .unwrap();
+        assert!(recalibrated
+            .import_pose_preset(path)
+            .err()
+            .unwrap()
+            .contains("보정값"));
+        let limited = SerialPortManager::new();
+        limited.set_joint_limit(2, 0, 100).unwrap();
+        assert!(limited
+            .import_pose_preset(path)
+            .err()
+            .unwrap()
+            .contains("관절 2"));
+        assert!(limited.pose("pick").is_none());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn disconnect_appends_manual_session() {
+        let (manager, _rx) = connected_manager(encode_command(&state(3), IoLayout::Expanded));
+        manager.read_data().unwrap();
+        manager.send_command(&state(1)).unwrap();
+        assert_eq!(manager.session_history()[0].close_reason, None);
+
+        manager.disconnect().unwrap();
+        let history = manager.session_history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].close_reason, Some(CloseReason::Manual));
+        assert_eq!((history[0].frames_received, history[0].frames_sent), (1, 1));
+        assert_eq!(history[0].baud_rate, None);
+    }
+
+    #[test]
+    fn send_normalized_applies_response_curve() {
+        let (manager, rx) = connected_manager(Vec::new());
+        manager.set_all_joint_limits([(0, 200); 6]).unwrap();
+        manager
+            .set_joint_response_curve(1, ResponseCurve::Exponential { gamma: 2.0 })
+            .unwrap();
+
+        manager.send_normalized([0.5; 6], 40).unwrap();
+        let sent = received(&rx, 15);
+        // 관절 1은 0.5^2 * 200, 나머지는 선형으로 0.5 * 200
+        assert_eq!(&sent[1..7], &[50, 100, 100, 100, 100, 100]);
+        assert_eq!(sent[13], 40);
+        assert!(manager.send_normalized([1.5; 6], 40).is_err());
+    }
+
+    // 정상 패킷 사이에 테일이 잘못된 패킷 3개가 낀 수신 데이터
+    fn overrun_stream() -> Vec<u8> {
+        let mut corrupted = encode_command(&state(9), IoLayout::Expanded);
+        corrupted[14] = 0;
+        let mut stream = encode_command(&state(1), IoLayout::Expanded);
+        for _ in 0..3 {
+            stream.extend(&corrupted);
+        }
+        stream.extend(encode_command(&state(2), IoLayout::Expanded));
+        stream
+    }
+
+    #[test]
+    fn framing_errors_after_slow_read_flag_overrun() {
+        let (manager, _rx) = connected_manager(overrun_stream());
+        manager.read_data().unwrap();
+        thread::sleep(Duration::from_millis(150));
+        for _ in 0..3 {
+            assert!(manager.read_data().is_err());
+        }
+
+        let warning = manager.take_buffer_overrun_warning().unwrap();
+        assert_eq!(warning.framing_errors, 3);
+        assert!(warning.read_gap_ms >= 100);
+        assert!(manager.take_buffer_overrun_warning().is_none());
+        assert_eq!(manager.buffer_overrun_count(), 1);
+        assert!(manager
+            .prometheus_metrics()
+            .contains("robot_arm_buffer_overruns_total 1"));
+        assert_eq!(manager.read_data().unwrap().joint_1, 2);
+    }
+
+    #[test]
+    fn framing_errors_without_read_gap_do_not_flag_overrun() {
+        let (manager, _rx) = connected_manager(overrun_stream());
+        for _ in 0..4 {
+            let _ = manager.read_data();
+        }
+        assert!(manager.take_buffer_overrun_warning().is_none());
+        assert_eq!(manager.buffer_overrun_count(), 0);
+    }
+
+    #[test]
+    fn send_in_units_converts_linear_axis() {
+        let (manager, rx) = connected_manager(Vec::new());
+        manager
+            .set_joint_unit(
+                6,
+                JointUnit {
+                    label: "mm".into(),
+                    scale: 0.5,
+                },
+            )
+            .unwrap();
+        let mut labels: [String; 6] = Default::default();
+        labels.fill("raw".into());
+        labels[5] = "mm".into();
+
+        manager
+            .send_in_units([10.0, 20.0, 30.0, 40.0, 50.0, 60.0], &labels, 50)
+            .unwrap();
+        let sent = received(&rx, 15);
+        assert_eq!(&sent[1..7], &[10, 20, 30, 40, 50, 120]);
+
+        // 호출 측이 가정한 단위가 다르면 보내지 않음
+        labels[5] = "deg".into();
+        assert!(manager.send_in_units([0.0; 6], &labels, 50).is_err());
+    }
+
+    #[test]
+    fn analyze_capture_counts_clean_and_corrupted_frames() {
+        let frame = |joint| encode_command(&state(joint), IoLayout::Expanded);
+        let mut corrupted = frame(5);
+        corrupted[14] = 0;
+        let mut capture = vec![1, 2];
+        capture.extend(frame(1));
+        capture.extend(corrupted);
+        capture.extend(frame(2));
+        // 기록 도중 끊긴 패킷
+        capture.extend(&frame(3)[..7]);
+        let path =
+            std::env::temp_dir().join(format!("robot-arm-capture-{}.bin", std::process::id()));
+        std::fs::write(&path, &capture).unwrap();
+
+        let manager = SerialPortManager::new();
+        let analysis = manager.analyze_capture(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(analysis.total_bytes, capture.len());
+        assert_eq!(analysis.valid_frames, 2);
+        assert_eq!(analysis.framing_errors, 1);
+        assert_eq!(analysis.controller_errors, 0);
+        assert_eq!((analysis.resyncs, analysis.skipped_bytes), (2, 16));
+        assert_eq!(analysis.truncated_bytes, 7);
+        let joints: Vec<u8> = analysis.states.iter().map(|s| s.joint_1).collect();
+        assert_eq!(joints, vec![1, 2]);
+
+        assert!(manager.analyze_capture(path.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn delta_commands_send_only_changed_joint_and_reset_on_reconnect() {
+        let (manager, rx) = connected_manager(Vec::new());
+        manager.set_delta_commands(Some(0xF0)).unwrap();
+        manager.send_command(&state(90)).unwrap();
+        manager
+            .send_command(&RobotState {
+                joint_2: 100,
+                ..state(90)
+            })
+            .unwrap();
+        let full = encode_command(&state(90), IoLayout::Expanded);
+        let mut expected = full.clone();
+        expected.extend([0xF0, 1, 2, 100, 254]);
+        assert_eq!(received(&rx, expected.len()), expected);
+
+        // 재연결 후에는 새 컨트롤러가 기준 패킷을 모르므로 전체 패킷부터 전송
+        let (addr, rx) = mock_controller(Vec::new());
+        manager.initialize_tcp(&addr).unwrap();
+        manager.send_command(&state(90)).unwrap();
+        assert_eq!(received(&rx, full.len()), full);
+    }
+
+    #[test]
+    fn home_on_connect_sends_home_pose_after_delay() {
+        let (manager, rx) = connected_manager(encode_command(&state(90), IoLayout::Expanded));
+        assert!(manager
+            .set_home_on_connect(true, Duration::from_millis(100))
+            .is_err());
+        manager
+            .set_init_pose(InitPose {
+                pose: state(10),
+                enabled: false,
+                delay_ms: 0,
+                require_state: false,
+            })
+            .unwrap();
+        manager
+            .set_home_on_connect(true, Duration::from_millis(100))
+            .unwrap();
+
+        let started = Instant::now();
+        assert!(matches!(
+            manager.send_init_pose(manager.motion_token()).unwrap(),
+            InitPoseOutcome::Sent
+        ));
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert_eq!(
+            received(&rx, 15),
+            encode_command(&state(10), IoLayout::Expanded)
+        );
+    }
+
+    #[test]
+    fn home_on_connect_skips_silent_controller() {
+        let (manager, rx) = connected_manager(Vec::new());
+        manager
+            .set_init_pose(InitPose {
+                pose: state(10),
+                enabled: false,
+                delay_ms: 0,
+                require_state: false,
+            })
+            .unwrap();
+        manager.set_home_on_connect(true, Duration::ZERO).unwrap();
+
+        let InitPoseOutcome::Skipped(reason) =
+            manager.send_init_pose(manager.motion_token()).unwrap()
+        else {
+            panic!("응답 없는 장치에 홈 자세를 전송함");
+        };
+        assert!(reason.contains("상태 확인 실패"));
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        assert!(received_before(&rx, &marker).is_empty());
+    }
+
+    #[test]
+    fn init_pose_delay_is_bounded_and_cancellable() {
+        let (manager, rx) = connected_manager(Vec::new());
+        let pose = |delay_ms| InitPose {
+            pose: state(10),
+            enabled: true,
+            delay_ms,
+            require_state: false,
+        };
+        assert!(manager
+            .set_init_pose(pose(MAX_HOME_DELAY.as_millis() as u64 + 1))
+            .is_err());
+        manager
+            .set_init_pose(pose(MAX_HOME_DELAY.as_millis() as u64))
+            .unwrap();
+
+        // 대기 중 취소되면 최대 대기 시간을 기다리지 않고 전송도 하지 않음
+        let manager = Arc::new(manager);
+        let token = manager.motion_token();
+        let sender = {
+            let manager = manager.clone();
+            thread::spawn(move || manager.send_init_pose(token))
+        };
+        let started = Instant::now();
+        manager.cancel_motion();
+        let InitPoseOutcome::Skipped(reason) = sender.join().unwrap().unwrap() else {
+            panic!("취소된 뒤 초기 자세를 전송함");
+        };
+        assert!(reason.contains("취소"));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        let marker = encode_command(&state(1), IoLayout::Expanded);
+        manager.send_command(&state(1)).unwrap();
+        assert!(received_before(&rx, &marker).is_empty());
+    }
+
+    #[test]
+    fn commanding_near_limit_lowers_emitted_speed() {
+        let (manager, rx) = connected_manager(Vec::new());
+        manager.set_joint_limit(1, 0, 180).unwrap();
+        assert!(manager
+            .set_limit_approach(ApproachSlowdown {
+                margin: 10,
+                min_speed: 0,
+            })
+            .is_err());
+        manager
+            .set_limit_approach(ApproachSlowdown {
+                margin: 10,
+                min_speed: 10,
+            })
+            .unwrap();
+
+        manager.send_command(&state(90)).unwrap();
+        manager
+            .send_command(&RobotState {
+                joint_1: 175,
+                ..state(90)
+            })
+            .unwrap();
+        let speeds: Vec<u8> = received(&rx, 15 * 2)
+            .chunks(15)
+            .map(|frame| frame[13])
+            .collect();
+        assert_eq!(speeds, vec![50, 30]);
+    }
+
+    #[test]
+    fn full_state_degrees_follow_calibration() {
+        let mut frame = encode_command(&state(40), IoLayout::Expanded);
+        frame[3] = 120;
+        frame[11] = 1;
+        let (manager, _rx) = connected_manager(frame);
+        let calibration = JointCalibration::fit(0.0, 0, 90.0, 200).unwrap();
+        manager.set_calibration(3, calibration).unwrap();
+
+        let full = manager.read_full_state(None).unwrap();
+        assert_eq!(full.joints.len(), 6);
+        assert_eq!(full.joints[2].raw, 120);
+        assert_eq!(full.joints[2].degrees, Some(calibration.degrees(120)));
+        assert_eq!(full.joints[0].raw, 40);
+        assert_eq!(full.joints[0].degrees, None);
+        assert_eq!(full.digital_outputs, [false, true, false]);
+        assert_eq!(full.robot_speed, 50);
+        assert!(full.received_unix_ms > 0);
+    }
+
+    fn identify_command() -> CustomCommand {
+        CustomCommand {
+            opcode: 0xA0,
+            fields: vec![PayloadField {
+                name: "duration_ms".into(),
+                kind: PayloadFieldType::U16,
+            }],
+        }
+    }
+
+    #[test]
+    fn identify_device_emits_identify_frame() {
+        let (manager, rx) = connected_manager(Vec::new());
+        assert!(manager.identify_device(Duration::from_secs(2)).is_err());
+        manager
+            .register_command("identify", identify_command())
+            .unwrap();
+        assert!(manager.identify_device(Duration::ZERO).is_err());
+
+        manager
+            .identify_device(Duration::from_millis(1500))
+            .unwrap();
+        let [low, high] = 1500u16.to_le_bytes();
+        assert_eq!(received(&rx, 4), vec![0xA0, low, high, 254]);
+    }
+
+    #[test]
+    fn identify_device_reports_nak() {
+        // 오류 코드 0x0001, 메시지 없음
+        let nak = vec![0xEE, 0x01, 0x00, 0x00, 254];
+        let (addr, _rx) = mock_responder(Vec::new(), move |_| nak.clone());
+        let manager = SerialPortManager::new();
+        manager.initialize_tcp(&addr).unwrap();
+        manager
+            .set_error_frames(
+                Some(0xEE),
+                HashMap::from([(1, "지원하지 않음".to_string())]),
+            )
+            .unwrap();
+        manager
+            .register_command("identify", identify_command())
+            .unwrap();
+
+        let error = manager
+            .identify_device(Duration::from_millis(500))
+            .unwrap_err();
+        assert!(error.contains("지원하지 않음"));
+    }
+
+    #[test]
+    fn handshake_blocks_commands_until_matching_identify() {
+        // 식별 명령을 받으면 프로토콜 버전 3을 보고하는 컨트롤러
+        let (addr, _rx) = mock_responder(Vec::new(), |bytes| {
+            if bytes.first() == Some(&0xA0) {
+                vec![0xA0, 3, 254]
+            } else {
+                Vec::new()
+            }
+        });
+        let manager = SerialPortManager::new();
+        manager.initialize_tcp(&addr).unwrap();
+        assert!(manager.set_require_handshake(true, 3).is_err());
+        manager
+            .register_command("identify", identify_command())
+            .unwrap();
+        manager.set_require_handshake(true, 3).unwrap();
+
+        // 식별 전에는 전송 거부
+        let error = manager.send_command(&state(90)).err().unwrap();
+        assert!(error.contains("식별 확인"));
+        assert!(manager.last_command().is_none());
+
+        manager.identify_device(Duration::from_millis(100)).unwrap();
+        manager.send_command(&state(90)).unwrap();
+
+        // 다른 버전을 기대하면 식별이 실패하고 다시 거부
+        manager.set_require_handshake(true, 4).unwrap();
+        let error = manager
+            .identify_device(Duration::from_millis(100))
+            .unwrap_err();
+        assert!(error.contains("기대 4, 보고 3"));
+        let error = manager.send_command(&state(91)).err().unwrap();
+        assert!(error.contains("기대 버전 4"));
+
+        // 다시 연결하면 식별 결과를 초기화
+        manager.set_require_handshake(true, 3).unwrap();
+        manager.identify_device(Duration::from_millis(100)).unwrap();
+        manager.send_command(&state(92)).unwrap();
+        manager
+            .initialize_tcp(&mock_controller(Vec::new()).0)
+            .unwrap();
+        assert!(manager.send_command(&state(93)).is_err());
+
+        manager.set_require_handshake(false, 0).unwrap();
+        manager.send_command(&state(93)).unwrap();
+    }
+
+    // 고장 비트를 위치 14에 담은 상태 패킷
+    fn fault_frame(bits: u8) -> Vec<u8> {
+        let mut frame = encode_command(&state(90), IoLayout::Expanded);
+        frame.insert(14, bits);
+        frame
+    }
+
+    // 고장 해제 명령을 받으면 replies를 한 번 보내는 컨트롤러에 연결
+    fn fault_manager(replies: Vec<u8>) -> SerialPortManager {
+        let (addr, _rx) = mock_responder(Vec::new(), move |bytes| {
+            if bytes == [0xB0, 254] {
+                replies.clone()
+            } else {
+                Vec::new()
+            }
+        });
+        let manager = SerialPortManager::new();
+        manager.initialize_tcp(&addr).unwrap();
+        manager
+            .register_command(
+                "clear_fault",
+                CustomCommand {
+                    opcode: 0xB0,
+                    fields: Vec::new(),
+                },
+            )
+            .unwrap();
+        manager.set_fault_offset(Some(14)).unwrap();
+        manager
+    }
+
+    #[test]
+    fn clear_controller_fault_waits_for_latch_release() {
+        let replies = [fault_frame(0x04), fault_frame(0x00)].concat();
+        let manager = fault_manager(replies);
+        manager.clear_controller_fault().unwrap();
+    }
+
+    #[test]
+    fn clear_controller_fault_reports_remaining_bits() {
+        let manager = fault_manager(fault_frame(0x05));
+        let error = manager.clear_controller_fault().unwrap_err();
+        assert!(error.contains("0x05"));
+
+        manager.set_fault_offset(None).unwrap();
+        assert!(manager.clear_controller_fault().is_err());
     }
 }
@@ -0,0 +1,220 @@
+// src-tauri/sessions.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 보관할 연결 기록 수 상한 (넘으면 오래된 기록부터 삭제)
+const MAX_SESSIONS: usize = 100;
+
+// 연결 종료 사유
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CloseReason {
+    // disconnect_port로 직접 종료
+    Manual,
+    // 읽기 중 장치나 원격 연결이 끊김
+    Disconnected,
+    // 연결 확인 실패 등 오류로 종료
+    Error,
+    // 새 연결로 교체
+    Replaced,
+    // 종료 기록 전에 앱이 끝남
+    Interrupted,
+}
+
+// 연결 기록 (close_reason이 None이면 아직 연결 중)
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SessionRecord {
+    pub opened_unix_ms: u64,
+    // 시리얼 포트 이름 또는 TCP 주소
+    pub port_name: String,
+    // TCP 연결은 None
+    pub baud_rate: Option<u32>,
+    pub duration_ms: Option<u64>,
+    pub close_reason: Option<CloseReason>,
+    pub frames_received: u64,
+    pub frames_sent: u64,
+}
+
+#[derive(Default)]
+struct HistoryInner {
+    records: VecDeque<SessionRecord>,
+    // 현재 연결 시작 시점의 누적 (수신, 송신) 패킷 수
+    open_counts: Option<(u64, u64)>,
+    // 기록을 저장할 파일 (None이면 메모리에만 보관)
+    path: Option<PathBuf>,
+}
+
+// 연결 기록 목록
+#[derive(Default)]
+pub struct SessionHistory {
+    inner: Mutex<HistoryInner>,
+}
+
+fn unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl SessionHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn inner(&self) -> MutexGuard<'_, HistoryInner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // 저장 파일 지정 및 이전 기록 불러오기 (파일이 없으면 새로 시작)
+    // 종료 기록 없이 남은 연결은 앱이 먼저 끝난 것으로 표시
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let mut loaded: VecDeque<SessionRecord> = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("연결 기록 파일 형식이 잘못되었습니다: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => VecDeque::new(),
+            Err(e) => {
+                return Err(format!(
+                    "연결 기록 파일을 읽을 수 없습니다 ({}): {}",
+                    path.display(),
+                    e
+                ))
+            }
+        };
+        for record in loaded.iter_mut() {
+            if record.close_reason.is_none() {
+                record.close_reason = Some(CloseReason::Interrupted);
+            }
+        }
+
+        let mut inner = self.inner();
+        // 불러오기 전에 시작된 기록은 뒤에 이어 붙임
+        loaded.extend(inner.records.drain(..));
+        while loaded.len() > MAX_SESSIONS {
+            loaded.pop_front();
+        }
+        inner.records = loaded;
+        inner.path = Some(path);
+        save(&inner);
+        Ok(())
+    }
+
+    // 연결 시작 기록 (frames: 현재 누적 (수신, 송신) 패킷 수, 진행 중인 연결은 교체로 종료)
+    pub fn open(&self, port_name: &str, baud_rate: Option<u32>, frames: (u64, u64)) {
+        let mut inner = self.inner();
+        finish(&mut inner, CloseReason::Replaced, frames);
+        inner.records.push_back(SessionRecord {
+            opened_unix_ms: unix_ms(),
+            port_name: port_name.to_string(),
+            baud_rate,
+            duration_ms: None,
+            close_reason: None,
+            frames_received: 0,
+            frames_sent: 0,
+        });
+        inner.open_counts = Some(frames);
+        if inner.records.len() > MAX_SESSIONS {
+            inner.records.pop_front();
+        }
+        save(&inner);
+    }
+
+    // 연결 종료 기록 (진행 중인 연결이 없으면 무시)
+    pub fn close(&self, reason: CloseReason, frames: (u64, u64)) {
+        let mut inner = self.inner();
+        if finish(&mut inner, reason, frames) {
+            save(&inner);
+        }
+    }
+
+    // 연결 기록 목록 (오래된 순)
+    pub fn list(&self) -> Vec<SessionRecord> {
+        self.inner().records.iter().cloned().collect()
+    }
+}
+
+// 진행 중인 연결 종료 처리 (종료한 연결이 있으면 true)
+fn finish(inner: &mut HistoryInner, reason: CloseReason, frames: (u64, u64)) -> bool {
+    let Some((received_at_open, sent_at_open)) = inner.open_counts.take() else {
+        return false;
+    };
+    let Some(record) = inner.records.back_mut() else {
+        return false;
+    };
+    record.duration_ms = Some(unix_ms().saturating_sub(record.opened_unix_ms));
+    record.close_reason = Some(reason);
+    record.frames_received = frames.0.saturating_sub(received_at_open);
+    record.frames_sent = frames.1.saturating_sub(sent_at_open);
+    true
+}
+
+// 기록 파일 저장 (실패해도 연결 처리는 계속)
+fn save(inner: &HistoryInner) {
+    let Some(path) = &inner.path else {
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(&inner.records).map_err(|e| e.to_string()))
+        .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        println!("Saving session history failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closing_session_records_reason_and_frame_counts() {
+        let history = SessionHistory::new();
+        history.open("/dev/ttyUSB0", Some(115200), (10, 4));
+        assert_eq!(history.list()[0].close_reason, None);
+
+        history.close(CloseReason::Manual, (15, 6));
+        // 진행 중인 연결이 없으면 무시
+        history.close(CloseReason::Error, (20, 8));
+        let records = history.list();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].close_reason, Some(CloseReason::Manual));
+        assert_eq!((records[0].frames_received, records[0].frames_sent), (5, 2));
+        assert!(records[0].duration_ms.is_some());
+
+        history.open("127.0.0.1:9000", None, (15, 6));
+        history.open("/dev/ttyUSB1", Some(9600), (15, 6));
+        let reasons: Vec<_> = history.list().iter().map(|r| r.close_reason).collect();
+        assert_eq!(
+            reasons,
+            vec![Some(CloseReason::Manual), Some(CloseReason::Replaced), None]
+        );
+    }
+
+    #[test]
+    fn reload_marks_unfinished_session_interrupted() {
+        let path =
+            std::env::temp_dir().join(format!("robot-arm-sessions-{}.json", std::process::id()));
+        let history = SessionHistory::new();
+        history.load(path.clone()).unwrap();
+        history.open("/dev/ttyUSB0", Some(115200), (0, 0));
+        history.close(CloseReason::Disconnected, (3, 1));
+        history.open("/dev/ttyUSB0", Some(115200), (3, 1));
+
+        let restarted = SessionHistory::new();
+        restarted.load(path.clone()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let reasons: Vec<_> = restarted.list().iter().map(|r| r.close_reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                Some(CloseReason::Disconnected),
+                Some(CloseReason::Interrupted)
+            ]
+        );
+    }
+}
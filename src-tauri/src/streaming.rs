@@ -0,0 +1,559 @@
+// src-tauri/streaming.rs
+
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::pipe::PipeStream;
+use crate::serial::{
+    emit_stuck_warning, lock, AppState, RobotState, SerialPortManager, IO_TIMEOUT,
+};
+
+// 상태 스트림을 자동 중지할 이벤트 전송 연속 실패 횟수
+const MAX_EMIT_FAILURES: u32 = 20;
+
+// 상태 스트림 읽기 오류 처리 방식
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ReadErrorMode {
+    // 오류를 무시하고 계속 읽기
+    Continue,
+    // 오류 이벤트를 보내고 계속 읽기
+    Emit,
+    // 오류 이벤트를 보내고 연속 오류가 max_consecutive에 도달하면 스트림 중지
+    Stop,
+}
+
+// 상태 스트림 읽기 오류 정책
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct ReadErrorPolicy {
+    pub mode: ReadErrorMode,
+    pub max_consecutive: u32,
+}
+
+// 상태 스트림 종료 사유
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub enum StreamEnd {
+    // stop_state_stream 또는 새 스트림 시작으로 중지
+    Stopped,
+    // 연속 읽기 오류로 중지
+    TooManyErrors(String),
+    // 상태 이벤트 전송이 연속으로 실패해 자동 중지 (창이 닫힌 경우 등, 마지막 오류 포함)
+    EmitFailed(String),
+}
+
+// 주기적으로 기록한 지표 샘플 구조체 정의
+#[derive(Serialize, Clone)]
+pub struct MetricsSample {
+    pub unix_ms: u64,
+    pub connected: bool,
+    pub bytes_read: u64,
+    pub frames_received: u64,
+    pub staleness_ms: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+// ndjson 로그 한 줄 구조체 정의
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LoggedState {
+    pub received_unix_ms: u64,
+    // 컨트롤러가 보고한 밀리초 타임스탬프 (호스트 수신 시각과 따로 기록)
+    #[serde(default)]
+    pub device_timestamp: Option<u32>,
+    pub state: RobotState,
+}
+
+impl SerialPortManager {
+    // 상태 스트림 읽기 오류 정책 설정 함수
+    pub fn set_read_error_policy(&self, policy: ReadErrorPolicy) -> Result<(), String> {
+        if policy.mode == ReadErrorMode::Stop && policy.max_consecutive == 0 {
+            return Err("중지 정책에는 1 이상의 연속 오류 횟수가 필요합니다.".into());
+        }
+        *lock(&self.read_error_policy) = policy;
+        Ok(())
+    }
+
+    // 새 상태 스트림 토큰 발급 함수 (이전 스트림은 중지됨)
+    pub fn start_stream_token(&self) -> u64 {
+        self.stream_generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    // 실행 중인 상태 스트림 중지 함수
+    pub fn stop_state_stream(&self) {
+        self.stream_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // 상태 스트림 루프 (토큰이 유효한 동안 계속 읽고 정책에 따라 오류 처리)
+    // on_state는 이벤트 전송 결과를 반환하며, 연속으로 실패하면 받는 쪽이 없는 것으로 보고 중지
+    pub fn stream_states<F, G>(&self, token: u64, mut on_state: F, mut on_error: G) -> StreamEnd
+    where
+        F: FnMut(&RobotState) -> Result<(), String>,
+        G: FnMut(&str),
+    {
+        let mut consecutive_errors = 0;
+        let mut emit_failures = 0;
+        while self.stream_generation.load(Ordering::SeqCst) == token {
+            match self.read_data() {
+                Ok(robot_state) => {
+                    consecutive_errors = 0;
+                    match on_state(&robot_state) {
+                        Ok(()) => emit_failures = 0,
+                        Err(e) => {
+                            emit_failures += 1;
+                            if emit_failures >= MAX_EMIT_FAILURES {
+                                println!(
+                                    "State stream paused after {} failed emits: {}",
+                                    emit_failures, e
+                                );
+                                return StreamEnd::EmitFailed(e);
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    consecutive_errors += 1;
+                    // 정책은 스트림 실행 중에도 바꿀 수 있음
+                    let policy = *lock(&self.read_error_policy);
+                    if policy.mode != ReadErrorMode::Continue {
+                        on_error(&e);
+                    }
+                    if policy.mode == ReadErrorMode::Stop
+                        && consecutive_errors >= policy.max_consecutive
+                    {
+                        return StreamEnd::TooManyErrors(e);
+                    }
+                    // 포트가 없을 때처럼 즉시 실패하는 경우 바쁜 대기 방지
+                    thread::sleep(IO_TIMEOUT);
+                }
+            }
+        }
+        StreamEnd::Stopped
+    }
+
+    // 로컬 소켓 스트림 시작 함수 (기존 스트림은 교체)
+    pub fn start_pipe_stream(&self, path: &str) -> Result<(), String> {
+        let mut pipe_stream = lock(&self.pipe_stream);
+        // 같은 경로로 다시 시작할 수 있도록 기존 소켓을 먼저 정리
+        *pipe_stream = None;
+        *pipe_stream = Some(PipeStream::start(path)?);
+        Ok(())
+    }
+
+    // 로컬 소켓 스트림 중지 함수
+    pub fn stop_pipe_stream(&self) -> Result<(), String> {
+        lock(&self.pipe_stream)
+            .take()
+            .map(|_| ())
+            .ok_or_else(|| "실행 중인 파이프 스트림이 없습니다.".into())
+    }
+
+    // ndjson 로그 시작 함수 (기존 로그는 닫고 교체, 파일은 새로 씀)
+    pub fn start_ndjson_log(&self, path: &str) -> Result<(), String> {
+        let file = File::create(path)
+            .map_err(|e| format!("로그 파일을 만들 수 없습니다 ({}): {}", path, e))?;
+        let previous = lock(&self.ndjson_log).replace(BufWriter::new(file));
+        if let Some(mut previous) = previous {
+            let _ = previous.flush();
+        }
+        Ok(())
+    }
+
+    // ndjson 로그 중지 함수 (남은 버퍼를 기록하고 닫음)
+    pub fn stop_ndjson_log(&self) -> Result<(), String> {
+        let mut log = lock(&self.ndjson_log)
+            .take()
+            .ok_or_else(|| "실행 중인 ndjson 로그가 없습니다.".to_string())?;
+        log.flush()
+            .map_err(|e| format!("로그 파일 기록 실패: {}", e))
+    }
+
+    // 텔레메트리 출력 간격 설정 함수 (1이면 모든 상태 출력)
+    pub fn set_telemetry_decimation(&self, n: u32) -> Result<(), String> {
+        if n == 0 {
+            return Err("텔레메트리 출력 간격은 1 이상이어야 합니다.".into());
+        }
+        self.telemetry_decimation.store(n, Ordering::SeqCst);
+        self.telemetry_counter.store(0, Ordering::SeqCst);
+        Ok(())
+    }
+
+    // 수신 상태를 JSON 한 줄로 로컬 소켓과 ndjson 로그에 기록하는 함수
+    // (출력 간격만큼 건너뜀, 경고와 오류는 상태와 별도로 전달되므로 영향 없음)
+    pub(crate) fn publish_state(&self, robot_state: &RobotState) {
+        let count = self.telemetry_counter.fetch_add(1, Ordering::SeqCst);
+        if !count.is_multiple_of(self.telemetry_decimation.load(Ordering::SeqCst) as u64) {
+            return;
+        }
+        if let Some(pipe_stream) = lock(&self.pipe_stream).as_ref() {
+            if let Ok(line) = serde_json::to_string(robot_state) {
+                pipe_stream.publish(&line);
+            }
+        }
+        let mut ndjson_log = lock(&self.ndjson_log);
+        if let Some(log) = ndjson_log.as_mut() {
+            let entry = LoggedState {
+                received_unix_ms: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0),
+                device_timestamp: robot_state.device_timestamp,
+                state: robot_state.clone(),
+            };
+            let written = serde_json::to_writer(&mut *log, &entry)
+                .map_err(|e| e.to_string())
+                .and_then(|_| log.write_all(b"\n").map_err(|e| e.to_string()));
+            // 디스크 오류 시 매 패킷마다 실패하지 않도록 로그 중지
+            if let Err(e) = written {
+                *ndjson_log = None;
+                *lock(&self.last_error) = Some(format!("로그 파일 기록 실패: {}", e));
+            }
+        }
+    }
+
+    // 지표 샘플 보관 개수 설정 함수 (이전 샘플링 중지용 토큰 반환)
+    pub fn set_metrics_history_len(&self, len: usize) -> u64 {
+        self.metrics_history_len.store(len, Ordering::SeqCst);
+        let mut history = lock(&self.metrics_history);
+        while history.len() > len {
+            history.pop_front();
+        }
+        self.sampling_generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    // 지표 주기 샘플링 중지 함수 (기록은 유지)
+    pub fn stop_metrics_sampling(&self) {
+        self.sampling_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // 토큰 발급 이후 샘플링 설정 변경 여부 확인 함수
+    pub fn is_sampling_stopped(&self, token: u64) -> bool {
+        self.sampling_generation.load(Ordering::SeqCst) != token
+    }
+
+    // 현재 지표 샘플 기록 함수
+    pub fn record_metrics_sample(&self) {
+        let len = self.metrics_history_len.load(Ordering::SeqCst);
+        if len == 0 {
+            return;
+        }
+        let health = self.health();
+        let sample = MetricsSample {
+            unix_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            connected: health.connected,
+            bytes_read: self.metrics.bytes_read.load(Ordering::Relaxed),
+            frames_received: self.metrics.frames_received.load(Ordering::Relaxed),
+            staleness_ms: health.staleness_ms,
+            last_error: health.last_error,
+        };
+        let mut history = lock(&self.metrics_history);
+        if history.len() >= len {
+            history.pop_front();
+        }
+        history.push_back(sample);
+    }
+
+    // 지표 샘플 기록 조회 함수 (오래된 순)
+    pub fn metrics_history(&self) -> Vec<MetricsSample> {
+        lock(&self.metrics_history).iter().cloned().collect()
+    }
+}
+
+// 지표 주기 샘플링 설정 커맨드 (interval_ms 또는 buffer_len이 0이면 중지)
+#[tauri::command]
+pub fn set_metrics_sampling(
+    state: State<'_, AppState>,
+    interval_ms: u64,
+    buffer_len: usize,
+) -> Result<(), String> {
+    let manager = state.serial_manager.clone();
+    let token = manager.set_metrics_history_len(buffer_len);
+    if interval_ms == 0 || buffer_len == 0 {
+        return Ok(());
+    }
+
+    let task = {
+        let manager = manager.clone();
+        state.tasks.register("지표 주기 샘플링", move || {
+            manager.stop_metrics_sampling()
+        })
+    };
+
+    thread::spawn(move || {
+        let _task = task;
+        while !manager.is_sampling_stopped(token) {
+            manager.record_metrics_sample();
+            thread::sleep(Duration::from_millis(interval_ms));
+        }
+    });
+
+    Ok(())
+}
+
+// 텔레메트리 출력 간격 설정 커맨드 (n개 상태마다 하나만 파이프 스트림/ndjson 로그로 출력)
+#[tauri::command]
+pub fn set_telemetry_decimation(state: State<'_, AppState>, n: u32) -> Result<(), String> {
+    state.serial_manager.set_telemetry_decimation(n)
+}
+
+// 상태 스트림 시작 커맨드 (robot_state 이벤트로 수신 상태 전달)
+#[tauri::command]
+pub fn start_state_stream(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    if !state.serial_manager.is_connected() {
+        return Err("시리얼 포트가 초기화되지 않았습니다.".into());
+    }
+    let manager = state.serial_manager.clone();
+    let token = manager.start_stream_token();
+    let task = {
+        let manager = manager.clone();
+        state
+            .tasks
+            .register("상태 스트림", move || manager.stop_state_stream())
+    };
+
+    thread::spawn(move || {
+        let _task = task;
+        let end = manager.stream_states(
+            token,
+            |robot_state| {
+                let emitted = app
+                    .emit("robot_state", robot_state.clone())
+                    .map_err(|e| e.to_string());
+                emit_stuck_warning(&app, &manager);
+                emitted
+            },
+            |e| {
+                let _ = app.emit("robot_state_error", format!("로봇 상태 읽기 실패: {}", e));
+            },
+        );
+        let _ = app.emit("state_stream_stopped", end);
+    });
+
+    Ok(())
+}
+
+// 상태 스트림 중지 커맨드
+#[tauri::command]
+pub fn stop_state_stream(state: State<'_, AppState>) -> Result<(), String> {
+    state.serial_manager.stop_state_stream();
+    Ok(())
+}
+
+// 상태 스트림 읽기 오류 정책 설정 커맨드
+#[tauri::command]
+pub fn set_read_error_policy(
+    state: State<'_, AppState>,
+    mode: ReadErrorMode,
+    max_consecutive: u32,
+) -> Result<(), String> {
+    state.serial_manager.set_read_error_policy(ReadErrorPolicy {
+        mode,
+        max_consecutive,
+    })
+}
+
+// 로컬 소켓 상태 스트림 시작 커맨드
+#[tauri::command]
+pub fn start_pipe_stream(state: State<'_, AppState>, path: String) -> Result<(), String> {
+    state.serial_manager.start_pipe_stream(&path)
+}
+
+// 수신 상태 ndjson 로그 시작 커맨드
+#[tauri::command]
+pub fn start_ndjson_log(state: State<'_, AppState>, path: String) -> Result<(), String> {
+    state.serial_manager.start_ndjson_log(&path)
+}
+
+// 수신 상태 ndjson 로그 중지 커맨드
+#[tauri::command]
+pub fn stop_ndjson_log(state: State<'_, AppState>) -> Result<(), String> {
+    state.serial_manager.stop_ndjson_log()
+}
+
+// 로컬 소켓 상태 스트림 중지 커맨드
+#[tauri::command]
+pub fn stop_pipe_stream(state: State<'_, AppState>) -> Result<(), String> {
+    state.serial_manager.stop_pipe_stream()
+}
+
+// 지표 샘플 기록 조회 커맨드
+#[tauri::command]
+pub fn get_metrics_history(state: State<'_, AppState>) -> Result<Vec<MetricsSample>, String> {
+    Ok(state.serial_manager.metrics_history())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{encode_command, IoLayout};
+    use crate::test_support::{connected_manager, state, voltage_frame, wait_until};
+    use std::sync::Arc;
+
+    #[test]
+    fn metrics_history_evicts_oldest_samples() {
+        let manager = SerialPortManager::new();
+        // 보관 길이를 정하기 전에는 기록하지 않음
+        manager.record_metrics_sample();
+        assert!(manager.metrics_history().is_empty());
+
+        let token = manager.set_metrics_history_len(3);
+        for _ in 0..5 {
+            manager.record_metrics_sample();
+            thread::sleep(Duration::from_millis(2));
+        }
+        let history = manager.metrics_history();
+        assert_eq!(history.len(), 3);
+        assert!(history[0].unix_ms <= history[2].unix_ms);
+        assert!(!manager.is_sampling_stopped(token));
+
+        let shorter = manager.set_metrics_history_len(1);
+        assert!(manager.is_sampling_stopped(token));
+        assert!(!manager.is_sampling_stopped(shorter));
+        assert_eq!(manager.metrics_history().len(), 1);
+    }
+
+    #[test]
+    fn stop_policy_ends_stream_after_consecutive_errors() {
+        let mut stream = encode_command(&state(1), IoLayout::Expanded);
+        // 테일이 잘못된 패킷 뒤로 데이터가 끊김
+        stream.extend([253, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7]);
+        let (manager, _rx) = connected_manager(stream);
+        assert!(manager
+            .set_read_error_policy(ReadErrorPolicy {
+                mode: ReadErrorMode::Stop,
+                max_consecutive: 0,
+            })
+            .is_err());
+        manager
+            .set_read_error_policy(ReadErrorPolicy {
+                mode: ReadErrorMode::Stop,
+                max_consecutive: 3,
+            })
+            .unwrap();
+
+        let (mut states, mut errors) = (0, 0);
+        let end = manager.stream_states(
+            manager.start_stream_token(),
+            |_| {
+                states += 1;
+                Ok(())
+            },
+            |_| errors += 1,
+        );
+        assert!(matches!(end, StreamEnd::TooManyErrors(_)));
+        assert_eq!((states, errors), (1, 3));
+    }
+
+    #[test]
+    fn continue_policy_streams_until_stopped() {
+        // 테일이 잘못된 패킷만 계속 들어옴
+        let mut corrupted = encode_command(&state(1), IoLayout::Expanded);
+        corrupted[14] = 7;
+        let manager = Arc::new(connected_manager(corrupted.repeat(5)).0);
+        manager
+            .set_read_error_policy(ReadErrorPolicy {
+                mode: ReadErrorMode::Continue,
+                max_consecutive: 0,
+            })
+            .unwrap();
+        let token = manager.start_stream_token();
+        let streaming = manager.clone();
+        let handle = thread::spawn(move || streaming.stream_states(token, |_| Ok(()), |_| {}));
+
+        // 연속 오류가 쌓여도 끝나지 않다가 중지 요청으로만 끝남
+        wait_until(|| manager.metrics.malformed_frames.load(Ordering::Relaxed) >= 5);
+        manager.stop_state_stream();
+        assert_eq!(handle.join().unwrap(), StreamEnd::Stopped);
+    }
+
+    #[test]
+    fn ndjson_log_parses_back_into_states() {
+        let stream: Vec<u8> = [1, 2]
+            .into_iter()
+            .flat_map(|joint| encode_command(&state(joint), IoLayout::Expanded))
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        let path =
+            std::env::temp_dir().join(format!("robot-arm-states-{}.ndjson", std::process::id()));
+        assert!(manager.stop_ndjson_log().is_err());
+        manager.start_ndjson_log(path.to_str().unwrap()).unwrap();
+        manager.read_data().unwrap();
+        manager.read_data().unwrap();
+        manager.stop_ndjson_log().unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let logged: Vec<LoggedState> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(logged.len(), 2);
+        assert_eq!((logged[0].state.joint_1, logged[1].state.joint_6), (1, 2));
+        assert!(logged[0].received_unix_ms > 0);
+    }
+
+    #[test]
+    fn decimation_thins_sinks_but_keeps_fault_events() {
+        let stream: Vec<u8> = (1..=12)
+            .flat_map(|joint| voltage_frame(joint, if joint == 7 { 900 } else { 1200 }))
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        manager.set_voltage_offset(Some(14)).unwrap();
+        manager.set_voltage_threshold(Some(1000));
+        assert!(manager.set_telemetry_decimation(0).is_err());
+        manager.set_telemetry_decimation(5).unwrap();
+        let path =
+            std::env::temp_dir().join(format!("robot-arm-decimated-{}.ndjson", std::process::id()));
+
+        manager.start_ndjson_log(path.to_str().unwrap()).unwrap();
+        for _ in 0..12 {
+            manager.read_data().unwrap();
+        }
+        manager.stop_ndjson_log().unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let logged: Vec<u8> = text
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<LoggedState>(line)
+                    .unwrap()
+                    .state
+                    .joint_1
+            })
+            .collect();
+        assert_eq!(logged, vec![1, 6, 11]);
+        // 저전압 경고는 건너뛴 상태에서도 전달
+        assert_eq!(manager.take_low_voltage_warning(), Some(900));
+    }
+
+    #[test]
+    fn state_stream_pauses_after_repeated_emit_failures() {
+        let stream: Vec<u8> = (0..30)
+            .flat_map(|_| encode_command(&state(90), IoLayout::Expanded))
+            .collect();
+        let (manager, _rx) = connected_manager(stream);
+        let token = manager.start_stream_token();
+
+        let mut attempts = 0;
+        let end = manager.stream_states(
+            token,
+            |_| {
+                attempts += 1;
+                // 처음 몇 번은 성공해 연속 실패 횟수가 초기화되는지 확인
+                if attempts <= 5 {
+                    Ok(())
+                } else {
+                    Err("window closed".into())
+                }
+            },
+            |_| {},
+        );
+        assert_eq!(end, StreamEnd::EmitFailed("window closed".into()));
+        assert_eq!(attempts, 5 + MAX_EMIT_FAILURES);
+    }
+}
@@ -0,0 +1,131 @@
+// src-tauri/tasks.rs
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+// 실행 중인 백그라운드 작업 정보
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct TaskInfo {
+    pub id: u64,
+    pub description: String,
+}
+
+struct TaskEntry {
+    description: String,
+    cancel: Box<dyn Fn() + Send>,
+}
+
+// 백그라운드 작업 등록부
+#[derive(Default)]
+pub struct TaskRegistry {
+    next_id: AtomicU64,
+    tasks: Mutex<BTreeMap<u64, TaskEntry>>,
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tasks(&self) -> MutexGuard<'_, BTreeMap<u64, TaskEntry>> {
+        self.tasks.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // 작업 등록 함수 (반환된 가드가 drop되면 목록에서 제거)
+    pub fn register<F>(self: &Arc<Self>, description: impl Into<String>, cancel: F) -> TaskGuard
+    where
+        F: Fn() + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        self.tasks().insert(
+            id,
+            TaskEntry {
+                description: description.into(),
+                cancel: Box::new(cancel),
+            },
+        );
+        TaskGuard {
+            id,
+            registry: self.clone(),
+        }
+    }
+
+    // 실행 중인 작업 목록 (등록순)
+    pub fn list(&self) -> Vec<TaskInfo> {
+        self.tasks()
+            .iter()
+            .map(|(&id, entry)| TaskInfo {
+                id,
+                description: entry.description.clone(),
+            })
+            .collect()
+    }
+
+    // 작업 취소 함수 (목록에서 바로 제거하고 작업에 중지 요청)
+    pub fn cancel(&self, id: u64) -> Result<(), String> {
+        let entry = self
+            .tasks()
+            .remove(&id)
+            .ok_or_else(|| format!("실행 중인 작업이 없습니다: {}", id))?;
+        (entry.cancel)();
+        Ok(())
+    }
+
+    // 모든 작업 취소 함수
+    pub fn cancel_all(&self) {
+        let entries = std::mem::take(&mut *self.tasks());
+        for entry in entries.into_values() {
+            (entry.cancel)();
+        }
+    }
+}
+
+// 작업 스레드가 끝날 때 등록을 해제하는 가드
+pub struct TaskGuard {
+    id: u64,
+    registry: Arc<TaskRegistry>,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.registry.tasks().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    #[test]
+    fn started_task_is_listed_until_cancelled() {
+        let registry = Arc::new(TaskRegistry::new());
+        let cancels = Arc::new(AtomicU32::new(0));
+        let counter = cancels.clone();
+        let _move = registry.register("move", move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let counter = cancels.clone();
+        let _stream = registry.register("stream", move || {
+            counter.fetch_add(10, Ordering::SeqCst);
+        });
+        let finished = registry.register("replay", || {});
+
+        let tasks = registry.list();
+        let descriptions: Vec<&str> = tasks.iter().map(|task| task.description.as_str()).collect();
+        assert_eq!(descriptions, vec!["move", "stream", "replay"]);
+
+        registry.cancel(tasks[0].id).unwrap();
+        assert_eq!(cancels.load(Ordering::SeqCst), 1);
+        assert!(registry.cancel(tasks[0].id).is_err());
+        // 끝난 작업은 가드가 drop되면서 사라짐
+        drop(finished);
+        assert_eq!(registry.list(), vec![tasks[1].clone()]);
+
+        registry.cancel_all();
+        assert_eq!(cancels.load(Ordering::SeqCst), 11);
+        assert!(registry.list().is_empty());
+    }
+}
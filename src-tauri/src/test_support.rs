@@ -0,0 +1,202 @@
+// src-tauri/test_support.rs
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+use serialport::{SerialPort, TTYPort};
+
+use crate::codec::{encode_command, IoLayout};
+use crate::serial::{RobotState, SerialPortManager};
+
+// 모의 컨트롤러가 동작하는 시간이자 테스트가 수신을 기다리는 최대 시간
+const MOCK_LIFETIME: Duration = Duration::from_secs(5);
+
+// 모든 관절이 같은 값이고 입출력이 꺼진 로봇 상태
+pub fn state(joint: u8) -> RobotState {
+    RobotState {
+        joint_1: joint,
+        joint_2: joint,
+        joint_3: joint,
+        joint_4: joint,
+        joint_5: joint,
+        joint_6: joint,
+        digital_input_1: false,
+        digital_input_2: false,
+        digital_input_3: false,
+        digital_output_1: false,
+        digital_output_2: false,
+        digital_output_3: false,
+        robot_speed: 50,
+        device_timestamp: None,
+        supply_voltage: None,
+        fault_bits: None,
+    }
+}
+
+// 관절 1만 `joint_1`이고 나머지는 100인 상태 패킷
+pub fn joint_1_frame(joint_1: u8) -> Vec<u8> {
+    let reply = RobotState {
+        joint_1,
+        ..state(100)
+    };
+    encode_command(&reply, IoLayout::Expanded)
+}
+
+// 위치 14에 리틀 엔디언 전원 전압(mV)을 붙인 상태 패킷
+pub fn voltage_frame(joint: u8, millivolts: u16) -> Vec<u8> {
+    let mut frame = encode_command(&state(joint), IoLayout::Expanded);
+    frame.pop();
+    frame.extend(millivolts.to_le_bytes());
+    frame.push(254);
+    frame
+}
+
+// 연결 직후 `reply`를 보내고 이후 수신한 바이트를 전달하는 모의 TCP 컨트롤러
+pub fn mock_controller(reply: Vec<u8>) -> (String, Receiver<Vec<u8>>) {
+    mock_responder(reply, |_| Vec::new())
+}
+
+// 수신한 바이트마다 `respond`가 돌려준 응답을 보내는 모의 TCP 컨트롤러
+pub fn mock_responder(
+    greeting: Vec<u8>,
+    mut respond: impl FnMut(&[u8]) -> Vec<u8> + Send + 'static,
+) -> (String, Receiver<Vec<u8>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.write_all(&greeting).unwrap();
+        // 연결 직후 응답을 다 보냈음을 알림 (빈 조각이라 수신 바이트에는 영향 없음)
+        let _ = tx.send(Vec::new());
+        stream
+            .set_read_timeout(Some(Duration::from_millis(20)))
+            .unwrap();
+        let started = Instant::now();
+        while started.elapsed() < MOCK_LIFETIME {
+            let mut buf = [0u8; 256];
+            match stream.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    let reply = respond(&buf[..n]);
+                    if !reply.is_empty() && stream.write_all(&reply).is_err() {
+                        break;
+                    }
+                    // 받는 쪽이 없어도 응답은 계속
+                    let _ = tx.send(buf[..n].to_vec());
+                }
+                Err(_) => {}
+            }
+        }
+    });
+    (addr, rx)
+}
+
+// 연결 직후부터 `frames`를 `interval` 간격으로 보내는 모의 TCP 컨트롤러
+pub fn paced_controller(frames: Vec<Vec<u8>>, interval: Duration) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        for frame in frames {
+            if stream.write_all(&frame).is_err() {
+                return;
+            }
+            thread::sleep(interval);
+        }
+        // 받는 쪽이 남은 데이터를 읽을 때까지 연결 유지
+        thread::sleep(MOCK_LIFETIME);
+    });
+    addr
+}
+
+// 받은 기본 배치 명령 패킷을 그대로 상태 패킷으로 돌려주는 응답 (명령대로 움직이는 팔)
+pub fn echo_responder(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .chunks(15)
+        .filter(|frame| frame.len() == 15 && frame[0] == 253 && frame[14] == 254)
+        .flatten()
+        .copied()
+        .collect()
+}
+
+// 모의 컨트롤러에 TCP로 연결된 관리자
+pub fn connected_manager(reply: Vec<u8>) -> (SerialPortManager, Receiver<Vec<u8>>) {
+    let (addr, rx) = mock_controller(reply);
+    let manager = SerialPortManager::new();
+    manager.initialize_tcp(&addr).unwrap();
+    // 연결 직후 응답이 수신 버퍼에 들어올 때까지 대기
+    rx.recv_timeout(MOCK_LIFETIME).unwrap();
+    (manager, rx)
+}
+
+// 모의 컨트롤러가 `count`바이트 이상 받을 때까지 기다려 반환
+pub fn received(rx: &Receiver<Vec<u8>>, count: usize) -> Vec<u8> {
+    let mut all = Vec::new();
+    while all.len() < count {
+        match rx.recv_timeout(MOCK_LIFETIME) {
+            Ok(bytes) => all.extend(bytes),
+            Err(_) => panic!("{}바이트를 기다렸지만 {}바이트만 수신", count, all.len()),
+        }
+    }
+    all
+}
+
+// 모의 컨트롤러가 `marker`로 끝나는 바이트를 받을 때까지 기다려 표식 앞부분만 반환
+// (표식 명령보다 먼저 보낸 것이 없는지 시간 창 없이 확인)
+pub fn received_before(rx: &Receiver<Vec<u8>>, marker: &[u8]) -> Vec<u8> {
+    let mut all = Vec::new();
+    while !all.ends_with(marker) {
+        match rx.recv_timeout(MOCK_LIFETIME) {
+            Ok(bytes) => all.extend(bytes),
+            Err(_) => panic!("표식 패킷을 받지 못함 ({}바이트 수신)", all.len()),
+        }
+    }
+    all.truncate(all.len() - marker.len());
+    all
+}
+
+// `condition`이 참이 될 때까지 대기
+pub fn wait_until(mut condition: impl FnMut() -> bool) {
+    let deadline = Instant::now() + MOCK_LIFETIME;
+    while !condition() {
+        assert!(Instant::now() < deadline, "조건을 기다리다 시간 초과");
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+// 가상 시리얼 포트 쌍의 장치 쪽을 연 관리자와 컨트롤러 쪽 포트
+#[cfg(unix)]
+pub fn pty_manager() -> (SerialPortManager, TTYPort) {
+    let (controller, device) = TTYPort::pair().unwrap();
+    let manager = SerialPortManager::new();
+    manager
+        .initialize(&device.name().unwrap(), 115_200)
+        .unwrap();
+    (manager, controller)
+}
+
+// 컨트롤러 쪽 포트가 `count`바이트 이상 받을 때까지 읽어 반환
+#[cfg(unix)]
+pub fn pty_received(controller: &mut TTYPort, count: usize) -> Vec<u8> {
+    controller.set_timeout(Duration::from_millis(20)).unwrap();
+    let deadline = Instant::now() + MOCK_LIFETIME;
+    let mut all = Vec::new();
+    while all.len() < count {
+        assert!(
+            Instant::now() < deadline,
+            "{}바이트를 기다렸지만 {}바이트만 수신",
+            count,
+            all.len()
+        );
+        let mut buf = [0u8; 256];
+        if let Ok(n) = controller.read(&mut buf) {
+            all.extend(&buf[..n]);
+        }
+    }
+    all
+}
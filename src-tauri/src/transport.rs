@@ -0,0 +1,172 @@
+// src-tauri/transport.rs
+
+use serialport::SerialPort;
+#[cfg(feature = "tcp")]
+use std::io::ErrorKind;
+use std::io::{self, Read, Write};
+#[cfg(feature = "tcp")]
+use std::net::{TcpStream, ToSocketAddrs};
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+// 전송 계층 트레이트 정의 (시리얼 포트, TCP 소켓 공통)
+pub trait Transport: Read + Write + Send {
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()>;
+
+    fn timeout(&self) -> Duration;
+
+    // 시리얼 포트 전용 기능 접근 (시리얼 포트가 아니면 None)
+    fn as_serial(&mut self) -> Option<&mut dyn SerialPort> {
+        None
+    }
+}
+
+// 시리얼 포트 전송 계층
+impl Transport for Box<dyn SerialPort> {
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()> {
+        SerialPort::set_timeout(self.as_mut(), timeout).map_err(io::Error::from)
+    }
+
+    fn timeout(&self) -> Duration {
+        SerialPort::timeout(self.as_ref())
+    }
+
+    fn as_serial(&mut self) -> Option<&mut dyn SerialPort> {
+        Some(self.as_mut())
+    }
+}
+
+// TCP 소켓 전송 계층 (시리얼-이더넷 브리지 등)
+#[cfg(feature = "tcp")]
+pub struct TcpTransport {
+    stream: TcpStream,
+}
+
+#[cfg(feature = "tcp")]
+impl TcpTransport {
+    // TCP 연결 함수
+    pub fn connect(addr: &str, timeout: Duration) -> Result<Self, String> {
+        let addrs = addr
+            .to_socket_addrs()
+            .map_err(|e| format!("주소를 해석할 수 없습니다 ({}): {}", addr, e))?;
+
+        let mut last_error = None;
+        for socket_addr in addrs {
+            match TcpStream::connect_timeout(&socket_addr, timeout) {
+                Ok(stream) => {
+                    stream
+                        .set_nodelay(true)
+                        .map_err(|e| format!("TCP 소켓 설정 실패: {}", e))?;
+                    return Ok(Self { stream });
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(match last_error {
+            Some(e) if e.kind() == ErrorKind::ConnectionRefused => {
+                format!("연결이 거부되었습니다 ({})", addr)
+            }
+            Some(e) if e.kind() == ErrorKind::TimedOut => {
+                format!("연결 시간이 초과되었습니다 ({})", addr)
+            }
+            Some(e) => format!("TCP 연결 실패 ({}): {}", addr, e),
+            None => format!("주소를 해석할 수 없습니다 ({})", addr),
+        })
+    }
+}
+
+#[cfg(feature = "tcp")]
+impl Read for TcpTransport {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // 플랫폼별 읽기 타임아웃(WouldBlock)을 시리얼 포트와 같은 TimedOut으로 통일
+        match self.stream.read(buf) {
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                Err(io::Error::new(ErrorKind::TimedOut, e))
+            }
+            // 원격 종료는 타임아웃이 아닌 연결 오류로 보고
+            Ok(0) if !buf.is_empty() => Err(io::Error::new(
+                ErrorKind::ConnectionAborted,
+                "TCP 연결이 종료되었습니다.",
+            )),
+            result => result,
+        }
+    }
+}
+
+#[cfg(feature = "tcp")]
+impl Write for TcpTransport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+#[cfg(feature = "tcp")]
+impl Transport for TcpTransport {
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()> {
+        self.stream.set_read_timeout(Some(timeout))?;
+        self.stream.set_write_timeout(Some(timeout))
+    }
+
+    fn timeout(&self) -> Duration {
+        self.stream
+            .read_timeout()
+            .ok()
+            .flatten()
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+// 호출 단위 타임아웃 가드 (drop 시 이전 타임아웃 복원)
+pub struct TimeoutGuard<'a> {
+    transport: &'a mut (dyn Transport + 'static),
+    previous: Option<Duration>,
+}
+
+impl<'a> TimeoutGuard<'a> {
+    // timeout이 None이면 기존 타임아웃 그대로 사용
+    pub fn new(
+        transport: &'a mut (dyn Transport + 'static),
+        timeout: Option<Duration>,
+    ) -> io::Result<Self> {
+        let previous = match timeout {
+            Some(timeout) => {
+                let previous = transport.timeout();
+                transport.set_timeout(timeout)?;
+                Some(previous)
+            }
+            None => None,
+        };
+        Ok(Self {
+            transport,
+            previous,
+        })
+    }
+}
+
+impl Deref for TimeoutGuard<'_> {
+    type Target = dyn Transport + 'static;
+
+    fn deref(&self) -> &Self::Target {
+        self.transport
+    }
+}
+
+impl DerefMut for TimeoutGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.transport
+    }
+}
+
+impl Drop for TimeoutGuard<'_> {
+    fn drop(&mut self) {
+        // 오류 경로에서도 원래 타임아웃 복원
+        if let Some(previous) = self.previous {
+            let _ = self.transport.set_timeout(previous);
+        }
+    }
+}